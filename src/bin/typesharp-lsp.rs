@@ -0,0 +1,8 @@
+//! The TypeSharp language server, it speaks LSP over stdin and stdout.
+use std::io;
+
+fn main() {
+	let stdin = io::stdin();
+	let stdout = io::stdout();
+	std::process::exit(typesharp::lsp::run(stdin.lock(), stdout.lock()));
+}
@@ -0,0 +1,8 @@
+//! The TypeSharp command line, see `typesharp --help`.
+use std::io;
+
+fn main() {
+	let args: Vec<String> = std::env::args().skip(1).collect();
+	let code: i32 = typesharp::cli::main(&args, &mut io::stdout().lock(), &mut io::stderr().lock());
+	std::process::exit(code);
+}
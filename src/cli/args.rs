@@ -0,0 +1,219 @@
+use crate::compiler::typesharp_feature::Feature;
+use std::fmt;
+
+pub const USAGE: &str = "\
+Usage: typesharp <command> [options] <files...>
+
+Commands:
+    lex      Print the tokens of each file
+    parse    Check that each file parses
+    check    Check each file for errors, without building it
+    build    Compile each file (not supported yet, the files are checked and it exits with 3)
+    run      Compile and run a file (not supported yet, the file is checked and it exits with 3)
+    fmt      Format each file in place
+    migrate  Rename identifiers that are keywords in a later edition, in place
+    dump-tokens
+             Print the keyword table and the tokens of each file as JSON or YAML
+    features List every feature that can be enabled, and whether it is stable
+
+Options:
+    --error-format <human|json>    How diagnostics are printed (default: human)
+    --color <auto|always|never>    Whether diagnostics are colored (default: auto)
+    --check                        With `fmt`, list files that are not formatted instead of writing them
+    --format <json|yaml>           With `dump-tokens`, the format to print (default: json)
+    --dump-ast[=<tree|sexpr>]      With `parse`, print the syntax tree of each file (default: tree)
+    --feature <name>               Enable an unstable feature in every file, can be given more than once
+    -h, --help                     Print this message
+    -V, --version                  Print the version of typesharp
+";
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Command {
+	Lex,
+	Parse,
+	Check,
+	Build,
+	Run,
+	Fmt,
+	Migrate,
+	DumpTokens,
+	Features,
+	Help,
+	Version,
+}
+
+impl Command {
+	pub fn from_name(name: &str) -> Option<Command> {
+		return Some(match name {
+			"lex" => Command::Lex,
+			"parse" => Command::Parse,
+			"check" => Command::Check,
+			"build" => Command::Build,
+			"run" => Command::Run,
+			"fmt" => Command::Fmt,
+			"migrate" => Command::Migrate,
+			"dump-tokens" => Command::DumpTokens,
+			"features" => Command::Features,
+			"help" => Command::Help,
+			_ => return None,
+		});
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ErrorFormat {
+	/// Rendered with source snippets, for people.
+	Human,
+	/// One JSON object per line, for tools.
+	Json,
+}
+
+/// The format `dump-tokens` prints in.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DumpFormat {
+	Json,
+	Yaml,
+}
+
+/// How `parse --dump-ast` prints the syntax tree.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AstFormat {
+	/// One node per line, indented.
+	Tree,
+	/// A single S-expression per file.
+	Sexpr,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Color {
+	Auto,
+	Always,
+	Never,
+}
+
+/// The parsed command line.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Options {
+	pub command: Command,
+	pub files: Vec<String>,
+	pub error_format: ErrorFormat,
+	pub color: Color,
+	pub format: DumpFormat,
+	pub dump_ast: Option<AstFormat>,
+	/// `fmt --check`
+	pub check: bool,
+	/// Features enabled with `--feature`
+	pub features: Vec<Feature>,
+}
+
+/// Why the command line could not be parsed.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ArgsError(pub String);
+
+impl fmt::Display for ArgsError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		return f.write_str(&self.0);
+	}
+}
+
+impl Options {
+	/// Parses the arguments given to the binary, not including the binary's name.
+	pub fn parse(args: &[String]) -> Result<Options, ArgsError> {
+		let mut options: Options = Options {
+			command: Command::Help,
+			files: Vec::new(),
+			error_format: ErrorFormat::Human,
+			color: Color::Auto,
+			format: DumpFormat::Json,
+			dump_ast: None,
+			check: false,
+			features: Vec::new(),
+		};
+
+		let mut command: Option<Command> = None;
+		let mut args = args.iter();
+		while let Some(arg) = args.next() {
+			// `--flag value` and `--flag=value` are both allowed
+			let (flag, inline): (&str, Option<&str>) = match arg.split_once('=') {
+				Some((flag, value)) if arg.starts_with("--") => (flag, Some(value)),
+				_ => (arg.as_str(), None),
+			};
+
+			match flag {
+				"-h" | "--help" => return Ok(Options { command: Command::Help, ..options }),
+				"-V" | "--version" => return Ok(Options { command: Command::Version, ..options }),
+				"--check" => options.check = true,
+				"--error-format" => {
+					options.error_format = match value(flag, inline, &mut args)? {
+						"human" => ErrorFormat::Human,
+						"json" => ErrorFormat::Json,
+						other => return Err(ArgsError(format!("unknown error format `{}`, expected `human` or `json`", other))),
+					};
+				},
+				// the format is optional, so it has to be given with `=`
+				"--dump-ast" => {
+					options.dump_ast = Some(match inline {
+						None | Some("tree") => AstFormat::Tree,
+						Some("sexpr") => AstFormat::Sexpr,
+						Some(other) => return Err(ArgsError(format!("unknown ast format `{}`, expected `tree` or `sexpr`", other))),
+					});
+				},
+				"--format" => {
+					options.format = match value(flag, inline, &mut args)? {
+						"json" => DumpFormat::Json,
+						"yaml" => DumpFormat::Yaml,
+						other => return Err(ArgsError(format!("unknown format `{}`, expected `json` or `yaml`", other))),
+					};
+				},
+				"--feature" => {
+					let name: &str = value(flag, inline, &mut args)?;
+					match Feature::from_name(name) {
+						Some(feature) => options.features.push(feature),
+						None => return Err(ArgsError(format!("unknown feature `{}`, see `typesharp features`", name))),
+					}
+				},
+				"--color" => {
+					options.color = match value(flag, inline, &mut args)? {
+						"auto" => Color::Auto,
+						"always" => Color::Always,
+						"never" => Color::Never,
+						other => return Err(ArgsError(format!("unknown color `{}`, expected `auto`, `always` or `never`", other))),
+					};
+				},
+				flag if flag.starts_with('-') && flag.len() > 1 => {
+					return Err(ArgsError(format!("unknown option `{}`", flag)));
+				},
+				name if command.is_none() => match Command::from_name(name) {
+					Some(c) => command = Some(c),
+					None => return Err(ArgsError(format!("unknown command `{}`", name))),
+				},
+				file => options.files.push(file.to_string()),
+			}
+		}
+
+		options.command = command.unwrap_or(Command::Help);
+		if options.check && options.command != Command::Fmt {
+			return Err(ArgsError(String::from("`--check` can only be used with `fmt`")));
+		}
+		if options.dump_ast.is_some() && options.command != Command::Parse {
+			return Err(ArgsError(String::from("`--dump-ast` can only be used with `parse`")));
+		}
+		// the keyword table can be dumped without any files
+		if options.files.is_empty() && !matches!(options.command, Command::Help | Command::Version | Command::DumpTokens | Command::Features) {
+			return Err(ArgsError(String::from("no input files")));
+		}
+		if options.command == Command::Run && options.files.len() > 1 {
+			return Err(ArgsError(String::from("`run` takes a single file")));
+		}
+
+		return Ok(options);
+	}
+}
+
+/// Gets the value of a flag, either after its `=` or as the next argument.
+fn value<'a>(flag: &str, inline: Option<&'a str>, args: &mut impl Iterator<Item = &'a String>) -> Result<&'a str, ArgsError> {
+	return match inline {
+		Some(value) => Ok(value),
+		None => args.next().map(|a| a.as_str()).ok_or_else(|| ArgsError(format!("`{}` needs a value", flag))),
+	};
+}
@@ -0,0 +1,50 @@
+use crate::compiler::typesharp_ast::Edition;
+use crate::util::Json;
+use std::fs;
+use std::io;
+use std::path::{ Path, PathBuf };
+
+/// The name of a package's manifest, it applies to every file in its directory and the directories below it.
+pub const MANIFEST: &str = "typesharp.json";
+
+/// A package's manifest, eg: `{ "edition": "2024" }`
+#[derive(Clone, PartialEq, Debug)]
+pub struct Manifest {
+	/// The edition of every file in the package, unless a file chooses its own with `@edition`.
+	pub edition: Option<Edition>,
+}
+
+impl Manifest {
+	/// Finds the manifest of the package `file` is in, looking in its directory and then each directory above it.
+	pub fn find(file: &Path) -> Option<PathBuf> {
+		let file: PathBuf = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
+		return file.ancestors().skip(1).map(|dir| dir.join(MANIFEST)).find(|path| path.is_file());
+	}
+
+	/// Reads and parses the manifest at `path`.
+	pub fn load(path: &Path) -> io::Result<Manifest> {
+		let text: String = fs::read_to_string(path)
+			.map_err(|e| io::Error::new(e.kind(), format!("could not read `{}`: {}", path.display(), e)))?;
+		return Manifest::parse(&text).map_err(|e| io::Error::other(format!("invalid manifest `{}`: {}", path.display(), e)));
+	}
+
+	pub fn parse(text: &str) -> Result<Manifest, String> {
+		let json: Json = Json::parse(text).map_err(|e| e.to_string())?;
+		if !matches!(json, Json::Object(_)) {
+			return Err(String::from("expected an object"));
+		}
+
+		let edition: Option<Edition> = match json.get("edition") {
+			None => None,
+			Some(Json::String(name)) => match Edition::from_name(name) {
+				Some(edition) => Some(edition),
+				None => return Err(format!("unknown edition `{}`, the editions are {}", name, Edition::expected())),
+			},
+			Some(_) => return Err(format!("`edition` has to be a string, eg: `\"{}\"`", Edition::LATEST)),
+		};
+
+		return Ok(Manifest {
+			edition: edition,
+		});
+	}
+}
@@ -0,0 +1,300 @@
+pub mod args;
+pub mod manifest;
+
+pub use self::args::{ ArgsError, AstFormat, Color, Command, DumpFormat, ErrorFormat, Options, USAGE };
+pub use self::manifest::{ Manifest, MANIFEST };
+use crate::compiler::{
+	typesharp_ast::{ Edition, FileId, SourceFile, SourceMap, TextEdit, ToTree },
+	typesharp_cst::format,
+	typesharp_driver::{ compile_front_end, parse_front_end },
+	typesharp_feature::{ Feature, Features },
+	typesharp_lexer::{ apply_edition, dump, edition_directive, edition_keywords, tokenize_file, Token, TokenKind },
+};
+use crate::error::{ codes, Diagnostic, DiagnosticSink, JsonEmitter, Renderer };
+use crate::util::{ to_yaml, Json };
+use std::io::{ IsTerminal, Write };
+use std::path::{ Path, PathBuf };
+
+/// Codes the `typesharp` binary exits with.
+pub mod exit {
+	pub const SUCCESS: i32 = 0;
+	/// The code had errors, or a file could not be read or written.
+	pub const FAILURE: i32 = 1;
+	/// The command line was not valid.
+	pub const USAGE: i32 = 2;
+	/// The command is not supported by this version of typesharp yet.
+	pub const UNSUPPORTED: i32 = 3;
+}
+
+/// The extension of TypeSharp source files.
+pub const EXTENSION: &str = "t#";
+
+/// Runs the `typesharp` command line, `args` does not include the binary's name.
+/// Results are written to `out` and diagnostics to `err`, the exit code is returned.
+pub fn main(args: &[String], out: &mut dyn Write, err: &mut dyn Write) -> i32 {
+	let options: Options = match Options::parse(args) {
+		Ok(options) => options,
+		Err(e) => {
+			let _ = writeln!(err, "error: {}\n\n{}", e, USAGE);
+			return exit::USAGE;
+		},
+	};
+
+	let result: std::io::Result<i32> = match options.command {
+		Command::Help => write!(out, "{}", USAGE).map(|_| exit::SUCCESS),
+		Command::Version => writeln!(out, "typesharp {}", env!("CARGO_PKG_VERSION")).map(|_| exit::SUCCESS),
+		Command::Features => list_features(out),
+		_ => Driver::new(&options, err).and_then(|mut driver| driver.run(out)),
+	};
+
+	return match result {
+		Ok(code) => code,
+		Err(e) => {
+			let _ = writeln!(err, "error: {}", e);
+			exit::FAILURE
+		},
+	};
+}
+
+/// Prints each feature, whether it is stable and what it allows.
+fn list_features(out: &mut dyn Write) -> std::io::Result<i32> {
+	for feature in Feature::ALL.iter() {
+		writeln!(out, "{}\t{}\t{}", feature.name(), feature.status(), feature.description())?;
+	}
+
+	return Ok(exit::SUCCESS);
+}
+
+/// Runs a command over every file given on the command line.
+struct Driver<'a> {
+	options: &'a Options,
+	files: SourceMap,
+	/// The edition of each file from its package's manifest, by `FileId`, a file's `@edition` directive takes precedence.
+	editions: Vec<Edition>,
+	err: &'a mut dyn Write,
+}
+
+impl<'a> Driver<'a> {
+	/// Loads every file and the manifest of its package,
+	/// failing if any of them is not a `.t#` file or can not be read.
+	fn new(options: &'a Options, err: &'a mut dyn Write) -> std::io::Result<Self> {
+		let mut files: SourceMap = SourceMap::new();
+		let mut editions: Vec<Edition> = Vec::new();
+		let mut manifests: Vec<(PathBuf, Manifest)> = Vec::new();
+		for path in options.files.iter() {
+			if !path.ends_with(&format!(".{}", EXTENSION)) {
+				return Err(std::io::Error::other(format!("`{}` is not a TypeSharp file, expected a `.{}` extension", path, EXTENSION)));
+			}
+
+			files.load(path).map_err(|e| std::io::Error::new(e.kind(), format!("could not read `{}`: {}", path, e)))?;
+
+			// files in the same package share a manifest, so each one is only read once
+			let edition: Option<Edition> = match Manifest::find(Path::new(path)) {
+				Some(found) => match manifests.iter().find(|(p, _)| *p == found) {
+					Some((_, manifest)) => manifest.edition,
+					None => {
+						let manifest: Manifest = Manifest::load(&found)?;
+						let edition: Option<Edition> = manifest.edition;
+						manifests.push((found, manifest));
+						edition
+					},
+				},
+				None => None,
+			};
+			editions.push(edition.unwrap_or_default());
+		}
+
+		return Ok(Driver {
+			options: options,
+			files: files,
+			editions: editions,
+			err: err,
+		});
+	}
+
+	fn run(&mut self, out: &mut dyn Write) -> std::io::Result<i32> {
+		let ids: Vec<FileId> = self.files.files().map(|f| f.id()).collect();
+		let mut diagnostics: DiagnosticSink = DiagnosticSink::new();
+
+		match self.options.command {
+			Command::Lex => {
+				for id in ids {
+					let tokens: Vec<Token> = self.lex(id, &mut diagnostics);
+					for token in tokens.iter().filter(|t| t.kind != TokenKind::WhiteSpace) {
+						writeln!(out, "{}\t{:?}\t{:?}", self.files.location(token.span), token.kind, self.files.snippet(token.span).unwrap_or(""))?;
+					}
+				}
+			},
+			Command::Parse | Command::Check | Command::Build | Command::Run => {
+				let features: Features = self.features();
+				for id in ids {
+					let (tokens, edition) = self.source(id, &mut diagnostics);
+					let (module, mut found) = match self.options.command {
+						Command::Parse => parse_front_end(tokens, edition, &features),
+						_ => {
+							let (module, _, _, found) = compile_front_end(tokens, edition, &features);
+							(module, found)
+						},
+					};
+					diagnostics.append(&mut found);
+
+					// the tree is printed even if there were errors, to show how the parser recovered
+					match self.options.dump_ast {
+						Some(AstFormat::Tree) => write!(out, "{}", module.to_tree().indented())?,
+						Some(AstFormat::Sexpr) => writeln!(out, "{}", module.sexpr())?,
+						None => (),
+					}
+				}
+
+				if !diagnostics.has_errors() && matches!(self.options.command, Command::Build | Command::Run) {
+					self.emit(&diagnostics)?;
+					let name: &str = if self.options.command == Command::Build { "build" } else { "run" };
+					writeln!(self.err, "error: `{}` is not supported yet, typesharp can not generate code", name)?;
+					return Ok(exit::UNSUPPORTED);
+				}
+			},
+			Command::DumpTokens => {
+				let mut files: Vec<Json> = Vec::new();
+				for id in ids {
+					let file: &SourceFile = self.files.get(id).unwrap();
+					let (tokens, mut found) = tokenize_file(file);
+					files.push(Json::object(vec![
+						("file", Json::from(file.name())),
+						("tokens", dump::tokens_to_json(&tokens, file.source())),
+					]));
+					diagnostics.append(&mut found);
+				}
+
+				let document: Json = Json::object(vec![("keywords", dump::keywords_to_json()), ("files", Json::Array(files))]);
+				match self.options.format {
+					DumpFormat::Json => writeln!(out, "{}", document.pretty())?,
+					DumpFormat::Yaml => write!(out, "{}", to_yaml(&document))?,
+				}
+			},
+			Command::Fmt => return self.fmt(out),
+			Command::Migrate => return self.migrate(out),
+			Command::Help | Command::Version | Command::Features => (),
+		}
+
+		self.emit(&diagnostics)?;
+		return Ok(if diagnostics.has_errors() { exit::FAILURE } else { exit::SUCCESS });
+	}
+
+	/// Lexes a file and chooses its edition, by the file's `@edition` directive, then its package's manifest.
+	fn source(&self, id: FileId, diagnostics: &mut DiagnosticSink) -> (Vec<Token>, Edition) {
+		let file: &SourceFile = self.files.get(id).unwrap();
+		let (tokens, mut found) = tokenize_file(file);
+		diagnostics.append(&mut found);
+
+		let (chosen, mut found) = edition_directive(&tokens);
+		diagnostics.append(&mut found);
+		return (tokens, chosen.unwrap_or(self.editions[id.as_u32() as usize]));
+	}
+
+	/// Lexes a file in its edition, keywords that are not reserved in it are lexed as identifiers.
+	fn lex(&self, id: FileId, diagnostics: &mut DiagnosticSink) -> Vec<Token> {
+		let (mut tokens, edition) = self.source(id, diagnostics);
+		apply_edition(&mut tokens, edition);
+		diagnostics.append(&mut edition_keywords(&tokens, edition));
+		return tokens;
+	}
+
+	/// The features enabled with `--feature`, files can enable more with `@compiler(...)`.
+	fn features(&self) -> Features {
+		let mut enabled: Features = Features::new();
+		for feature in self.options.features.iter() {
+			enabled.enable(*feature);
+		}
+		return enabled;
+	}
+
+	/// Formats every file in place, or with `--check` lists the files that would change.
+	fn fmt(&mut self, out: &mut dyn Write) -> std::io::Result<i32> {
+		let mut code: i32 = exit::SUCCESS;
+
+		for file in self.files.files() {
+			let formatted: String = format(file.source());
+			if formatted == file.source() {
+				continue;
+			}
+
+			if self.options.check {
+				writeln!(out, "{}", file.name())?;
+				code = exit::FAILURE;
+			} else {
+				std::fs::write(file.name(), formatted)
+					.map_err(|e| std::io::Error::new(e.kind(), format!("could not write `{}`: {}", file.name(), e)))?;
+			}
+		}
+
+		return Ok(code);
+	}
+
+	/// Renames every identifier that is a keyword in a later edition, so the files can move to the latest edition.
+	/// The edition itself is not changed, that is left to the `@edition` directive or the manifest.
+	fn migrate(&mut self, out: &mut dyn Write) -> std::io::Result<i32> {
+		let ids: Vec<FileId> = self.files.files().map(|f| f.id()).collect();
+		let mut diagnostics: DiagnosticSink = DiagnosticSink::new();
+
+		for id in ids {
+			let mut found: DiagnosticSink = DiagnosticSink::new();
+			self.lex(id, &mut found);
+			if found.has_errors() {
+				diagnostics.append(&mut found);
+				continue;
+			}
+
+			// applied from the end of the file, so the ranges of the edits before it stay the same
+			let mut edits: Vec<TextEdit> = found
+				.iter()
+				.filter(|d| d.code == Some(codes::EDITION_KEYWORD))
+				.flat_map(|d| d.suggestions.iter())
+				.map(|s| TextEdit::new(s.span.byte_range(), s.replacement.clone()))
+				.collect();
+			if edits.is_empty() {
+				continue;
+			}
+			edits.sort_by_key(|e| std::cmp::Reverse(e.range.start));
+
+			let file: &SourceFile = self.files.get(id).unwrap();
+			let mut source: String = file.source().to_string();
+			for edit in edits.iter() {
+				source = edit.apply(&source).unwrap_or(source);
+			}
+			std::fs::write(file.name(), source)
+				.map_err(|e| std::io::Error::new(e.kind(), format!("could not write `{}`: {}", file.name(), e)))?;
+
+			let plural: &str = if edits.len() == 1 { "" } else { "s" };
+			writeln!(out, "{}: renamed {} identifier{}, it can now use the {} edition", file.name(), edits.len(), plural, Edition::LATEST)?;
+		}
+
+		self.emit(&diagnostics)?;
+		return Ok(if diagnostics.has_errors() { exit::FAILURE } else { exit::SUCCESS });
+	}
+
+	fn emit(&mut self, diagnostics: &DiagnosticSink) -> std::io::Result<()> {
+		if diagnostics.is_empty() {
+			return Ok(());
+		}
+
+		match self.options.error_format {
+			ErrorFormat::Json => write!(self.err, "{}", JsonEmitter::new(&self.files).emit_all(diagnostics.iter()))?,
+			ErrorFormat::Human => {
+				let color: bool = match self.options.color {
+					Color::Always => true,
+					Color::Never => false,
+					Color::Auto => std::io::stderr().is_terminal(),
+				};
+				writeln!(self.err, "{}", Renderer::new(&self.files).colored(color).render_all(diagnostics.iter()))?;
+
+				let errors: usize = diagnostics.error_count();
+				if errors > 0 {
+					let plural: &str = if errors == 1 { "" } else { "s" };
+					writeln!(self.err, "error: aborting due to {} previous error{}", errors, plural)?;
+				}
+			},
+		}
+
+		return Ok(());
+	}
+}
@@ -1,3 +1,8 @@
 pub mod typesharp_ast;
+pub mod typesharp_check;
+pub mod typesharp_cst;
+pub mod typesharp_driver;
+pub mod typesharp_feature;
 pub mod typesharp_lexer;
 pub mod typesharp_parser;
+pub mod typesharp_resolve;
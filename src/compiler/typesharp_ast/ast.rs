@@ -3,55 +3,251 @@
 use super::types;
 use super::util::position;
 use super::node::NodeId;
+use super::keyword::KeyWord;
 use crate::{ compiler::typesharp_parser as parser };
-use crate::compiler::typesharp_lexer::token::Token;
+use crate::compiler::typesharp_lexer::token::{ Token, Numeric };
 
+#[derive(Clone, PartialEq, Debug)]
 pub struct Identifier {
+	pub name: String,
 	pub loc: position::Span,
 	pub tokens: Option<Vec<Token>>
 }
 
+/// A single parsed source file.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Module {
+	pub id: NodeId,
+	pub directives: Vec<Directive>,
+	pub statements: Vec<Statement>,
+	pub loc: position::Span
+}
+
+/// An instruction to the compiler, written at the top level of a file.
+/// EG: `@compiler(numericTypes);`
+#[derive(Clone, PartialEq, Debug)]
+pub struct Directive {
+	pub id: NodeId,
+	/// The name after the `@`, eg: `compiler`
+	pub name: Identifier,
+	/// The names or numbers in the parentheses, eg: `numericTypes`
+	pub args: Vec<Identifier>,
+	pub loc: position::Span
+}
+
+#[derive(Clone, PartialEq, Debug)]
 pub enum StatementKind {
-	Constant(Constant),
+	/// A constant declaration eg: `const x: u8 = 0;`
+	Constant(Local),
+	/// A mutable declaration eg: `let x: u8 = 0;`
+	Let(Local),
+	/// A named function eg: `function foo(a: u8): u8 {}`
+	Function(Function),
+	/// `if x {} else {}`, the else branch is either another `if` or a `Scope`
+	If(Expression, Block, Option<Box<Statement>>),
+	/// `while x {}`
+	While(Expression, Block),
+	/// `for let x in y {}` or `for let x of y {}`
+	For(Box<ForLoop>),
+	/// `return x;`
+	Return(Option<Expression>),
+	Break,
+	Continue,
 	Item,
-	Expression,
+	Expression(Expression),
 	Label,
-	Scope,
+	/// A block of statements eg: `{ let x = 0; }`
+	Scope(Block),
 	TypeDeclaration(types::Type),
-	Class(Constant)
+	/// A class eg: `class Foo extends Bar {}`
+	Class(Class),
+	/// An enum eg: `enum Color { Red, Green }`
+	Enum(Enum),
+	/// The package the statements after it are in, up to the next package eg: `package geometry.shapes;`
+	/// Each name in the path is a package, `shapes` is in `geometry`.
+	Package(Vec<PackageName>)
 }
 
+#[derive(Clone, PartialEq, Debug)]
 pub enum ExpressionKind {
 	/// An array of any expression
 	Array(Vec<Expression>),
 	/// Private context
 	Scope,
-	/// A call to a function or reference to a signature
-	Function,
+	/// A function expression eg: `fn _() {}`
+	Function(Box<Function>),
+	/// A call to a function eg: `foo(a, b)`
+	Call(Box<Expression>, Vec<Expression>),
+	/// An instance of a class eg: `new Foo(a, b)`
+	New(Box<Expression>, Vec<Expression>),
 	/// A method call eg: `foo.bar(a, b)`
 	///
+	/// The `Expression` here represents the receiver, `foo`
 	/// The `Identifier` here represents the Name of the method being called
 	/// `Vec<Expression>` represents the arguments given to the expression
-	Method(Identifier, Vec<Expression>, position::Span)
+	Method(Box<Expression>, Identifier, Vec<Expression>, position::Span),
+	/// A field access eg: `foo.bar`
+	Field(Box<Expression>, Identifier),
+	/// An index eg: `foo[0]`
+	Index(Box<Expression>, Box<Expression>),
+	/// A reference to a name eg: `foo`
+	Identifier(Identifier),
+	/// Any literal eg: `"foo"` or `10`
+	Literal(Literal),
+	/// An expression wrapped in parenthesis eg: `(foo)`
+	Paren(Box<Expression>),
+	/// A binary operation eg: `a + b`
+	Binary(parser::BinOp, Box<Expression>, Box<Expression>),
+	/// A prefix or postfix operation eg: `-a` or `a++`
+	Unary(parser::UnaryOp, Box<Expression>),
+	/// An assignment eg: `a = b` or `a += b`
+	Assign(parser::AssignmentOp, Box<Expression>, Box<Expression>),
+	/// A logical operation eg: `a && b`
+	Logical(parser::LogicalOp, Box<Expression>, Box<Expression>),
+	/// A comparison eg: `a == b` or `a instanceof b`
+	Compare(parser::ComparisonOp, Box<Expression>, Box<Expression>),
+	/// A template string eg: `` `a ${b}` ``
+	Template(Vec<TemplatePart>),
+	/// A literal that could not be lexed, or an expression that could not be parsed, it has already been reported.
+	Err
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum TemplatePart {
+	String(String),
+	/// An expression inside of `${}`
+	Expression(Expression)
 }
 
+#[derive(Clone, PartialEq, Debug)]
+pub enum Literal {
+	Number(Numeric),
+	String(String),
+	Bool(bool)
+}
+
+#[derive(Clone, PartialEq, Debug)]
 pub struct Constant {
 	pub id: NodeId,
 	pub typ: types::Type
 }
 
+/// A binding made with `let` or `const`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Local {
+	pub id: NodeId,
+	pub name: Identifier,
+	pub typ: Option<types::Type>,
+	pub value: Option<Expression>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Function {
+	pub id: NodeId,
+	/// Function expressions may be anonymous, eg: `fn () {}`
+	pub name: Option<Identifier>,
+	/// The type parameters, `T` in `function id<T>(x: T): T {}`
+	pub generics: Vec<GenericParam>,
+	pub params: Vec<Param>,
+	pub ret: Option<types::Type>,
+	pub body: Block,
+	pub loc: position::Span
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Class {
+	pub id: NodeId,
+	pub name: Identifier,
+	/// The type parameters, `T` in `class Box<T> {}`
+	pub generics: Vec<GenericParam>,
+	/// The class being extended, `Bar` in `class Foo extends Bar {}`
+	pub extends: Option<types::Type>,
+	pub members: Vec<Member>,
+	pub loc: position::Span
+}
+
+/// A field or method of a class, eg: `private x: u8 = 0;` or `static foo() {}`
+#[derive(Clone, PartialEq, Debug)]
+pub struct Member {
+	pub id: NodeId,
+	/// Keywords before the member, eg: `public` or `static`
+	pub modifiers: Vec<KeyWord>,
+	pub kind: MemberKind,
+	pub loc: position::Span
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum MemberKind {
+	Field(Local),
+	Method(Function)
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Enum {
+	pub id: NodeId,
+	pub name: Identifier,
+	pub variants: Vec<Variant>,
+	pub loc: position::Span
+}
+
+/// A variant of an enum, with an optional value eg: `Green = 2`
+#[derive(Clone, PartialEq, Debug)]
+pub struct Variant {
+	pub id: NodeId,
+	pub name: Identifier,
+	pub value: Option<Expression>,
+	pub loc: position::Span
+}
+
+/// A name in the path of a `package` declaration, eg: `shapes` in `package geometry.shapes;`
+#[derive(Clone, PartialEq, Debug)]
+pub struct PackageName {
+	pub id: NodeId,
+	pub name: Identifier,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ForLoop {
+	/// The binding for each item, `x` in `for let x in y {}`
+	pub binding: Local,
+	/// Whether the loop is a `for..in` or a `for..of` loop
+	pub of: bool,
+	pub iter: Expression,
+	pub body: Block
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Block {
+	pub id: NodeId,
+	pub statements: Vec<Statement>,
+	pub loc: position::Span
+}
+
+#[derive(Clone, PartialEq, Debug)]
 pub struct Statement {
 	pub id: NodeId,
 	pub kind: StatementKind,
 	pub loc: position::Span
 }
 
+#[derive(Clone, PartialEq, Debug)]
 pub struct Param {
 	pub id: NodeId,
-	// pub val: Node
+	pub name: Identifier,
+	pub typ: Option<types::Type>,
+	pub loc: position::Span
+}
+
+/// A type parameter of a generic function or class.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GenericParam {
+	pub id: NodeId,
+	pub name: Identifier
 }
 
+#[derive(Clone, PartialEq, Debug)]
 pub struct Expression {
 	pub id: NodeId,
 	pub kind: ExpressionKind,
-}
\ No newline at end of file
+	pub loc: position::Span
+}
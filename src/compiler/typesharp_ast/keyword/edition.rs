@@ -0,0 +1,65 @@
+use super::KeyWord;
+use std::fmt;
+
+/// A version of the language, it decides which words are reserved as keywords.
+/// Newer editions can reserve more words, so code using them as names has to be migrated first.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Edition {
+	Edition2021,
+	Edition2024,
+}
+
+impl Edition {
+	/// Every edition, from oldest to newest.
+	pub const ALL: &'static [Edition] = &[Edition::Edition2021, Edition::Edition2024];
+
+	/// The edition of a file that does not choose one.
+	pub const DEFAULT: Edition = Edition::Edition2021;
+
+	pub const LATEST: Edition = Edition::Edition2024;
+
+	/// How the edition is written, eg: `2024`
+	pub fn name(&self) -> &'static str {
+		return match self {
+			Edition::Edition2021 => "2021",
+			Edition::Edition2024 => "2024",
+		};
+	}
+
+	/// The edition written as `name`, eg: `Edition2024` for `2024`
+	pub fn from_name(name: &str) -> Option<Edition> {
+		return Edition::ALL.iter().find(|e| e.name() == name).copied();
+	}
+
+	/// The edition after this one, if there is one.
+	pub fn next(&self) -> Option<Edition> {
+		return Edition::ALL.iter().find(|e| *e > self).copied();
+	}
+
+	/// Whether `keyword` is reserved in this edition, if it is not it can be used as a name.
+	pub fn is_reserved(&self, keyword: &KeyWord) -> bool {
+		return keyword.edition() <= *self;
+	}
+
+	/// The editions, written as they are in code, eg: "`2021` or `2024`"
+	pub fn expected() -> String {
+		let names: Vec<String> = Edition::ALL.iter().map(|e| format!("`{}`", e.name())).collect();
+		return match names.split_last() {
+			Some((last, [])) => last.clone(),
+			Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+			None => String::new(),
+		};
+	}
+}
+
+impl Default for Edition {
+	fn default() -> Self {
+		return Edition::DEFAULT;
+	}
+}
+
+impl fmt::Display for Edition {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		return f.write_str(self.name());
+	}
+}
@@ -1,111 +1,155 @@
+pub mod edition;
+
+pub use self::edition::Edition;
 use crate::compiler::typesharp_lexer::token::TokenValue;
-use std::{error, fmt};
+use std::{convert::TryFrom, error, fmt, str::FromStr};
+
+/// Declares `KeyWord` from a table of each keyword and how it is written, so that lexing,
+/// `as_str` and `from_str` can not disagree. Words after a `|` are other ways to write the keyword,
+/// and keywords that are only reserved from a later edition are marked with `in`.
+macro_rules! keywords {
+	(@edition) => { Edition::Edition2021 };
+	(@edition $edition:ident) => { Edition::$edition };
+	($( $(#[$attr:meta])* $variant:ident => $name:literal $(| $alias:literal)* $(in $edition:ident)?, )*) => {
+		/// These keywords are reserved, from the edition given by `KeyWord::edition`.
+		/// If you use these as identifiers you will be yelled at.
+		#[derive(Clone, PartialEq, Debug)]
+		pub enum KeyWord {
+			$( $(#[$attr])* $variant, )*
+		}
 
-/// These keywords are reserved.
-/// If you use these as identifiers you will be yelled at.
-#[derive(Clone, PartialEq, Debug)]
-pub enum KeyWord {
+		impl KeyWord {
+			/// Every keyword, in the order they are declared.
+			pub const ALL: &'static [KeyWord] = &[$( Self::$variant, )*];
+
+			pub fn as_str(&self) -> &'static str {
+				return match self {
+					$( Self::$variant => $name, )*
+				};
+			}
+
+			/// The first edition the keyword is reserved in, in earlier editions it is an identifier.
+			pub fn edition(&self) -> Edition {
+				return match self {
+					$( Self::$variant => keywords!(@edition $($edition)?), )*
+				};
+			}
+
+			/// The keyword written as `name`, eg: `Let` for `let`
+			#[allow(clippy::should_implement_trait)]
+			pub fn from_str(name: &str) -> Result<KeyWord, KeyWordError> {
+				return match name {
+					$( $name $(| $alias)* => Ok(Self::$variant), )*
+					_ => Err(KeyWordError(name.to_string())),
+				};
+			}
+		}
+	};
+}
+
+keywords! {
 	/// Keywords available in all versions of typesharp.
 	/// Asm, allows use for inline assembly
-	Asm,
+	Asm => "asm",
 
 	/// Await, used for synchronizing load.
-	Await,
+	Await => "await",
 
 	/// Async, allows for asynchronous programming
-	Async,
+	Async => "async",
 
 	/// As, used for type casting
-	As,
+	As => "as",
 
 	/// Used to break out of statements like "if" etc.
-	Break,
+	Break => "break",
 
-	///
-	Case,
+	/// Case, used in switch statements.
+	Case => "case",
 
 	/// Used in try...catch blocks, catches errors.
-	Catch,
+	Catch => "catch",
 
 	/// OOP, reserved word for class
-	Class,
+	Class => "class",
 
 	/// Continue, used in loops to continue the loop
-	Continue,
+	Continue => "continue",
 
 	/// Constants, non-mutable constant vars.
-	Const,
+	Const => "const",
 
 	/// Defaults, used in switch statements.
-	Default,
+	Default => "default",
 
 	/// Delete (may be removed), removes a var. (this may be removed due to memory safety)
 	/// See issue https:///github.com/TypeSharp/Typesharp/issues/1
-	Delete,
+	Delete => "delete",
 
 	/// Used in if...else blocks.
-	Else,
+	Else => "else",
 
 	/// Enums, mainly just syntax sugar
-	Enum,
+	Enum => "enum",
 
 	/// Exports module
 	/// THIS DOES NOT HAVE THE SAME USE CASE AS JS
 	/// Exporting a module makes it global.
-	Export,
+	Export => "export",
 
 	/// Creates an external function (used for FFI or dynlibs)
-	Extern,
+	/// May be written as "external", this may change in the future.
+	Extern => "extern" | "external",
 
 	/// Class inheritance for extending
-	Extends,
+	Extends => "extends",
 
 	/// False
-	False,
+	False => "false",
 
 	/// For loops, eg: for let i in blah {}
-	For,
+	For => "for",
 
 	/// Function keyword, makes a function
-	Function,
+	Function => "function",
 
 	/// Reserved macro for function
-	Fn,
+	Fn => "fn",
 
 	/// If keyword
-	If,
+	If => "if",
 
 	/// In, used in for..in loops
-	In,
+	In => "in",
 
 	/// Checks an instance of a given object.
-	InstanceOf,
+	InstanceOf => "instanceof",
 
 	/// Imports a public module
 	/// Use case is SIMILAR to JS
-	Import,
+	Import => "import",
 
 	/// Creates a mutable identifier.
 	/// Exactly equal to: let mut something: u8 = 0; in rust.
-	Let,
+	Let => "let",
 
 	/// Creates an instance of a class
-	New,
+	New => "new",
 
 	/// Used in for...of loops
-	Of,
+	Of => "of",
 
 	/// Creates a package. Similar to `mod` keyword in rust.
-	Package,
+	Package => "package",
 
 	/// Return
-	Return,
+	Return => "return",
 
 	/// Self, used in classes, and functions for getting "it's self"
-	SelfKeyword,
+	SelfKeyword => "self",
 
 	/// Static, used to create a static reference
-	Static,
+	Static => "static",
 
 	/// Super, accesses the parent class in typesharp
 	/// Eg:
@@ -118,13 +162,13 @@ pub enum KeyWord {
 	///     }
 	/// }
 	/// ```
-	Super,
+	Super => "super",
 
 	/// Switch, used to catch cases, maybe implement match?
-	Switch,
+	Switch => "switch",
 
 	/// True
-	True,
+	True => "true",
 
 	/// Traits, traits in typesharp are very similar to php
 	/// ```ts
@@ -147,176 +191,49 @@ pub enum KeyWord {
 	///   dog.talk("Woof!");
 	///   dog.getName(); // Dog
 	/// ```
-	Trait,
+	Trait => "trait",
 
 	/// An accessor for a class, access as "self" but not statically.
-	This,
+	This => "this",
 
 	/// Throws an error.
-	Throw,
+	Throw => "throw",
 
 	/// Type, declares a type
-	Type,
+	Type => "type",
 
 	/// Used in Try...catch loops
-	Try,
+	Try => "try",
 
 	/// Where, (Might implement)
-	Where,
+	Where => "where",
 
 	/// While loops.
-	While,
+	While => "while",
 
-	/// Abstractions
-	Abstract,
-	Become,
-	Do,
+	/// Abstractions, reserved for later use.
+	Abstract => "abstract" in Edition2024,
+	Become => "become" in Edition2024,
+	Do => "do" in Edition2024,
 
 	/// This is not for sure being implemented
 	/// Final classes and vars seem redundant
 	/// See issue: https://github.com/TypeSharp/Typesharp/issues/10
-	Final,
-	Finally,
-	Override,
-	Typeof,
-	Yield,
-	Public,
-	Pub,
-	Private,
-	Protected,
+	Final => "final",
+	Finally => "finally",
+	Override => "override",
+	Typeof => "typeof",
+	Yield => "yield" in Edition2024,
+	Public => "public",
+	/// Reserved in case it is used, the parser treats it as "public" for now.
+	Pub => "pub" in Edition2024,
+	Private => "private",
+	Protected => "protected",
 
 	/// Types
-	Union,
-	Implements,
-	Interface,
-}
-
-impl KeyWord {
-	pub fn as_str(&self) -> &'static str {
-		// To-Do
-		match self {
-			Self::Asm => "asm",
-			Self::Async => "async",
-			Self::Await => "await",
-			Self::As => "as",
-			Self::Become => "become",
-			Self::Break => "break",
-			Self::Case => "case",
-			Self::Catch => "catch",
-			Self::Class => "class",
-			Self::Continue => "continue",
-			Self::Const => "const",
-			Self::Default => "default",
-			Self::Delete => "delete",
-			Self::Else => "else",
-			Self::Enum => "enum",
-			Self::Extends => "extends",
-			Self::Export => "export",
-			Self::Extern => "extern",
-			Self::False => "false",
-			Self::For => "for",
-			Self::Function => "function",
-			Self::Fn => "fn",
-			Self::If => "if",
-			Self::In => "in",
-			Self::InstanceOf => "instanceof",
-			Self::Import => "import",
-			Self::Let => "let",
-			Self::New => "new",
-			Self::Of => "of",
-			Self::Package => "package",
-			Self::Return => "return",
-			Self::SelfKeyword => "self",
-			Self::Static => "static",
-			Self::Super => "super",
-			Self::Switch => "switch",
-			Self::Trait => "trait",
-			Self::True => "true",
-			Self::This => "this",
-			Self::Throw => "throw",
-			Self::Type => "type",
-			Self::Try => "try",
-			Self::Where => "where",
-			Self::While => "while",
-			Self::Abstract => "abstract",
-			Self::Do => "do",
-			Self::Final => "final",
-			Self::Finally => "finally",
-			Self::Override => "override",
-			Self::Typeof => "typeof",
-			Self::Yield => "yield",
-			Self::Public => "public",
-			Self::Pub => "pub",
-			Self::Private => "private",
-			Self::Protected => "protected",
-			Self::Union => "union",
-			Self::Implements => "implements",
-			Self::Interface => "interface",
-		}
-	}
-
-	pub fn from_str(name: &'static str) -> KeyWord {
-		match name {
-			"Asm" => Self::Asm,
-			"async" => Self::Async,
-			"await" => Self::Await,
-			"as" => Self::As,
-			"become" => Self::Become,
-			"break" => Self::Break,
-			"case" => Self::Case,
-			"catch" => Self::Catch,
-			"class" => Self::Class,
-			"continue" => Self::Continue,
-			"const" => Self::Const,
-			"default" => Self::Default,
-			"delete" => Self::Delete,
-			"else" => Self::Else,
-			"enum" => Self::Enum,
-			"extends" => Self::Extends,
-			"export" => Self::Export,
-			"extern" => Self::Extern,
-			"false" => Self::False,
-			"for" => Self::For,
-			"function" => Self::Function,
-			"fn" => Self::Fn,
-			"if" => Self::If,
-			"in" => Self::In,
-			"instanceof" => Self::InstanceOf,
-			"import" => Self::Import,
-			"let" => Self::Let,
-			"new" => Self::New,
-			"of" => Self::Of,
-			"package" => Self::Package,
-			"return" => Self::Return,
-			"self" => Self::SelfKeyword,
-			"static" => Self::Static,
-			"super" => Self::Super,
-			"switch" => Self::Switch,
-			"trait" => Self::Trait,
-			"true" => Self::True,
-			"this" => Self::This,
-			"throw" => Self::Throw,
-			"type" => Self::Type,
-			"try" => Self::Try,
-			"where" => Self::Where,
-			"while" => Self::While,
-			"abstract" => Self::Abstract,
-			"do" => Self::Do,
-			"final" => Self::Final,
-			"finally" => Self::Finally,
-			"override" => Self::Override,
-			"typeof" => Self::Typeof,
-			"yield" => Self::Yield,
-			"public" => Self::Public,
-			"pub" => Self::Pub,
-			"private" => Self::Private,
-			"protected" => Self::Protected,
-			"union" => Self::Union,
-			"implements" => Self::Implements,
-			"interface" => Self::Interface,
-			_ => panic!("Unknown Keyword!"),
-		}
-	}
+	Union => "union",
+	Implements => "implements",
+	Interface => "interface",
 }
 
 impl TokenValue<String> for KeyWord {
@@ -325,29 +242,34 @@ impl TokenValue<String> for KeyWord {
 	}
 }
 
-impl From<&'static str> for KeyWord {
-	fn from(m: &'static str) -> KeyWord {
+impl TryFrom<&str> for KeyWord {
+	type Error = KeyWordError;
+
+	fn try_from(m: &str) -> Result<KeyWord, KeyWordError> {
 		return KeyWord::from_str(m);
 	}
 }
 
-pub struct KeyWordError;
-impl fmt::Display for KeyWordError {
-	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-		return write!(f, "");
+impl FromStr for KeyWord {
+	type Err = KeyWordError;
+
+	fn from_str(name: &str) -> Result<KeyWord, KeyWordError> {
+		return KeyWord::from_str(name);
 	}
 }
 
-impl error::Error for KeyWord {
-	fn description(&self) -> &str {
-		return "Unknown Token";
-	}
+/// The error given when a word is not a keyword.
+#[derive(Clone, PartialEq, Debug)]
+pub struct KeyWordError(pub String);
 
-	fn cause(&self) -> Option<&dyn error::Error> {
-		return None;
+impl fmt::Display for KeyWordError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		return write!(f, "`{}` is not a keyword", self.0);
 	}
 }
 
+impl error::Error for KeyWordError {}
+
 impl fmt::Display for KeyWord {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		return fmt::Display::fmt(self.as_str(), f);
@@ -1,11 +1,14 @@
 pub mod ast;
 pub mod keyword;
+pub mod pretty;
 pub mod types;
 pub mod util;
 pub mod node;
 
 pub use self::{
-	keyword::{KeyWord, KeyWordError},
+	keyword::{Edition, KeyWord, KeyWordError},
+	pretty::{ToTree, Tree},
 	util::cursor::Cursor,
 	util::position::{Position, Span},
+	util::source_map::{FileId, SourceFile, SourceMap, TextEdit},
 };
@@ -1,7 +1,20 @@
+/// A unique identifier given to every node in the ast.
+/// Ids are handed out in order by the parser, and are unique per module.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 pub struct NodeId {
-	id: u16
+	id: u32
+}
+
+impl NodeId {
+	pub fn new(id: u32) -> Self {
+		return NodeId { id: id };
+	}
+
+	pub fn as_u32(&self) -> u32 {
+		return self.id;
+	}
 }
 
 pub struct Node {
 	
-}
\ No newline at end of file
+}
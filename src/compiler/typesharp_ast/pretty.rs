@@ -0,0 +1,342 @@
+// Dumps of the ast, used to inspect what the parser made of some code and for snapshot tests.
+use super::ast::*;
+use super::types::{ Type, TypeKinds };
+use std::fmt;
+
+/// A node of a dump, either an atom (eg: a name or a literal) or a list with a head.
+/// Spans and ids are left out, so a dump only changes when the meaning of the code does.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Tree {
+	Atom(String),
+	List(String, Vec<Tree>),
+}
+
+impl Tree {
+	pub fn atom(head: impl Into<String>) -> Self {
+		return Tree::Atom(head.into());
+	}
+
+	pub fn list(head: impl Into<String>, children: Vec<Tree>) -> Self {
+		return Tree::List(head.into(), children);
+	}
+
+	pub fn head(&self) -> &str {
+		return match self {
+			Tree::Atom(head) | Tree::List(head, _) => head,
+		};
+	}
+
+	pub fn children(&self) -> &[Tree] {
+		return match self {
+			Tree::Atom(_) => &[],
+			Tree::List(_, children) => children,
+		};
+	}
+
+	/// Writes the tree as an S-expression on a single line, eg: `(let x (+ 1 2))`
+	pub fn sexpr(&self) -> String {
+		return match self {
+			Tree::Atom(head) => head.clone(),
+			Tree::List(head, children) => {
+				let mut out: String = format!("({}", head);
+				for child in children.iter() {
+					out.push(' ');
+					out.push_str(&child.sexpr());
+				}
+				out.push(')');
+				out
+			},
+		};
+	}
+
+	/// Writes the tree with each node on its own line, children are indented by two spaces.
+	/// Atoms that come before any list are kept on their parent's line, eg:
+	/// ```text
+	/// let x
+	///   + 1 2
+	/// ```
+	pub fn indented(&self) -> String {
+		let mut out: String = String::new();
+		self.write_indented(&mut out, 0);
+		return out;
+	}
+
+	fn write_indented(&self, out: &mut String, depth: usize) {
+		out.push_str(&"  ".repeat(depth));
+		out.push_str(self.head());
+
+		let children: &[Tree] = self.children();
+		let inline: usize = children.iter().take_while(|c| matches!(c, Tree::Atom(_))).count();
+		for child in children[..inline].iter() {
+			out.push(' ');
+			out.push_str(child.head());
+		}
+		out.push('\n');
+
+		for child in children[inline..].iter() {
+			child.write_indented(out, depth + 1);
+		}
+	}
+}
+
+/// An ast node that can be dumped.
+pub trait ToTree {
+	fn to_tree(&self) -> Tree;
+
+	fn sexpr(&self) -> String {
+		return self.to_tree().sexpr();
+	}
+}
+
+impl ToTree for Module {
+	fn to_tree(&self) -> Tree {
+		let directives = self.directives.iter().map(|d| d.to_tree());
+		return Tree::list("module", directives.chain(self.statements.iter().map(|s| s.to_tree())).collect());
+	}
+}
+
+impl ToTree for Directive {
+	fn to_tree(&self) -> Tree {
+		return Tree::list(format!("@{}", self.name.name), self.args.iter().map(|a| Tree::atom(a.name.as_str())).collect());
+	}
+}
+
+impl ToTree for Block {
+	fn to_tree(&self) -> Tree {
+		return Tree::list("block", self.statements.iter().map(|s| s.to_tree()).collect());
+	}
+}
+
+impl ToTree for Statement {
+	fn to_tree(&self) -> Tree {
+		return match &self.kind {
+			StatementKind::Constant(local) => local_tree("const", local),
+			StatementKind::Let(local) => local_tree("let", local),
+			StatementKind::Function(function) => function.to_tree(),
+			StatementKind::If(condition, then, otherwise) => {
+				let mut children: Vec<Tree> = vec![condition.to_tree(), then.to_tree()];
+				if let Some(otherwise) = otherwise {
+					children.push(Tree::list("else", vec![otherwise.to_tree()]));
+				}
+				Tree::list("if", children)
+			},
+			StatementKind::While(condition, body) => Tree::list("while", vec![condition.to_tree(), body.to_tree()]),
+			StatementKind::For(for_loop) => {
+				let head: &str = if for_loop.of { "for-of" } else { "for-in" };
+				let binding: Tree = local_tree("let", &for_loop.binding);
+				Tree::list(head, vec![binding, for_loop.iter.to_tree(), for_loop.body.to_tree()])
+			},
+			StatementKind::Return(Some(value)) => Tree::list("return", vec![value.to_tree()]),
+			StatementKind::Return(None) => Tree::atom("return"),
+			StatementKind::Break => Tree::atom("break"),
+			StatementKind::Continue => Tree::atom("continue"),
+			StatementKind::Item => Tree::atom("item"),
+			StatementKind::Label => Tree::atom("label"),
+			StatementKind::Expression(expression) => Tree::list("expr", vec![expression.to_tree()]),
+			StatementKind::Scope(block) => block.to_tree(),
+			StatementKind::TypeDeclaration(typ) => Tree::list("type", vec![typ.to_tree()]),
+			StatementKind::Class(class) => class.to_tree(),
+			StatementKind::Enum(item) => item.to_tree(),
+			StatementKind::Package(path) => Tree::list("package", path.iter().map(|package| Tree::atom(package.name.name.as_str())).collect()),
+		};
+	}
+}
+
+impl ToTree for Function {
+	fn to_tree(&self) -> Tree {
+		let mut children: Vec<Tree> = Vec::new();
+		if let Some(name) = &self.name {
+			children.push(Tree::atom(name.name.as_str()));
+		}
+		if !self.generics.is_empty() {
+			children.push(generics_tree(&self.generics));
+		}
+
+		let params: Vec<Tree> = self
+			.params
+			.iter()
+			.map(|p| {
+				let mut param: Vec<Tree> = vec![Tree::atom(p.name.name.as_str())];
+				if let Some(typ) = &p.typ {
+					param.push(Tree::list("type", vec![typ.to_tree()]));
+				}
+				Tree::list("param", param)
+			})
+			.collect();
+		children.push(Tree::list("params", params));
+
+		if let Some(ret) = &self.ret {
+			children.push(Tree::list("returns", vec![ret.to_tree()]));
+		}
+		children.push(self.body.to_tree());
+
+		return Tree::list("function", children);
+	}
+}
+
+impl ToTree for Class {
+	fn to_tree(&self) -> Tree {
+		let mut children: Vec<Tree> = vec![Tree::atom(self.name.name.as_str())];
+		if !self.generics.is_empty() {
+			children.push(generics_tree(&self.generics));
+		}
+		if let Some(extends) = &self.extends {
+			children.push(Tree::list("extends", vec![extends.to_tree()]));
+		}
+
+		for member in self.members.iter() {
+			let (head, mut tree): (&str, Vec<Tree>) = match &member.kind {
+				MemberKind::Field(local) => ("field", local_tree("field", local).children().to_vec()),
+				MemberKind::Method(function) => ("method", function.to_tree().children().to_vec()),
+			};
+			if !member.modifiers.is_empty() {
+				let modifiers: Vec<Tree> = member.modifiers.iter().map(|m| Tree::atom(m.as_str())).collect();
+				tree.insert(1, Tree::list("modifiers", modifiers));
+			}
+			children.push(Tree::list(head, tree));
+		}
+
+		return Tree::list("class", children);
+	}
+}
+
+impl ToTree for Enum {
+	fn to_tree(&self) -> Tree {
+		let mut children: Vec<Tree> = vec![Tree::atom(self.name.name.as_str())];
+		for variant in self.variants.iter() {
+			let mut tree: Vec<Tree> = vec![Tree::atom(variant.name.name.as_str())];
+			if let Some(value) = &variant.value {
+				tree.push(value.to_tree());
+			}
+			children.push(Tree::list("variant", tree));
+		}
+
+		return Tree::list("enum", children);
+	}
+}
+
+impl ToTree for Expression {
+	fn to_tree(&self) -> Tree {
+		let trees = |expressions: &[Expression]| expressions.iter().map(|e| e.to_tree()).collect::<Vec<Tree>>();
+
+		return match &self.kind {
+			ExpressionKind::Array(items) => Tree::list("array", trees(items)),
+			ExpressionKind::Scope => Tree::atom("scope"),
+			ExpressionKind::Function(function) => function.to_tree(),
+			ExpressionKind::Call(callee, args) => {
+				let mut children: Vec<Tree> = vec![callee.to_tree()];
+				children.extend(trees(args));
+				Tree::list("call", children)
+			},
+			ExpressionKind::New(callee, args) => {
+				let mut children: Vec<Tree> = vec![callee.to_tree()];
+				children.extend(trees(args));
+				Tree::list("new", children)
+			},
+			ExpressionKind::Method(receiver, name, args, _) => {
+				let mut children: Vec<Tree> = vec![receiver.to_tree(), Tree::atom(name.name.as_str())];
+				children.extend(trees(args));
+				Tree::list("method-call", children)
+			},
+			ExpressionKind::Field(receiver, name) => Tree::list("field", vec![receiver.to_tree(), Tree::atom(name.name.as_str())]),
+			ExpressionKind::Index(receiver, index) => Tree::list("index", vec![receiver.to_tree(), index.to_tree()]),
+			ExpressionKind::Identifier(name) => Tree::atom(name.name.as_str()),
+			ExpressionKind::Literal(literal) => Tree::atom(match literal {
+				Literal::Number(n) => n.to_string(),
+				Literal::String(s) => format!("{:?}", s),
+				Literal::Bool(b) => b.to_string(),
+			}),
+			ExpressionKind::Paren(inner) => Tree::list("paren", vec![inner.to_tree()]),
+			ExpressionKind::Binary(op, a, b) => Tree::list(op.as_str(), vec![a.to_tree(), b.to_tree()]),
+			ExpressionKind::Unary(op, operand) => {
+				let head: String = match op.is_prefix() {
+					true => op.as_str().to_string(),
+					false => format!("post{}", op.as_str()),
+				};
+				Tree::list(head, vec![operand.to_tree()])
+			},
+			ExpressionKind::Assign(op, a, b) => Tree::list(op.as_str(), vec![a.to_tree(), b.to_tree()]),
+			ExpressionKind::Logical(op, a, b) => Tree::list(op.as_str(), vec![a.to_tree(), b.to_tree()]),
+			ExpressionKind::Compare(op, a, b) => Tree::list(op.as_str(), vec![a.to_tree(), b.to_tree()]),
+			ExpressionKind::Template(parts) => Tree::list(
+				"template",
+				parts
+					.iter()
+					.map(|p| match p {
+						TemplatePart::String(s) => Tree::atom(format!("{:?}", s)),
+						TemplatePart::Expression(e) => e.to_tree(),
+					})
+					.collect(),
+			),
+			ExpressionKind::Err => Tree::atom("<error>"),
+		};
+	}
+}
+
+impl ToTree for Constant {
+	fn to_tree(&self) -> Tree {
+		return Tree::list("constant", vec![self.typ.to_tree()]);
+	}
+}
+
+impl ToTree for Type {
+	fn to_tree(&self) -> Tree {
+		return match &self.kind {
+			TypeKinds::Named(name) => Tree::atom(name.as_str()),
+			TypeKinds::Array(inner) => Tree::list("array", vec![inner.to_tree()]),
+			TypeKinds::RawPtr => Tree::atom("raw-ptr"),
+			TypeKinds::AClass => Tree::atom("class"),
+			TypeKinds::ARef => Tree::atom("ref"),
+			TypeKinds::Generic(name, args) => {
+				let mut children: Vec<Tree> = vec![Tree::atom(name.name.as_str())];
+				children.extend(args.iter().map(|a| a.to_tree()));
+				Tree::list("generic", children)
+			},
+			TypeKinds::Union(variants) => Tree::list("union", variants.iter().map(|v| v.to_tree()).collect()),
+			TypeKinds::Function(params, ret) => {
+				let mut children: Vec<Tree> = vec![Tree::list("params", params.iter().map(|p| p.to_tree()).collect())];
+				if let Some(ret) = ret {
+					children.push(Tree::list("returns", vec![ret.to_tree()]));
+				}
+				Tree::list("fn", children)
+			},
+		};
+	}
+}
+
+/// The type parameters of a function or class, eg: `(generics T U)`
+fn generics_tree(generics: &[GenericParam]) -> Tree {
+	return Tree::list("generics", generics.iter().map(|g| Tree::atom(g.name.name.as_str())).collect());
+}
+
+/// A `let`, `const` or field, eg: `(let x (type u8) 1)`
+fn local_tree(head: &str, local: &Local) -> Tree {
+	let mut children: Vec<Tree> = vec![Tree::atom(local.name.name.as_str())];
+	if let Some(typ) = &local.typ {
+		children.push(Tree::list("type", vec![typ.to_tree()]));
+	}
+	if let Some(value) = &local.value {
+		children.push(value.to_tree());
+	}
+
+	return Tree::list(head, children);
+}
+
+/// `Display` writes the indented tree, and `{:#}` writes the S-expression.
+macro_rules! display_tree {
+	($($node: ty),*) => {
+		$(
+			impl fmt::Display for $node {
+				fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+					let tree: Tree = self.to_tree();
+					return match f.alternate() {
+						true => f.write_str(&tree.sexpr()),
+						false => f.write_str(tree.indented().trim_end()),
+					};
+				}
+			}
+		)*
+	};
+}
+
+display_tree!(Module, Block, Statement, Function, Class, Enum, Expression, Constant, Type);
@@ -1,13 +1,26 @@
 // This is a module that handles internal classes and types.
-use crate::{ compiler::typesharp_lexer::Token };
+use crate::{ compiler::typesharp_lexer::Token, compiler::typesharp_ast::Span, compiler::typesharp_ast::ast::Identifier };
 
+#[derive(Clone, PartialEq, Debug)]
 pub struct Type {
 	pub kind: TypeKinds,
-	pub tokens: Option<Vec<Token>>
+	pub tokens: Option<Vec<Token>>,
+	pub loc: Span
 }
 
+#[derive(Clone, PartialEq, Debug)]
 pub enum TypeKinds {
 	RawPtr,
 	AClass,
-	ARef
-}
\ No newline at end of file
+	ARef,
+	/// A type referred to by name, eg: `u8` or `Animal`
+	Named(String),
+	/// An array of a type, eg: `u8[]`
+	Array(Box<Type>),
+	/// A generic type given type arguments, eg: `Box<u8>`
+	Generic(Identifier, Vec<Type>),
+	/// Any one of several types, eg: `u8 | string`
+	Union(Vec<Type>),
+	/// A function, eg: `fn(u8, string): bool`, without a return type it returns `void`
+	Function(Vec<Type>, Option<Box<Type>>)
+}
@@ -1,11 +1,20 @@
-use super::position::Position;
+use super::position::{Position, Span};
+use super::source_map::FileId;
+use crate::error::DiagnosticSink;
 use std::str::Chars;
 
 pub struct Cursor<'a> {
+	input: &'a str,
 	init_length: usize,
 	chars: Chars<'a>,
 	pub previous: char,
+	/// The file being consumed, given to every span the cursor creates.
+	pub file: FileId,
 	pub pos: Position,
+	/// The position before the last consumed char, used to unpeek.
+	prev_pos: Position,
+	/// Problems found while consuming, eg: malformed literals.
+	pub diagnostics: DiagnosticSink,
 }
 
 pub const EOF: char = '\0';
@@ -14,11 +23,30 @@ impl<'a> Cursor<'a> {
 	/// Creates a new cursor.
 	/// Very useful for creating.
 	pub fn new(input: &'a str) -> Cursor<'a> {
+		return Cursor::with_file(input, FileId::default());
+	}
+
+	/// Creates a cursor for a file loaded in a `SourceMap`.
+	pub fn with_file(input: &'a str, file: FileId) -> Cursor<'a> {
+		return Cursor::resume(input, file, Position::default());
+	}
+
+	/// Creates a cursor that starts part way through `input`, eg: to lex
+	/// only the code that changed after an edit.
+	/// `pos` should be the start of a token, as the cursor does not know
+	/// if it is inside of a string or comment.
+	pub fn resume(input: &'a str, file: FileId, pos: Position) -> Cursor<'a> {
+		let offset: usize = pos.offset().min(input.len());
+
 		Cursor {
+			input: input,
 			init_length: input.len(),
-			chars: input.chars(),
-			previous: EOF,
-			pos: Position::new(0, 0),
+			chars: input[offset..].chars(),
+			previous: input[..offset].chars().next_back().unwrap_or(EOF),
+			file: file,
+			pos: pos,
+			prev_pos: pos,
+			diagnostics: DiagnosticSink::new(),
 		}
 	}
 
@@ -28,16 +56,44 @@ impl<'a> Cursor<'a> {
 		let c: char = self.chars.next()?;
 
 		self.previous = c;
+		self.prev_pos = self.pos;
 		self.pos.increment(c);
 
 		return Some(c);
 	}
 
-	/// Decreases the cursor offset.
-	/// Keep in mind, if the character has already been consumed
-	/// EOF is returned
+	/// Decreases the cursor offset, returning the char that was unconsumed.
+	/// Keep in mind, only the last consumed char can be unpeeked,
+	/// if there is nothing to unpeek EOF is returned
 	pub fn unpeek(&mut self) -> char {
-		return self.chars.nth(self.length_consumed() - 1).unwrap_or(EOF);
+		let consumed: usize = self.length_consumed();
+		if consumed == 0 || self.pos == self.prev_pos {
+			return EOF;
+		}
+
+		let c: char = self.input[..consumed].chars().next_back().unwrap_or(EOF);
+		let offset: usize = consumed - c.len_utf8();
+
+		self.chars = self.input[offset..].chars();
+		self.previous = self.input[..offset].chars().next_back().unwrap_or(EOF);
+		self.pos = self.prev_pos;
+		return c;
+	}
+
+	/// The position before the last consumed char.
+	/// EG: the start of a token, once its first char is consumed.
+	pub fn last_pos(&self) -> Position {
+		return self.prev_pos;
+	}
+
+	/// Creates a span from `start` to the current position.
+	pub fn span(&self, start: Position) -> Span {
+		return Span::new(start, self.pos).in_file(self.file);
+	}
+
+	/// Creates an empty span at the current position, eg: to insert code.
+	pub fn span_here(&self) -> Span {
+		return Span::from(self.pos).in_file(self.file);
 	}
 
 	// Grabs the next char without consuming it.
@@ -61,7 +117,8 @@ impl<'a> Cursor<'a> {
 		return self.chars.clone();
 	}
 
-	/// Checks the length that has been consumed by the cursor
+	/// Checks the length that has been consumed by the cursor,
+	/// this is the byte offset of the cursor in its input.
 	/// Consumed symbols are not kept.
 	pub fn length_consumed(&self) -> usize {
 		return self.init_length - self.chars.as_str().len();
@@ -83,7 +140,7 @@ impl<'a> Cursor<'a> {
 	/// Consumes chars until the predicate returns false, or until the end of file is met, and returns the offspring.
 	pub fn consume_segment(&mut self, mut pred: impl FnMut(char) -> bool) -> String {
 		let mut segment = String::new();
-		while !self.is_eof() && pred(self.first()) == true {
+		while !self.is_eof() && pred(self.first()) {
 			segment.push(self.peek().unwrap());
 		}
 		return segment;
@@ -1,2 +1,3 @@
 pub mod cursor;
 pub mod position;
+pub mod source_map;
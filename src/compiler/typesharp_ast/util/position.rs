@@ -1,37 +1,71 @@
-use super::cursor::EOF;
-use std::{cmp::Ordering, fmt};
+use super::source_map::FileId;
+use std::{cmp::Ordering, fmt, ops::Range};
 
 ///
 /// This is the position that is lexed.
-/// Tokens will contain a specific position, as well as lines.
+/// A position sits between two chars, lines and columns start at 1,
+/// and `offset` is the number of bytes before the position.
 ///
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd)]
 pub struct Position {
 	line: u32,
 	column: u32,
+	offset: usize,
 }
 
 impl Position {
 	#[inline]
 	#[track_caller]
-	pub fn new(line: u32, column: u32) -> Self {
+	pub fn new(line: u32, column: u32, offset: usize) -> Self {
 		return Self {
 			line: line,
 			column: column,
+			offset: offset,
 		};
 	}
 
-	pub fn increment(&mut self, c: char) -> bool {
+	pub fn line(&self) -> u32 {
+		return self.line;
+	}
+
+	pub fn column(&self) -> u32 {
+		return self.column;
+	}
+
+	/// The byte offset of the position in its file.
+	pub fn offset(&self) -> usize {
+		return self.offset;
+	}
+
+	/// Moves the position past `c`.
+	pub fn increment(&mut self, c: char) {
+		self.offset += c.len_utf8();
+
 		if c == '\n' {
-			self.line = self.line + 1;
-			return true;
-		} else if c == EOF {
-			return false;
+			self.line += 1;
+			self.column = 1;
 		} else {
-			self.column = self.column + 1;
-			return true;
+			self.column += 1;
 		}
 	}
+
+	/// Moves a position that comes after `from` so that it comes after `to` instead,
+	/// eg: to move the code after an edit. Only the line of `from` has its columns moved.
+	pub fn moved(self, from: Position, to: Position) -> Position {
+		let column: u32 = match self.line == from.line {
+			true => self.column - from.column + to.column,
+			false => self.column,
+		};
+
+		return Position::new(self.line - from.line + to.line, column, self.offset - from.offset + to.offset);
+	}
+}
+
+impl Default for Position {
+	/// The start of a file.
+	fn default() -> Self {
+		return Position::new(1, 1, 0);
+	}
 }
 
 impl fmt::Display for Position {
@@ -42,13 +76,17 @@ impl fmt::Display for Position {
 
 /// This is used for grabbing a "section" or "span"
 /// of code in a file. EG: Comments.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord)]
+/// The end of a span is exclusive, so a span with the same start
+/// and end is empty. Spans are ordered by file, then where they start and end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub struct Span {
+	file: FileId,
 	start: Position,
 	end: Position,
 }
 
 impl Span {
+	/// Creates a span in the first file, use `in_file` for any other file.
 	pub fn new(start: Position, end: Position) -> Self {
 		assert!(
 			start <= end,
@@ -56,20 +94,87 @@ impl Span {
 		);
 
 		return Self {
+			file: FileId::default(),
 			start: start,
 			end: end,
 		};
 	}
 
+	/// Moves the span to another file.
+	pub fn in_file(mut self, file: FileId) -> Self {
+		self.file = file;
+		return self;
+	}
+
+	pub fn file(&self) -> FileId {
+		return self.file;
+	}
+
+	pub fn start(&self) -> Position {
+		return self.start;
+	}
+
+	pub fn end(&self) -> Position {
+		return self.end;
+	}
+
+	/// The bytes of the file covered by the span.
+	pub fn byte_range(&self) -> Range<usize> {
+		return self.start.offset..self.end.offset;
+	}
+
+	pub fn len(&self) -> usize {
+		return self.end.offset - self.start.offset;
+	}
+
+	pub fn is_empty(&self) -> bool {
+		return self.start == self.end;
+	}
+
+	/// Creates a span that covers both this span, and the other span.
+	/// Both spans are expected to be in the same file.
+	pub fn to(self, other: Span) -> Span {
+		return Span {
+			file: self.file,
+			start: std::cmp::min(self.start, other.start),
+			end: std::cmp::max(self.end, other.end),
+		};
+	}
+
 	pub fn contains(self, other: Span) -> bool {
-		return self.start <= other.start && self.end >= other.end;
+		return self.file == other.file && self.start <= other.start && self.end >= other.end;
 	}
 
 	pub fn into_position(&self) -> Position {
-		return self.start.clone();
+		return self.start;
+	}
+
+	/// Moves a span that comes after `from` so that it comes after `to` instead.
+	pub fn moved(self, from: Position, to: Position) -> Span {
+		return Span {
+			file: self.file,
+			start: self.start.moved(from, to),
+			end: self.end.moved(from, to),
+		};
+	}
+
+	/// An empty span at the start of this span.
+	pub fn shrink_to_start(self) -> Span {
+		return Span {
+			file: self.file,
+			start: self.start,
+			end: self.start,
+		};
 	}
 
-	// to-do: Shrink
+	/// An empty span at the end of this span, eg: to insert code after it.
+	pub fn shrink_to_end(self) -> Span {
+		return Span {
+			file: self.file,
+			start: self.end,
+			end: self.end,
+		};
+	}
 
 	pub fn verify(self) -> bool {
 		return self.start <= self.end;
@@ -85,22 +190,9 @@ impl fmt::Display for Span {
 impl From<Position> for Span {
 	fn from(pos: Position) -> Self {
 		Self {
+			file: FileId::default(),
 			start: pos,
 			end: pos,
 		}
 	}
 }
-
-impl PartialOrd for Span {
-	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-		if self == other {
-			return Some(Ordering::Equal);
-		} else if self.end < other.start {
-			return Some(Ordering::Less);
-		} else if self.start > other.end {
-			return Some(Ordering::Greater);
-		} else {
-			return None;
-		}
-	}
-}
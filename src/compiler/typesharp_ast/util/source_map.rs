@@ -0,0 +1,198 @@
+use super::position::{Position, Span};
+use std::{fs, io, ops::Range, path::Path};
+
+/// Identifies a file loaded in to a `SourceMap`.
+/// Ids are handed out in the order files are added, starting at 0.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Default)]
+pub struct FileId {
+	id: u32
+}
+
+impl FileId {
+	pub fn new(id: u32) -> Self {
+		return FileId { id: id };
+	}
+
+	pub fn as_u32(&self) -> u32 {
+		return self.id;
+	}
+}
+
+/// A single file of source code, and where each of its lines start.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SourceFile {
+	id: FileId,
+	name: String,
+	source: String,
+	/// The byte offset of the start of each line.
+	line_starts: Vec<usize>,
+}
+
+impl SourceFile {
+	pub fn new(id: FileId, name: impl Into<String>, source: impl Into<String>) -> Self {
+		let source: String = source.into();
+		let mut line_starts: Vec<usize> = vec![0];
+		line_starts.extend(source.match_indices('\n').map(|(i, _)| i + 1));
+
+		return SourceFile {
+			id: id,
+			name: name.into(),
+			source: source,
+			line_starts: line_starts,
+		};
+	}
+
+	pub fn id(&self) -> FileId {
+		return self.id;
+	}
+
+	pub fn name(&self) -> &str {
+		return &self.name;
+	}
+
+	pub fn source(&self) -> &str {
+		return &self.source;
+	}
+
+	pub fn line_count(&self) -> usize {
+		return self.line_starts.len();
+	}
+
+	/// Gets a line by its line number, starting at 1, without its line ending.
+	pub fn line(&self, line: u32) -> Option<&str> {
+		let index: usize = (line as usize).checked_sub(1)?;
+		let start: usize = *self.line_starts.get(index)?;
+		let end: usize = self.line_starts.get(index + 1).map_or(self.source.len(), |end| end - 1);
+
+		let text: &str = &self.source[start..end];
+		return Some(text.strip_suffix('\r').unwrap_or(text));
+	}
+
+	/// Gets the line and column of a byte offset.
+	/// Offsets past the end of the file are moved to the end of the file.
+	pub fn position_at(&self, offset: usize) -> Position {
+		let mut offset: usize = offset.min(self.source.len());
+		while !self.source.is_char_boundary(offset) {
+			offset -= 1;
+		}
+
+		let index: usize = match self.line_starts.binary_search(&offset) {
+			Ok(index) => index,
+			Err(index) => index - 1,
+		};
+		let start: usize = self.line_starts[index];
+		let column: usize = self.source[start..offset].chars().count() + 1;
+
+		return Position::new(index as u32 + 1, column as u32, offset);
+	}
+
+	/// Gets the code covered by a span.
+	pub fn snippet(&self, span: Span) -> Option<&str> {
+		return self.source.get(span.byte_range());
+	}
+
+	/// Applies an edit to the source, returns false if the edit is not in the file.
+	pub fn edit(&mut self, edit: &TextEdit) -> bool {
+		return match edit.apply(&self.source) {
+			Some(source) => {
+				*self = SourceFile::new(self.id, std::mem::take(&mut self.name), source);
+				true
+			},
+			None => false,
+		};
+	}
+}
+
+/// A change to some text, the bytes in `range` are replaced with `replacement`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TextEdit {
+	pub range: Range<usize>,
+	pub replacement: String,
+}
+
+impl TextEdit {
+	pub fn new(range: Range<usize>, replacement: impl Into<String>) -> Self {
+		return TextEdit {
+			range: range,
+			replacement: replacement.into(),
+		};
+	}
+
+	pub fn insert(offset: usize, text: impl Into<String>) -> Self {
+		return TextEdit::new(offset..offset, text);
+	}
+
+	pub fn delete(range: Range<usize>) -> Self {
+		return TextEdit::new(range, "");
+	}
+
+	/// How many bytes longer the text is after the edit, this is negative if it got shorter.
+	pub fn delta(&self) -> isize {
+		return self.replacement.len() as isize - (self.range.end - self.range.start) as isize;
+	}
+
+	/// Applies the edit to `text`, returns `None` if the range is not
+	/// in `text` or does not start and end on a char.
+	pub fn apply(&self, text: &str) -> Option<String> {
+		if self.range.start > self.range.end {
+			return None;
+		}
+
+		let before: &str = text.get(..self.range.start)?;
+		let after: &str = text.get(self.range.end..)?;
+		return Some(format!("{}{}{}", before, self.replacement, after));
+	}
+}
+
+/// Owns every file loaded by the compiler, so that spans
+/// can be mapped back to the code they came from.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct SourceMap {
+	files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+	pub fn new() -> Self {
+		return SourceMap { files: Vec::new() };
+	}
+
+	/// Adds a file, `name` is the name shown in diagnostics.
+	pub fn add(&mut self, name: impl Into<String>, source: impl Into<String>) -> FileId {
+		let id: FileId = FileId::new(self.files.len() as u32);
+		self.files.push(SourceFile::new(id, name, source));
+		return id;
+	}
+
+	/// Reads a file from disk and adds it.
+	pub fn load(&mut self, path: impl AsRef<Path>) -> io::Result<FileId> {
+		let path: &Path = path.as_ref();
+		let source: String = fs::read_to_string(path)?;
+		return Ok(self.add(path.display().to_string(), source));
+	}
+
+	pub fn get(&self, id: FileId) -> Option<&SourceFile> {
+		return self.files.get(id.as_u32() as usize);
+	}
+
+	pub fn files(&self) -> std::slice::Iter<'_, SourceFile> {
+		return self.files.iter();
+	}
+
+	/// Gets the code covered by a span.
+	pub fn snippet(&self, span: Span) -> Option<&str> {
+		return self.get(span.file())?.snippet(span);
+	}
+
+	/// Gets the line a span starts on.
+	pub fn line(&self, span: Span) -> Option<&str> {
+		return self.get(span.file())?.line(span.start().line());
+	}
+
+	/// Formats where a span starts, eg: `main.t#:1:10`
+	pub fn location(&self, span: Span) -> String {
+		return match self.get(span.file()) {
+			Some(file) => format!("{}:{}", file.name(), span.start()),
+			None => span.start().to_string(),
+		};
+	}
+}
@@ -0,0 +1,1238 @@
+use super::ty::{ FloatTy, InferTy, IntTy, Ty };
+use crate::compiler::typesharp_ast::{ ast::*, node::NodeId, types::{ Type, TypeKinds }, Span };
+use crate::compiler::typesharp_lexer::token::Numeric;
+use crate::compiler::typesharp_parser::{ AssignmentOp, BinOp, ComparisonOp, LogicalOp, UnaryOp };
+use crate::compiler::typesharp_resolve::{ DefKind, Resolutions };
+use crate::error::{ codes, Diagnostic, DiagnosticSink };
+use std::collections::{ BTreeMap, BTreeSet };
+
+/// The types the checker found in a module.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct TypeTable {
+	/// The type of each expression, by its id.
+	pub expressions: BTreeMap<NodeId, Ty>,
+	/// The type of each declaration, by the id of the node declaring it.
+	/// Classes and enums themselves are `Ty::Static`.
+	pub defs: BTreeMap<NodeId, Ty>,
+}
+
+/// A class, as much of it as checking needs.
+#[derive(Clone, PartialEq, Debug)]
+struct ClassInfo {
+	/// The class being extended, in terms of this class's type parameters.
+	extends: Option<Ty>,
+	/// Fields and methods by name, with the id declaring them.
+	members: Vec<(String, NodeId)>,
+}
+
+/// A type being inferred.
+#[derive(Clone, PartialEq, Debug)]
+struct Var {
+	value: Option<Ty>,
+	/// The type it has if nothing decides it, only numbers have one.
+	default: Option<Ty>,
+	/// What the type is of, for when it can not be inferred, eg: "type parameter `T` of `id`"
+	what: String,
+	span: Span,
+}
+
+/// Checks that can only be made once the types being inferred are known.
+#[derive(Clone, PartialEq, Debug)]
+enum Deferred {
+	/// A number literal must fit in its type, the value is already negated if it is `negative`.
+	Literal(i128, bool, Ty, Span),
+	/// Only signed numbers can be negated.
+	Negate(Ty, Span),
+}
+
+/// Checks the types of a module, the module must already be resolved.
+pub fn check(module: &Module, resolutions: &Resolutions) -> (TypeTable, DiagnosticSink) {
+	let mut checker: Checker = Checker::new(resolutions);
+	checker.module(module);
+	return (checker.types, checker.diagnostics);
+}
+
+/// Checks a module in two passes, the first gives every declaration a type (so functions and classes
+/// can be used before they are declared), the second checks every expression against what it is used as.
+///
+/// The types of `let` and `const` bindings without an annotation, the parameters of function expressions
+/// and the type arguments of generic calls are inferred: each starts as a `Ty::Infer` variable that is
+/// decided by the first type it is used as. Parameters of declared functions must be annotated,
+/// without one they are `Ty::Unknown`, which is never an error to use.
+pub struct Checker<'a> {
+	resolutions: &'a Resolutions,
+	classes: BTreeMap<NodeId, ClassInfo>,
+	/// The return types of the functions being checked, innermost last, with the span of the annotation.
+	returns: Vec<(Ty, Option<Span>)>,
+	vars: Vec<Var>,
+	/// The types being inferred in the order they were decided, so a check that fails can undo what it decided.
+	decided: Vec<u32>,
+	deferred: Vec<Deferred>,
+	/// Bindings and parameters whose type is inferred, in the order they are declared.
+	bindings: Vec<(NodeId, Identifier)>,
+	pub types: TypeTable,
+	pub diagnostics: DiagnosticSink,
+}
+
+impl<'a> Checker<'a> {
+	pub fn new(resolutions: &'a Resolutions) -> Self {
+		return Checker {
+			resolutions: resolutions,
+			classes: BTreeMap::new(),
+			returns: Vec::new(),
+			vars: Vec::new(),
+			decided: Vec::new(),
+			deferred: Vec::new(),
+			bindings: Vec::new(),
+			types: TypeTable::default(),
+			diagnostics: DiagnosticSink::new(),
+		};
+	}
+
+	pub fn module(&mut self, module: &Module) {
+		self.collect(&module.statements);
+		self.statements(&module.statements);
+		self.finish();
+	}
+
+	/// Gives a type to everything declared in the statements, and in the blocks inside of them.
+	fn collect(&mut self, statements: &[Statement]) {
+		for statement in statements.iter() {
+			match &statement.kind {
+				StatementKind::Let(local) | StatementKind::Constant(local) => self.collect_local(local),
+				StatementKind::Function(function) => self.collect_function(function),
+				StatementKind::Class(class) => self.collect_class(class),
+				StatementKind::Enum(item) => {
+					let ty: Ty = Ty::Enum(item.id, item.name.name.clone());
+					for variant in item.variants.iter() {
+						self.types.defs.insert(variant.id, ty.clone());
+					}
+					self.types.defs.insert(item.id, Ty::Static(Box::new(ty)));
+				},
+				StatementKind::If(_, then, otherwise) => {
+					self.collect(&then.statements);
+					if let Some(otherwise) = otherwise {
+						self.collect(std::slice::from_ref(otherwise));
+					}
+				},
+				StatementKind::While(_, body) | StatementKind::Scope(body) => self.collect(&body.statements),
+				StatementKind::For(for_loop) => {
+					self.collect_local(&for_loop.binding);
+					self.collect(&for_loop.body.statements);
+				},
+				_ => (),
+			}
+		}
+	}
+
+	fn collect_local(&mut self, local: &Local) {
+		if let Some(typ) = &local.typ {
+			let ty: Ty = self.lower(typ);
+			self.types.defs.insert(local.id, ty);
+		}
+	}
+
+	fn collect_function(&mut self, function: &Function) {
+		let mut params: Vec<Ty> = Vec::new();
+		for param in function.params.iter() {
+			let ty: Ty = param.typ.as_ref().map(|t| self.lower(t)).unwrap_or(Ty::Unknown);
+			self.types.defs.insert(param.id, ty.clone());
+			params.push(ty);
+		}
+
+		let ret: Ty = function.ret.as_ref().map(|t| self.lower(t)).unwrap_or(Ty::Unknown);
+		self.types.defs.insert(function.id, Ty::Function(params, Box::new(ret)));
+		self.collect(&function.body.statements);
+	}
+
+	fn collect_class(&mut self, class: &Class) {
+		let params: Vec<Ty> = self.type_params(class.id).into_iter().map(|(id, name)| Ty::Param(id, name)).collect();
+		let ty: Ty = Ty::Class(class.id, class.name.name.clone(), params);
+		self.types.defs.insert(class.id, Ty::Static(Box::new(ty.clone())));
+
+		let extends: Option<Ty> = class.extends.as_ref().map(|t| self.lower(t));
+		let mut members: Vec<(String, NodeId)> = Vec::new();
+		for member in class.members.iter() {
+			let (name, id) = match &member.kind {
+				MemberKind::Field(local) => {
+					self.collect_local(local);
+					(&local.name, local.id)
+				},
+				MemberKind::Method(function) => {
+					self.collect_function(function);
+					match &function.name {
+						Some(name) => (name, function.id),
+						None => continue,
+					}
+				},
+			};
+			members.push((name.name.clone(), id));
+		}
+
+		self.classes.insert(class.id, ClassInfo { extends: extends, members: members });
+
+		// a cycle is only complete once its last class is collected, so it is reported once, on that class
+		let ancestors: Vec<Ty> = self.ancestors(&ty);
+		let cyclic: bool = ancestors.last().and_then(|last| self.supertype(last)).is_some_and(|t| matches!(t, Ty::Class(id, ..) if id == class.id));
+		if let (true, Some(extends)) = (cyclic, &class.extends) {
+			let mut names: Vec<String> = ancestors.iter().map(|t| format!("`{}`", t)).collect();
+			let mut diagnostic: Diagnostic = Diagnostic::error(format!("the class `{}` extends itself", class.name.name), extends.loc)
+				.with_code(codes::CYCLIC_EXTENDS);
+			if names.len() > 1 {
+				names.push(names[0].clone());
+				diagnostic = diagnostic.with_note(format!("{} extends {}", names[0], names[1..].join(", which extends ")));
+			}
+			self.diagnostics.push(diagnostic);
+		}
+	}
+
+	/// The type parameters of a generic function or class, in the order they are written.
+	fn type_params(&self, owner: NodeId) -> Vec<(NodeId, String)> {
+		return self
+			.resolutions
+			.defs
+			.values()
+			.filter(|d| d.kind == DefKind::TypeParam && d.parent == Some(owner))
+			.map(|d| (d.id, d.name.clone()))
+			.collect();
+	}
+
+	/// Turns a type as written in to the type it names.
+	fn lower(&mut self, typ: &Type) -> Ty {
+		let all = |checker: &mut Self, types: &[Type]| types.iter().map(|t| checker.lower(t)).collect::<Vec<Ty>>();
+
+		return match &typ.kind {
+			TypeKinds::Named(name) => self.named(name, typ.loc, &[], typ.loc),
+			TypeKinds::Generic(name, args) => {
+				let lowered: Vec<Ty> = all(self, args);
+				self.named(&name.name, name.loc, &lowered, typ.loc)
+			},
+			TypeKinds::Array(inner) => Ty::Array(Box::new(self.lower(inner))),
+			TypeKinds::Union(variants) => {
+				let mut flat: Vec<Ty> = Vec::new();
+				for variant in all(self, variants) {
+					let variant: Vec<Ty> = match variant {
+						Ty::Union(inner) => inner,
+						other => vec![other],
+					};
+					for v in variant {
+						if !flat.contains(&v) {
+							flat.push(v);
+						}
+					}
+				}
+				if flat.len() == 1 { flat.remove(0) } else { Ty::Union(flat) }
+			},
+			TypeKinds::Function(params, ret) => {
+				let params: Vec<Ty> = all(self, params);
+				let ret: Ty = ret.as_ref().map(|r| self.lower(r)).unwrap_or(Ty::Void);
+				Ty::Function(params, Box::new(ret))
+			},
+			TypeKinds::RawPtr | TypeKinds::AClass | TypeKinds::ARef => Ty::Unknown,
+		};
+	}
+
+	/// The type a name refers to, given type arguments.
+	fn named(&mut self, name: &str, span: Span, args: &[Ty], whole: Span) -> Ty {
+		if let Some(def) = self.resolutions.type_def(span) {
+			let (id, kind, def_name) = (def.id, def.kind, def.name.clone());
+			return match kind {
+				DefKind::Class => {
+					let params: Vec<(NodeId, String)> = self.type_params(id);
+					if params.len() != args.len() {
+						self.type_arity(name, params.len(), args.len(), whole);
+						return Ty::Class(id, def_name, vec![Ty::Unknown; params.len()]);
+					}
+					Ty::Class(id, def_name, args.to_vec())
+				},
+				DefKind::Enum | DefKind::TypeParam => {
+					if !args.is_empty() {
+						self.type_arity(name, 0, args.len(), whole);
+					}
+					if kind == DefKind::Enum { Ty::Enum(id, def_name) } else { Ty::Param(id, def_name) }
+				},
+				_ => Ty::Unknown,
+			};
+		}
+
+		// `Array<T>` is another way of writing `T[]`
+		if name == "Array" {
+			if args.len() != 1 {
+				self.type_arity(name, 1, args.len(), whole);
+			}
+			return Ty::Array(Box::new(args.first().cloned().unwrap_or(Ty::Unknown)));
+		}
+
+		return match Ty::primitive(name) {
+			Some(ty) => {
+				if !args.is_empty() {
+					self.type_arity(name, 0, args.len(), whole);
+				}
+				ty
+			},
+			// already reported by name resolution
+			None => Ty::Unknown,
+		};
+	}
+
+	fn type_arity(&mut self, name: &str, expected: usize, found: usize, span: Span) {
+		let message: String = match expected {
+			0 => format!("`{}` does not take type arguments", name),
+			_ => format!("`{}` takes {} type argument{} but {} {} given", name, expected, plural(expected), found, if found == 1 { "was" } else { "were" }),
+		};
+		self.diagnostics.push(Diagnostic::error(message, span).with_code(codes::WRONG_TYPE_ARGUMENT_COUNT));
+	}
+
+	fn statements(&mut self, statements: &[Statement]) {
+		for statement in statements.iter() {
+			self.statement(statement);
+		}
+	}
+
+	fn statement(&mut self, statement: &Statement) {
+		match &statement.kind {
+			StatementKind::Let(local) | StatementKind::Constant(local) => self.local(local),
+			StatementKind::Function(function) => self.function(function),
+			StatementKind::Class(class) => self.class(class),
+			StatementKind::Enum(item) => {
+				for variant in item.variants.iter() {
+					if let Some(value) = &variant.value {
+						self.expression(value, None);
+					}
+				}
+			},
+			StatementKind::If(condition, then, otherwise) => {
+				self.coerce(condition, &Ty::Bool, None);
+				self.statements(&then.statements);
+				if let Some(otherwise) = otherwise {
+					self.statement(otherwise);
+				}
+			},
+			StatementKind::While(condition, body) => {
+				self.coerce(condition, &Ty::Bool, None);
+				self.statements(&body.statements);
+			},
+			StatementKind::For(for_loop) => self.for_loop(for_loop),
+			StatementKind::Return(value) => self.ret(value.as_ref(), statement.loc),
+			StatementKind::Expression(expression) => {
+				self.expression(expression, None);
+			},
+			StatementKind::Scope(block) => self.statements(&block.statements),
+			StatementKind::Break
+			| StatementKind::Continue
+			| StatementKind::Item
+			| StatementKind::Label
+			| StatementKind::TypeDeclaration(_)
+			| StatementKind::Package(_) => (),
+		}
+	}
+
+	fn local(&mut self, local: &Local) {
+		match (self.types.defs.get(&local.id).cloned(), &local.typ, &local.value) {
+			(Some(ty), Some(typ), Some(value)) => {
+				self.coerce(value, &ty, Some((typ.loc, "expected due to this type")));
+			},
+			(Some(_), Some(_), None) => (),
+			(_, _, value) => {
+				let ty: Ty = match value {
+					Some(value) => self.expression(value, None),
+					None => self.var(format!("the type of `{}`", local.name.name), local.name.loc, None),
+				};
+				self.types.defs.insert(local.id, ty);
+				self.bindings.push((local.id, local.name.clone()));
+			},
+		}
+	}
+
+	fn function(&mut self, function: &Function) {
+		let ret: Ty = match self.types.defs.get(&function.id) {
+			Some(Ty::Function(_, ret)) => (**ret).clone(),
+			_ => Ty::Unknown,
+		};
+
+		self.returns.push((ret.clone(), function.ret.as_ref().map(|t| t.loc)));
+		self.statements(&function.body.statements);
+		self.returns.pop();
+
+		if let Some(typ) = &function.ret {
+			if !matches!(self.shallow(&ret), Ty::Void | Ty::Unknown) && !always_returns(&function.body.statements) {
+				let name: String = function.name.as_ref().map(|n| format!("`{}`", n.name)).unwrap_or_else(|| "this function".to_string());
+				self.diagnostics.push(
+					Diagnostic::error(format!("{} must return `{}`, but it can reach its end without returning", name, ret), typ.loc)
+						.with_code(codes::MISSING_RETURN),
+				);
+			}
+		}
+	}
+
+	/// Checks a function expression, parameters and a return type that are not written are inferred,
+	/// from the type the function is expected to be if it is known.
+	fn lambda(&mut self, function: &Function, expected: Option<&Ty>) -> Ty {
+		let expected: Option<(Vec<Ty>, Ty)> = match expected.map(|e| self.shallow(e)) {
+			Some(Ty::Function(params, ret)) if params.len() == function.params.len() => Some((params, *ret)),
+			_ => None,
+		};
+
+		let mut params: Vec<Ty> = Vec::new();
+		for (i, param) in function.params.iter().enumerate() {
+			let ty: Ty = match (&param.typ, &expected) {
+				(Some(typ), _) => self.lower(typ),
+				(None, Some((expected, _))) => expected[i].clone(),
+				(None, None) => {
+					self.bindings.push((param.id, param.name.clone()));
+					self.var(format!("the type of `{}`", param.name.name), param.name.loc, None)
+				},
+			};
+			self.types.defs.insert(param.id, ty.clone());
+			params.push(ty);
+		}
+
+		let ret: Ty = match (&function.ret, expected) {
+			(Some(typ), _) => self.lower(typ),
+			(None, Some((_, ret))) => ret,
+			(None, None) => self.var(String::from("the return type of this function"), function.loc, None),
+		};
+		self.types.defs.insert(function.id, Ty::Function(params, Box::new(ret.clone())));
+		self.collect(&function.body.statements);
+		self.function(function);
+
+		// a function that never returns a value returns `void`
+		if let Ty::Infer(var @ InferTy::Var(_)) = self.shallow(&ret) {
+			self.bind(var, &Ty::Void);
+		}
+		return self.types.defs[&function.id].clone();
+	}
+
+	fn class(&mut self, class: &Class) {
+		for member in class.members.iter() {
+			match &member.kind {
+				MemberKind::Field(local) => self.local(local),
+				MemberKind::Method(function) => self.function(function),
+			}
+		}
+	}
+
+	fn for_loop(&mut self, for_loop: &ForLoop) {
+		let iter: Ty = self.expression(&for_loop.iter, None);
+		let element: Ty = if !for_loop.of {
+			// `for in` goes over the indexes
+			Ty::Int(IntTy::Usize)
+		} else {
+			match self.shallow(&iter) {
+				Ty::Array(inner) => *inner,
+				Ty::String => Ty::String,
+				// the type of what is iterated over must be known by now
+				Ty::Unknown | Ty::Infer(InferTy::Var(_)) => Ty::Unknown,
+				other => {
+					self.diagnostics.push(
+						Diagnostic::error(format!("`{}` is not iterable", self.zonk(&other)), for_loop.iter.loc).with_code(codes::NOT_ITERABLE),
+					);
+					Ty::Unknown
+				},
+			}
+		};
+
+		let binding: &Local = &for_loop.binding;
+		match (self.types.defs.get(&binding.id).cloned(), &binding.typ) {
+			(Some(ty), Some(typ)) => {
+				if !self.is_assignable(&element, &ty) {
+					self.mismatch(&ty, &element, binding.name.loc, Some((typ.loc, "expected due to this type")));
+				}
+			},
+			_ => {
+				self.types.defs.insert(binding.id, element);
+			},
+		}
+		self.statements(&for_loop.body.statements);
+	}
+
+	fn ret(&mut self, value: Option<&Expression>, span: Span) {
+		let (ret, annotation) = match self.returns.last() {
+			Some(ret) => ret.clone(),
+			None => return,
+		};
+		let because: Option<(Span, &str)> = annotation.map(|a| (a, "expected because of this return type"));
+
+		match value {
+			Some(value) if self.shallow(&ret) == Ty::Void => {
+				let found: Ty = self.expression(value, None);
+				self.mismatch(&Ty::Void, &found, value.loc, because);
+			},
+			Some(value) => {
+				self.coerce(value, &ret, because);
+			},
+			None => {
+				if !self.is_assignable(&Ty::Void, &ret) {
+					self.mismatch(&ret, &Ty::Void, span, because);
+				}
+			},
+		}
+	}
+
+	/// Checks an expression can be used where `expected` is, returning its type.
+	fn coerce(&mut self, expression: &Expression, expected: &Ty, because: Option<(Span, &str)>) -> Ty {
+		let found: Ty = self.expression(expression, Some(expected));
+		if !self.is_assignable(&found, expected) {
+			self.mismatch(expected, &found, expression.loc, because);
+		}
+		return found;
+	}
+
+	fn mismatch(&mut self, expected: &Ty, found: &Ty, span: Span, because: Option<(Span, &str)>) {
+		let (expected, found) = (self.zonk(expected), self.zonk(found));
+		let mut diagnostic: Diagnostic =
+			Diagnostic::error(format!("mismatched types: expected `{}`, found `{}`", expected, found), span).with_code(codes::MISMATCHED_TYPES);
+		if let Some((at, label)) = because {
+			diagnostic = diagnostic.with_label(at, label);
+		}
+		self.diagnostics.push(diagnostic);
+	}
+
+	/// Checks an expression, `expected` is what it is used as and decides the type of number literals.
+	fn expression(&mut self, expression: &Expression, expected: Option<&Ty>) -> Ty {
+		let ty: Ty = self.expression_kind(expression, expected);
+		self.types.expressions.insert(expression.id, ty.clone());
+		return ty;
+	}
+
+	fn expression_kind(&mut self, expression: &Expression, expected: Option<&Ty>) -> Ty {
+		return match &expression.kind {
+			ExpressionKind::Literal(Literal::Number(number)) => self.number(number, false, expression.loc, expected),
+			ExpressionKind::Literal(Literal::String(_)) | ExpressionKind::Template(_) => {
+				if let ExpressionKind::Template(parts) = &expression.kind {
+					for part in parts.iter() {
+						if let TemplatePart::Expression(inner) = part {
+							self.expression(inner, None);
+						}
+					}
+				}
+				Ty::String
+			},
+			ExpressionKind::Literal(Literal::Bool(_)) => Ty::Bool,
+			ExpressionKind::Identifier(name) => self.identifier(expression.id, name),
+			ExpressionKind::Paren(inner) => self.expression(inner, expected),
+			ExpressionKind::Array(items) => self.array(items, expression.loc, expected),
+			ExpressionKind::Function(function) => self.lambda(function, expected),
+			ExpressionKind::Call(callee, args) => {
+				let ty: Ty = self.expression(callee, None);
+				let owner: Option<NodeId> = self.resolutions.uses.get(&callee.id).map(|u| u.def);
+				self.call(ty, owner, args, expression.loc, expected)
+			},
+			ExpressionKind::New(callee, args) => {
+				let ty: Ty = self.expression(callee, None);
+				match self.shallow(&ty) {
+					Ty::Static(inner) if matches!(*inner, Ty::Class(..)) => {
+						let owner: Option<NodeId> = self.resolutions.uses.get(&callee.id).map(|u| u.def);
+						self.call(ty, owner, args, expression.loc, expected)
+					},
+					Ty::Unknown | Ty::Infer(InferTy::Var(_)) => self.call(ty, None, args, expression.loc, expected),
+					other => {
+						args.iter().for_each(|a| drop(self.expression(a, None)));
+						let other: Ty = self.zonk(&other);
+						self.diagnostics.push(
+							Diagnostic::error(format!("expected a class, found `{}`", other), callee.loc).with_code(codes::NOT_CALLABLE),
+						);
+						Ty::Unknown
+					},
+				}
+			},
+			// `package.name(...)` is a call to the function it names, the package itself is not a value
+			ExpressionKind::Method(receiver, name, args, _) if self.is_package(receiver) => {
+				let ty: Ty = self.identifier(expression.id, name);
+				let owner: Option<NodeId> = self.resolutions.uses.get(&expression.id).map(|u| u.def);
+				self.call(ty, owner, args, expression.loc, expected)
+			},
+			ExpressionKind::Method(receiver, name, args, _) => {
+				let receiver: Ty = self.expression(receiver, None);
+				match self.member(&receiver, name, "method") {
+					Some((owner, ty)) => self.call(ty, owner, args, expression.loc, expected),
+					None => {
+						args.iter().for_each(|a| drop(self.expression(a, None)));
+						Ty::Unknown
+					},
+				}
+			},
+			ExpressionKind::Field(receiver, name) if self.is_package(receiver) => self.identifier(expression.id, name),
+			ExpressionKind::Field(receiver, name) => {
+				let receiver: Ty = self.expression(receiver, None);
+				match self.shallow(&receiver) {
+					Ty::Static(inner) if matches!(*inner, Ty::Enum(..)) => *inner,
+					Ty::Array(_) | Ty::String if name.name == "length" => Ty::Int(IntTy::Usize),
+					receiver => self.member(&receiver, name, "field").map(|(_, ty)| ty).unwrap_or(Ty::Unknown),
+				}
+			},
+			ExpressionKind::Index(target, index) => {
+				let target_ty: Ty = self.expression(target, None);
+				let index_ty: Ty = self.expression(index, Some(&Ty::Int(IntTy::Usize)));
+				// any integer can index, an integer being inferred becomes a `usize`
+				if !matches!(self.shallow(&index_ty), Ty::Int(_)) && !self.is_assignable(&index_ty, &Ty::Int(IntTy::Usize)) {
+					self.mismatch(&Ty::Int(IntTy::Usize), &index_ty, index.loc, None);
+				}
+				match self.shallow(&target_ty) {
+					Ty::Array(inner) => *inner,
+					Ty::String => Ty::String,
+					Ty::Unknown | Ty::Infer(InferTy::Var(_)) => Ty::Unknown,
+					other => {
+						let other: Ty = self.zonk(&other);
+						self.diagnostics.push(
+							Diagnostic::error(format!("cannot index into a value of type `{}`", other), target.loc)
+								.with_code(codes::NOT_INDEXABLE),
+						);
+						Ty::Unknown
+					},
+				}
+			},
+			ExpressionKind::Unary(op, inner) => self.unary(op, inner, expression.loc, expected),
+			ExpressionKind::Binary(op, a, b) => {
+				let hint: Option<&Ty> = expected.filter(|e| e.is_numeric());
+				let (left, right) = self.operands(a, b, hint);
+				self.binary(op, &left, &right, a, b)
+			},
+			ExpressionKind::Compare(op, a, b) => {
+				let (left, right) = self.operands(a, b, None);
+				self.compare(op, &left, &right, a, b);
+				Ty::Bool
+			},
+			ExpressionKind::Logical(op, a, b) => match op {
+				LogicalOp::And | LogicalOp::Or => {
+					self.coerce(a, &Ty::Bool, None);
+					self.coerce(b, &Ty::Bool, None);
+					Ty::Bool
+				},
+				LogicalOp::Coalasce => {
+					let left: Ty = self.expression(a, expected);
+					let right: Ty = self.expression(b, Some(&left));
+					if left.is_unknown() { right } else { left }
+				},
+			},
+			ExpressionKind::Assign(op, target, value) => self.assign(op, target, value),
+			ExpressionKind::Scope | ExpressionKind::Err => Ty::Unknown,
+		};
+	}
+
+	/// The type of a number literal, it becomes the number type it is used as if it fits in it.
+	/// Without one it is inferred, and if nothing decides it, it has the type its size gives it eg: `i32`
+	/// whether it is written in decimal, binary, octal or hexadecimal.
+	fn number(&mut self, number: &Numeric, negative: bool, span: Span, expected: Option<&Ty>) -> Ty {
+		let expected: Option<Ty> = expected.map(|e| self.shallow(e));
+		let (value, default) = match number {
+			Numeric::IntegerLiteral(n) => (*n as i128, IntTy::I32),
+			Numeric::IntegerLiteralBig(n) => (*n as i128, IntTy::I64),
+			Numeric::ItegerLiteralSigned128(n) => (*n, IntTy::I128),
+			Numeric::Binary(n) | Numeric::Octal(n) | Numeric::Hexadecimal(n) => {
+				let int: IntTy = if *n <= i32::MAX as usize {
+					IntTy::I32
+				} else if *n as u128 <= i64::MAX as u128 {
+					IntTy::I64
+				} else {
+					IntTy::I128
+				};
+				(*n as i128, int)
+			},
+			Numeric::FloatLiteral(_) | Numeric::DoubleLiteral(_) => {
+				return match expected {
+					Some(Ty::Float(float)) => Ty::Float(float),
+					Some(ty @ Ty::Infer(InferTy::Float(_))) => ty,
+					_ => {
+						let float: FloatTy = if matches!(number, Numeric::FloatLiteral(_)) { FloatTy::F32 } else { FloatTy::F64 };
+						self.var(String::from("the type of this number"), span, Some(Ty::Float(float)))
+					},
+				};
+			},
+		};
+
+		let ty: Ty = match expected {
+			Some(ty) if ty.is_numeric() => ty,
+			_ => self.var(String::from("the type of this number"), span, Some(Ty::Int(default))),
+		};
+		self.literal(if negative { -value } else { value }, negative, &ty, span);
+		return ty;
+	}
+
+	/// Checks a number literal fits in its type, once the type is known.
+	fn literal(&mut self, value: i128, negative: bool, ty: &Ty, span: Span) {
+		let int: IntTy = match self.shallow(ty) {
+			Ty::Int(int) => int,
+			Ty::Infer(_) => return self.deferred.push(Deferred::Literal(value, negative, ty.clone(), span)),
+			_ => return,
+		};
+
+		let (min, max) = int.range();
+		// negating an unsigned literal is reported by the unary operator
+		if (value < min && (int.is_signed() || !negative)) || value > max {
+			self.diagnostics.push(
+				Diagnostic::error(format!("literal out of range for `{}`", int.name()), span)
+					.with_code(codes::LITERAL_OUT_OF_RANGE)
+					.with_note(format!("`{}` can hold values from {} to {}", int.name(), min, max)),
+			);
+		}
+	}
+
+	fn identifier(&mut self, id: NodeId, name: &Identifier) -> Ty {
+		let def: NodeId = match self.resolutions.uses.get(&id) {
+			Some(u) => u.def,
+			None => return Ty::Unknown,
+		};
+
+		let ty: Ty = self.types.defs.get(&def).cloned().unwrap_or(Ty::Unknown);
+		return match ty {
+			// `this` is an instance of the class it is in
+			Ty::Static(inner) if matches!(name.name.as_str(), "this" | "self") => *inner,
+			ty => ty,
+		};
+	}
+
+	/// Whether an expression names a package, eg: `geometry` in `geometry.origin()`
+	fn is_package(&self, expression: &Expression) -> bool {
+		return self.resolutions.def_of(expression.id).is_some_and(|d| d.kind == DefKind::Package);
+	}
+
+	fn array(&mut self, items: &[Expression], span: Span, expected: Option<&Ty>) -> Ty {
+		// without an expected type the first item decides the type of the others
+		let (element, rest) = match expected.map(|e| self.shallow(e)) {
+			Some(Ty::Array(inner)) => (*inner, items),
+			_ => match items.split_first() {
+				Some((first, rest)) => (self.expression(first, None), rest),
+				None => (self.var(String::from("the type of the items of this array"), span, None), items),
+			},
+		};
+
+		for item in rest.iter() {
+			self.coerce(item, &element, None);
+		}
+		return Ty::Array(Box::new(element));
+	}
+
+	/// Checks a call to a value of type `callee`, `owner` is the function or class being called if it is known.
+	fn call(&mut self, callee: Ty, owner: Option<NodeId>, args: &[Expression], span: Span, expected: Option<&Ty>) -> Ty {
+		let callee: Ty = match owner {
+			Some(owner) => self.instantiate(owner, &callee, span),
+			None => self.shallow(&callee),
+		};
+
+		match callee {
+			Ty::Function(params, ret) => {
+				if params.len() != args.len() {
+					let message: String = format!(
+						"this function takes {} argument{} but {} {} supplied",
+						params.len(),
+						plural(params.len()),
+						args.len(),
+						if args.len() == 1 { "was" } else { "were" }
+					);
+					let mut diagnostic: Diagnostic = Diagnostic::error(message, span).with_code(codes::WRONG_ARGUMENT_COUNT);
+					if let Some(def) = owner.and_then(|o| self.resolutions.defs.get(&o)) {
+						diagnostic = diagnostic.with_label(def.span, format!("`{}` is declared here", def.name));
+					}
+					self.diagnostics.push(diagnostic);
+				}
+
+				// what the result is used as decides type arguments before the arguments do, eg: `let x: u8 = id(1)`
+				if let Some(expected) = expected.filter(|_| self.has_vars(&ret)) {
+					self.is_assignable(&ret, expected);
+				}
+
+				for (i, arg) in args.iter().enumerate() {
+					match params.get(i) {
+						Some(param) => drop(self.coerce(arg, param, None)),
+						None => drop(self.expression(arg, None)),
+					}
+				}
+				return *ret;
+			},
+			// calling a class makes an instance of it
+			Ty::Static(inner) if matches!(*inner, Ty::Class(..)) => {
+				args.iter().for_each(|a| drop(self.expression(a, None)));
+				return *inner;
+			},
+			Ty::Unknown | Ty::Infer(InferTy::Var(_)) => {
+				args.iter().for_each(|a| drop(self.expression(a, None)));
+				return Ty::Unknown;
+			},
+			other => {
+				args.iter().for_each(|a| drop(self.expression(a, None)));
+				let other: Ty = self.zonk(&other);
+				self.diagnostics.push(
+					Diagnostic::error(format!("expected a function, found `{}`", other), span).with_code(codes::NOT_CALLABLE),
+				);
+				return Ty::Unknown;
+			},
+		}
+	}
+
+	/// Replaces the type parameters of a generic function or class with types to infer, for one use of it.
+	fn instantiate(&mut self, owner: NodeId, ty: &Ty, span: Span) -> Ty {
+		let ty: Ty = self.shallow(ty);
+		let params: Vec<(NodeId, String)> = self.type_params(owner);
+		if params.is_empty() {
+			return ty;
+		}
+
+		let owner: String = self.resolutions.defs.get(&owner).map(|d| d.name.clone()).unwrap_or_default();
+		let mut vars: Vec<(NodeId, Ty)> = Vec::new();
+		for (id, name) in params {
+			let var: Ty = self.var(format!("type parameter `{}` of `{}`", name, owner), span, None);
+			vars.push((id, var));
+		}
+		return ty.substitute(&|id| vars.iter().find(|(param, _)| *param == id).map(|(_, var)| var.clone()));
+	}
+
+	/// Finds a field or method of a class, or of a class it extends.
+	/// `None` is returned if the type has no members, after reporting it if it is a class.
+	fn member(&mut self, receiver: &Ty, name: &Identifier, what: &str) -> Option<(Option<NodeId>, Ty)> {
+		let receiver: Ty = self.shallow(receiver);
+		let current: Ty = match &receiver {
+			Ty::Static(inner) => (**inner).clone(),
+			other => other.clone(),
+		};
+
+		for ancestor in self.ancestors(&current) {
+			let (id, args) = match &ancestor {
+				Ty::Class(id, _, args) => (*id, args.clone()),
+				// members of other types are not declared anywhere yet
+				_ => return Some((None, Ty::Unknown)),
+			};
+			let info: ClassInfo = match self.classes.get(&id) {
+				Some(info) => info.clone(),
+				None => return Some((None, Ty::Unknown)),
+			};
+
+			let params: Vec<NodeId> = self.type_params(id).into_iter().map(|(id, _)| id).collect();
+			let substitute = |ty: &Ty| ty.substitute(&|param| params.iter().position(|p| *p == param).and_then(|i| args.get(i).cloned()));
+
+			if let Some((_, member)) = info.members.iter().find(|(n, _)| *n == name.name) {
+				// a field without a type that has not been checked yet is not known
+				let ty: Ty = self.types.defs.get(member).cloned().unwrap_or(Ty::Unknown);
+				return Some((Some(*member), substitute(&ty)));
+			}
+		}
+
+		let receiver: Ty = self.zonk(&receiver);
+		self.diagnostics.push(
+			Diagnostic::error(format!("no {} named `{}` on type `{}`", what, name.name, receiver), name.loc).with_code(codes::UNKNOWN_MEMBER),
+		);
+		return None;
+	}
+
+	/// Checks both sides of an operator, a number literal on one side takes the type of the other side.
+	fn operands(&mut self, a: &Expression, b: &Expression, hint: Option<&Ty>) -> (Ty, Ty) {
+		if is_number(a) && !is_number(b) {
+			let right: Ty = self.expression(b, hint);
+			let left: Ty = self.expression(a, Some(&right));
+			return (left, right);
+		}
+
+		let left: Ty = self.expression(a, hint);
+		let right: Ty = self.expression(b, Some(&left));
+		return (left, right);
+	}
+
+	fn binary(&mut self, op: &BinOp, left: &Ty, right: &Ty, a: &Expression, b: &Expression) -> Ty {
+		let (left, right) = (self.shallow(left), self.shallow(right));
+		if left.is_unknown() || right.is_unknown() {
+			return if left.is_unknown() { right } else { left };
+		}
+
+		let valid: bool = self.unify(&left, &right) && {
+			let ty: Ty = self.shallow(&left);
+			let any: bool = matches!(ty, Ty::Infer(InferTy::Var(_)));
+			match op {
+				BinOp::Plus => ty.is_numeric() || ty == Ty::String || any,
+				BinOp::Minus | BinOp::Star | BinOp::Slash | BinOp::Percent => ty.is_numeric() || any,
+				BinOp::Caret | BinOp::And | BinOp::Or | BinOp::Sh1 | BinOp::Shr | BinOp::UShr => ty.is_integer() || any,
+			}
+		};
+
+		if !valid {
+			self.invalid_operands(op.as_str(), &left, &right, a, b);
+			return Ty::Unknown;
+		}
+		return self.shallow(&left);
+	}
+
+	fn compare(&mut self, op: &ComparisonOp, left: &Ty, right: &Ty, a: &Expression, b: &Expression) {
+		let (left, right) = (self.shallow(left), self.shallow(right));
+		if left.is_unknown() || right.is_unknown() {
+			return;
+		}
+
+		let valid: bool = match op {
+			ComparisonOp::Eq | ComparisonOp::NotEq => self.is_assignable(&left, &right) || self.is_assignable(&right, &left),
+			ComparisonOp::GreaterThan | ComparisonOp::GreaterThanOrEqual | ComparisonOp::LessThan | ComparisonOp::LessThanOrEqual => {
+				self.unify(&left, &right) && {
+					let ty: Ty = self.shallow(&left);
+					ty.is_numeric() || matches!(ty, Ty::String | Ty::Char | Ty::Infer(InferTy::Var(_)))
+				}
+			},
+			ComparisonOp::Contains | ComparisonOp::In | ComparisonOp::InstanceOf => true,
+		};
+
+		if !valid {
+			self.invalid_operands(op.as_str(), &left, &right, a, b);
+		}
+	}
+
+	fn invalid_operands(&mut self, op: &str, left: &Ty, right: &Ty, a: &Expression, b: &Expression) {
+		let (left, right) = (self.zonk(left), self.zonk(right));
+		self.diagnostics.push(
+			Diagnostic::error(format!("cannot apply `{}` to `{}` and `{}`", op, left, right), a.loc.to(b.loc))
+				.with_code(codes::INVALID_OPERANDS)
+				.with_label(a.loc, format!("this is `{}`", left))
+				.with_label(b.loc, format!("this is `{}`", right)),
+		);
+	}
+
+	fn unary(&mut self, op: &UnaryOp, inner: &Expression, span: Span, expected: Option<&Ty>) -> Ty {
+		let ty: Ty = match (op, &inner.kind) {
+			(UnaryOp::Neg, ExpressionKind::Literal(Literal::Number(number))) => {
+				let ty: Ty = self.number(number, true, span, expected);
+				self.types.expressions.insert(inner.id, ty.clone());
+				ty
+			},
+			(UnaryOp::Not, _) => {
+				self.coerce(inner, &Ty::Bool, None);
+				return Ty::Bool;
+			},
+			(UnaryOp::Delete | UnaryOp::Object, _) => {
+				self.expression(inner, None);
+				return Ty::Unknown;
+			},
+			_ => self.expression(inner, expected),
+		};
+
+		self.unary_operand(op, &ty, span);
+		return ty;
+	}
+
+	/// Checks a unary operator can be applied to a type, once the type is known.
+	fn unary_operand(&mut self, op: &UnaryOp, ty: &Ty, span: Span) {
+		let valid: bool = match (op, self.shallow(ty)) {
+			(_, Ty::Unknown) | (_, Ty::Infer(InferTy::Var(_))) => true,
+			(UnaryOp::Neg, Ty::Int(int)) => int.is_signed(),
+			(UnaryOp::Neg, Ty::Infer(InferTy::Int(_))) => {
+				self.deferred.push(Deferred::Negate(ty.clone(), span));
+				true
+			},
+			(_, ty) => ty.is_numeric(),
+		};
+
+		if !valid {
+			let ty: Ty = self.zonk(ty);
+			self.diagnostics.push(
+				Diagnostic::error(format!("cannot apply unary operator `{}` to type `{}`", op.as_str(), ty), span)
+					.with_code(codes::INVALID_OPERANDS),
+			);
+		}
+	}
+
+	fn assign(&mut self, op: &AssignmentOp, target: &Expression, value: &Expression) -> Ty {
+		let target_ty: Ty = self.expression(target, None);
+		if let Some(def) = self.resolutions.def_of(target.id).filter(|d| d.kind == DefKind::Constant) {
+			let message: String = format!("cannot assign to `{}`, it is a constant", def.name);
+			let label: String = format!("`{}` is declared here", def.name);
+			self.diagnostics.push(
+				Diagnostic::error(message, target.loc).with_code(codes::ASSIGN_TO_CONSTANT).with_label(def.span, label),
+			);
+		}
+
+		let op: BinOp = match op {
+			AssignmentOp::Assign => {
+				self.coerce(value, &target_ty, None);
+				return target_ty;
+			},
+			AssignmentOp::BoolAnd | AssignmentOp::BoolOr => {
+				// the value is only checked against `bool` when the target is one, so a mismatch is reported once
+				if self.is_assignable(&target_ty, &Ty::Bool) {
+					self.coerce(value, &Ty::Bool, None);
+				} else {
+					self.mismatch(&Ty::Bool, &target_ty, target.loc, None);
+					self.expression(value, None);
+				}
+				return target_ty;
+			},
+			AssignmentOp::Coalesce => {
+				self.coerce(value, &target_ty, None);
+				return target_ty;
+			},
+			AssignmentOp::Add => BinOp::Plus,
+			AssignmentOp::Sub => BinOp::Minus,
+			AssignmentOp::Mul => BinOp::Star,
+			AssignmentOp::Div => BinOp::Slash,
+			AssignmentOp::Rem => BinOp::Percent,
+			AssignmentOp::And => BinOp::And,
+			AssignmentOp::Or => BinOp::Or,
+			AssignmentOp::Xor => BinOp::Caret,
+			AssignmentOp::Sh1 => BinOp::Sh1,
+			AssignmentOp::Shr => BinOp::Shr,
+			AssignmentOp::Ushr => BinOp::UShr,
+		};
+
+		let value_ty: Ty = self.expression(value, Some(&target_ty));
+		self.binary(&op, &target_ty, &value_ty, target, value);
+		return target_ty;
+	}
+
+	/// Whether a value of type `from` can be used where `to` is expected.
+	/// Types being inferred are decided by the first type they are used as, but only if it can be used.
+	pub fn is_assignable(&mut self, from: &Ty, to: &Ty) -> bool {
+		return self.probe(|checker| checker.assignable(from, to));
+	}
+
+	fn assignable(&mut self, from: &Ty, to: &Ty) -> bool {
+		let (from, to) = (self.shallow(from), self.shallow(to));
+		return match (&from, &to) {
+			(Ty::Unknown, _) | (_, Ty::Unknown) => true,
+			(Ty::Infer(a), Ty::Infer(b)) if a.index() == b.index() => true,
+			// the less specific variable is decided by the other, an integer can be a float but not the other way around
+			(Ty::Infer(a), Ty::Infer(b)) => {
+				let rank = |var: &InferTy| match var {
+					InferTy::Var(_) => 0,
+					InferTy::Int(_) => 1,
+					InferTy::Float(_) => 2,
+				};
+				if rank(a) <= rank(b) { self.bind(*a, &to) } else { self.bind(*b, &from) }
+			},
+			(Ty::Infer(var), _) => self.bind(*var, &to),
+			(_, Ty::Infer(var)) => self.bind(*var, &from),
+			_ if from == to => true,
+			(Ty::Union(variants), _) => variants.iter().all(|v| self.is_assignable(v, &to)),
+			(_, Ty::Union(variants)) => variants.iter().any(|v| self.is_assignable(&from, v)),
+			(Ty::Array(a), Ty::Array(b)) => self.is_assignable(a, b),
+			(Ty::Function(ps, r), Ty::Function(qs, s)) => {
+				ps.len() == qs.len() && qs.iter().zip(ps.iter()).all(|(q, p)| self.is_assignable(q, p)) && self.is_assignable(r, s)
+			},
+			// type arguments must be the same both ways, a `Box<u8>` is not a `Box<u8 | string>`
+			(Ty::Class(a, _, xs), Ty::Class(b, _, ys)) if a == b => xs.iter().zip(ys.iter()).all(|(x, y)| self.unify(x, y)),
+			// the class `to` names, or whatever a class extends that is not a class, eg: an unknown type
+			(Ty::Class(..), Ty::Class(b, ..)) => match self.ancestors(&from).into_iter().skip(1).find(|t| !matches!(t, Ty::Class(id, ..) if id != b)) {
+				Some(parent) => self.is_assignable(&parent, &to),
+				None => false,
+			},
+			_ => false,
+		};
+	}
+
+	/// Whether two types are the same, deciding the types being inferred in them.
+	fn unify(&mut self, a: &Ty, b: &Ty) -> bool {
+		return self.probe(|checker| checker.is_assignable(a, b) && checker.is_assignable(b, a));
+	}
+
+	/// Runs a check, undoing the types it decided if it fails, so trying a check never decides anything by itself.
+	fn probe(&mut self, check: impl FnOnce(&mut Self) -> bool) -> bool {
+		let mark: usize = self.decided.len();
+		if check(self) {
+			return true;
+		}
+
+		for index in self.decided.drain(mark..) {
+			self.vars[index as usize].value = None;
+		}
+		return false;
+	}
+
+	/// Makes a new type to infer, `what` and `span` describe it if it can not be.
+	fn var(&mut self, what: String, span: Span, default: Option<Ty>) -> Ty {
+		let index: u32 = self.vars.len() as u32;
+		let var: InferTy = match &default {
+			Some(Ty::Int(_)) => InferTy::Int(index),
+			Some(Ty::Float(_)) => InferTy::Float(index),
+			_ => InferTy::Var(index),
+		};
+		self.vars.push(Var { value: None, default: default, what: what, span: span });
+		return Ty::Infer(var);
+	}
+
+	/// Decides a type being inferred, failing if it can not be the type.
+	fn bind(&mut self, var: InferTy, ty: &Ty) -> bool {
+		// a number used as a union is the first number type in it
+		let ty: Ty = match (var, ty) {
+			(InferTy::Int(_) | InferTy::Float(_), Ty::Union(variants)) => match variants.iter().find(|v| self.accepts(var, v)) {
+				Some(variant) => variant.clone(),
+				None => return false,
+			},
+			_ => ty.clone(),
+		};
+
+		if !self.accepts(var, &ty) {
+			return false;
+		}
+		self.vars[var.index() as usize].value = Some(ty);
+		self.decided.push(var.index());
+		return true;
+	}
+
+	fn accepts(&self, var: InferTy, ty: &Ty) -> bool {
+		return match var {
+			InferTy::Var(index) => !self.occurs(index, ty),
+			InferTy::Int(_) => matches!(ty, Ty::Int(_) | Ty::Float(_) | Ty::Infer(InferTy::Int(_) | InferTy::Float(_))),
+			InferTy::Float(_) => matches!(ty, Ty::Float(_) | Ty::Infer(InferTy::Float(_))),
+		};
+	}
+
+	/// Whether a type contains the type being inferred, a type can not contain itself eg: `T = T[]`
+	fn occurs(&self, index: u32, ty: &Ty) -> bool {
+		let mut found: bool = false;
+		self.walk(&self.zonk(ty), &mut |t| found |= matches!(t, Ty::Infer(var) if var.index() == index));
+		return found;
+	}
+
+	/// Whether a type contains types that are not inferred yet.
+	fn has_vars(&self, ty: &Ty) -> bool {
+		let mut found: bool = false;
+		self.walk(&self.zonk(ty), &mut |t| found |= matches!(t, Ty::Infer(_)));
+		return found;
+	}
+
+	/// Calls `f` with a type and every type inside of it.
+	fn walk(&self, ty: &Ty, f: &mut dyn FnMut(&Ty)) {
+		f(ty);
+		match ty {
+			Ty::Array(inner) | Ty::Static(inner) => self.walk(inner, f),
+			Ty::Function(params, ret) => {
+				params.iter().for_each(|p| self.walk(p, f));
+				self.walk(ret, f);
+			},
+			Ty::Class(_, _, types) | Ty::Union(types) => types.iter().for_each(|t| self.walk(t, f)),
+			_ => (),
+		}
+	}
+
+	/// Follows a type being inferred to what it was decided to be, if it was.
+	fn shallow(&self, ty: &Ty) -> Ty {
+		let mut ty: Ty = ty.clone();
+		while let Ty::Infer(var) = ty {
+			match &self.vars[var.index() as usize].value {
+				Some(value) => ty = value.clone(),
+				None => break,
+			}
+		}
+		return ty;
+	}
+
+	/// Replaces every type being inferred in a type with what it was decided to be.
+	fn zonk(&self, ty: &Ty) -> Ty {
+		let all = |types: &[Ty]| types.iter().map(|t| self.zonk(t)).collect::<Vec<Ty>>();
+
+		return match self.shallow(ty) {
+			Ty::Array(inner) => Ty::Array(Box::new(self.zonk(&inner))),
+			Ty::Function(params, ret) => Ty::Function(all(&params), Box::new(self.zonk(&ret))),
+			Ty::Class(id, name, args) => Ty::Class(id, name, all(&args)),
+			Ty::Union(variants) => Ty::Union(all(&variants)),
+			Ty::Static(inner) => Ty::Static(Box::new(self.zonk(&inner))),
+			other => other,
+		};
+	}
+
+	/// Decides every type that is still being inferred, and reports those that can not be.
+	fn finish(&mut self) {
+		// numbers that were never used as a specific type have the type their size gives them
+		for var in self.vars.iter_mut() {
+			if var.value.is_none() {
+				var.value = var.default.clone();
+			}
+		}
+
+		for deferred in std::mem::take(&mut self.deferred) {
+			match deferred {
+				Deferred::Literal(value, negative, ty, span) => self.literal(value, negative, &ty, span),
+				Deferred::Negate(ty, span) => self.unary_operand(&UnaryOp::Neg, &ty, span),
+			}
+		}
+
+		// a binding is reported rather than what made its type, as that is where an annotation is needed
+		for (id, name) in std::mem::take(&mut self.bindings) {
+			let ty: Ty = self.zonk(&self.types.defs[&id]);
+			if self.has_vars(&ty) {
+				let note: String = match ty {
+					Ty::Infer(_) => format!("consider giving `{}` a type", name.name),
+					_ => format!("`{}` is only known to be `{}`, consider giving it a type", name.name, ty),
+				};
+				self.diagnostics.push(
+					Diagnostic::error(format!("cannot infer the type of `{}`", name.name), name.loc)
+						.with_code(codes::CANNOT_INFER)
+						.with_note(note),
+				);
+				self.forget(&ty);
+			}
+		}
+
+		for i in 0..self.vars.len() {
+			if self.vars[i].value.is_none() {
+				let var: &Var = &self.vars[i];
+				self.diagnostics.push(Diagnostic::error(format!("cannot infer {}", var.what), var.span).with_code(codes::CANNOT_INFER));
+				self.vars[i].value = Some(Ty::Unknown);
+			}
+		}
+
+		let types: TypeTable = std::mem::take(&mut self.types);
+		self.types = TypeTable {
+			expressions: types.expressions.iter().map(|(id, ty)| (*id, self.zonk(ty))).collect(),
+			defs: types.defs.iter().map(|(id, ty)| (*id, self.zonk(ty))).collect(),
+		};
+	}
+
+	/// Decides every type being inferred in a type is `Ty::Unknown`, so it is only reported once.
+	fn forget(&mut self, ty: &Ty) {
+		let mut vars: Vec<u32> = Vec::new();
+		self.walk(ty, &mut |t| {
+			if let Ty::Infer(var) = t {
+				vars.push(var.index());
+			}
+		});
+		for index in vars {
+			self.vars[index as usize].value = Some(Ty::Unknown);
+		}
+	}
+
+	/// The class a class extends, with its type arguments.
+	fn supertype(&self, ty: &Ty) -> Option<Ty> {
+		let (id, args) = match ty {
+			Ty::Class(id, _, args) => (*id, args),
+			_ => return None,
+		};
+		let extends: &Ty = self.classes.get(&id)?.extends.as_ref()?;
+		let params: Vec<NodeId> = self.type_params(id).into_iter().map(|(id, _)| id).collect();
+		return Some(extends.substitute(&|param| params.iter().position(|p| *p == param).and_then(|i| args.get(i).cloned())));
+	}
+
+	/// A type followed by the class it extends, the class that one extends and so on.
+	/// Each class is only in it once, so it ends even if a class extends itself.
+	fn ancestors(&self, ty: &Ty) -> Vec<Ty> {
+		let mut ancestors: Vec<Ty> = Vec::new();
+		let mut seen: BTreeSet<NodeId> = BTreeSet::new();
+		let mut current: Option<Ty> = Some(ty.clone());
+		while let Some(ty) = current {
+			if let Ty::Class(id, ..) = &ty {
+				if !seen.insert(*id) {
+					break;
+				}
+			}
+			current = self.supertype(&ty);
+			ancestors.push(ty);
+		}
+		return ancestors;
+	}
+}
+
+/// Whether an expression is a number literal, eg: `1` or `-1`
+fn is_number(expression: &Expression) -> bool {
+	return match &expression.kind {
+		ExpressionKind::Literal(Literal::Number(_)) => true,
+		ExpressionKind::Unary(UnaryOp::Neg, inner) | ExpressionKind::Paren(inner) => is_number(inner),
+		_ => false,
+	};
+}
+
+/// Whether running the statements always reaches a `return`.
+fn always_returns(statements: &[Statement]) -> bool {
+	return statements.iter().any(|statement| match &statement.kind {
+		StatementKind::Return(_) => true,
+		StatementKind::Scope(block) => always_returns(&block.statements),
+		StatementKind::If(_, then, Some(otherwise)) => always_returns(&then.statements) && always_returns(std::slice::from_ref(otherwise)),
+		_ => false,
+	});
+}
+
+fn plural(count: usize) -> &'static str {
+	return if count == 1 { "" } else { "s" };
+}
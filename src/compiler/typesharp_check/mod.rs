@@ -0,0 +1,7 @@
+pub mod checker;
+pub mod ty;
+
+pub use self::{
+	checker::{check, Checker, TypeTable},
+	ty::{FloatTy, InferTy, IntTy, Ty},
+};
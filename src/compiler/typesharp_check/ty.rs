@@ -0,0 +1,205 @@
+use crate::compiler::typesharp_ast::node::NodeId;
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum IntTy {
+	U8,
+	U16,
+	U32,
+	U64,
+	U128,
+	Usize,
+	I8,
+	I16,
+	I32,
+	I64,
+	I128,
+	Isize,
+}
+
+impl IntTy {
+	pub fn name(&self) -> &'static str {
+		return match self {
+			IntTy::U8 => "u8",
+			IntTy::U16 => "u16",
+			IntTy::U32 => "u32",
+			IntTy::U64 => "u64",
+			IntTy::U128 => "u128",
+			IntTy::Usize => "usize",
+			IntTy::I8 => "i8",
+			IntTy::I16 => "i16",
+			IntTy::I32 => "i32",
+			IntTy::I64 => "i64",
+			IntTy::I128 => "i128",
+			IntTy::Isize => "isize",
+		};
+	}
+
+	pub fn is_signed(&self) -> bool {
+		return matches!(self, IntTy::I8 | IntTy::I16 | IntTy::I32 | IntTy::I64 | IntTy::I128 | IntTy::Isize);
+	}
+
+	/// The smallest and largest values of the type, `usize` and `isize` are 64 bits wide.
+	pub fn range(&self) -> (i128, i128) {
+		return match self {
+			IntTy::U8 => (0, u8::MAX as i128),
+			IntTy::U16 => (0, u16::MAX as i128),
+			IntTy::U32 => (0, u32::MAX as i128),
+			IntTy::U64 | IntTy::Usize => (0, u64::MAX as i128),
+			// values past `i128::MAX` can not be written as literals yet
+			IntTy::U128 => (0, i128::MAX),
+			IntTy::I8 => (i8::MIN as i128, i8::MAX as i128),
+			IntTy::I16 => (i16::MIN as i128, i16::MAX as i128),
+			IntTy::I32 => (i32::MIN as i128, i32::MAX as i128),
+			IntTy::I64 | IntTy::Isize => (i64::MIN as i128, i64::MAX as i128),
+			IntTy::I128 => (i128::MIN, i128::MAX),
+		};
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FloatTy {
+	F32,
+	F64,
+}
+
+impl FloatTy {
+	pub fn name(&self) -> &'static str {
+		return match self {
+			FloatTy::F32 => "f32",
+			FloatTy::F64 => "f64",
+		};
+	}
+}
+
+/// A type that is not known yet, it is decided by how the value is used.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum InferTy {
+	/// Any type, eg: the type of `x` in `let x;`
+	Var(u32),
+	/// An integer or float type, eg: the type of `1`
+	Int(u32),
+	/// A float type, eg: the type of `1.5`
+	Float(u32),
+}
+
+impl InferTy {
+	pub fn index(&self) -> u32 {
+		return match self {
+			InferTy::Var(i) | InferTy::Int(i) | InferTy::Float(i) => *i,
+		};
+	}
+}
+
+/// The type of a value, as the checker sees it.
+/// Unlike `typesharp_ast::types::Type` this is not what was written, names are already resolved.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Ty {
+	Int(IntTy),
+	Float(FloatTy),
+	Bool,
+	String,
+	Char,
+	Void,
+	/// eg: `u8[]`
+	Array(Box<Ty>),
+	/// The parameters and return type of a function, eg: `fn(u8): bool`
+	Function(Vec<Ty>, Box<Ty>),
+	/// An instance of a class, with its type arguments, eg: `Box<u8>`
+	Class(NodeId, String, Vec<Ty>),
+	/// A value of an enum.
+	Enum(NodeId, String),
+	/// A type parameter of a generic function or class, it is only the same as itself.
+	Param(NodeId, String),
+	/// Any one of several types, eg: `u8 | string`
+	Union(Vec<Ty>),
+	/// A class or enum itself, rather than a value of it, eg: `Color` in `Color.Red`
+	Static(Box<Ty>),
+	/// A type being inferred, the checker decides it once the whole module is checked.
+	Infer(InferTy),
+	/// A type that is not known, eg: an unannotated parameter, or an expression that already has an error.
+	/// It is the same as every other type, so it never causes more errors.
+	Unknown,
+}
+
+impl Ty {
+	/// The type of a primitive written by name, eg: `u8` or `string`
+	pub fn primitive(name: &str) -> Option<Ty> {
+		return Some(match name {
+			"u8" => Ty::Int(IntTy::U8),
+			"u16" => Ty::Int(IntTy::U16),
+			"u32" => Ty::Int(IntTy::U32),
+			"u64" => Ty::Int(IntTy::U64),
+			"u128" => Ty::Int(IntTy::U128),
+			"usize" => Ty::Int(IntTy::Usize),
+			"i8" => Ty::Int(IntTy::I8),
+			"i16" => Ty::Int(IntTy::I16),
+			"i32" => Ty::Int(IntTy::I32),
+			"i64" => Ty::Int(IntTy::I64),
+			"i128" => Ty::Int(IntTy::I128),
+			"isize" => Ty::Int(IntTy::Isize),
+			"f32" => Ty::Float(FloatTy::F32),
+			"f64" => Ty::Float(FloatTy::F64),
+			"bool" => Ty::Bool,
+			"string" => Ty::String,
+			"char" => Ty::Char,
+			"void" => Ty::Void,
+			_ => return None,
+		});
+	}
+
+	pub fn is_numeric(&self) -> bool {
+		return matches!(self, Ty::Int(_) | Ty::Float(_) | Ty::Infer(InferTy::Int(_) | InferTy::Float(_)));
+	}
+
+	pub fn is_integer(&self) -> bool {
+		return matches!(self, Ty::Int(_) | Ty::Infer(InferTy::Int(_)));
+	}
+
+	pub fn is_unknown(&self) -> bool {
+		return *self == Ty::Unknown;
+	}
+
+	/// Replaces each type parameter with a type, eg: `T[]` with `u8[]`
+	/// Type parameters that are not given a type are left as they are.
+	pub fn substitute(&self, with: &dyn Fn(NodeId) -> Option<Ty>) -> Ty {
+		let all = |types: &[Ty]| types.iter().map(|t| t.substitute(with)).collect::<Vec<Ty>>();
+
+		return match self {
+			Ty::Param(id, _) => with(*id).unwrap_or_else(|| self.clone()),
+			Ty::Array(inner) => Ty::Array(Box::new(inner.substitute(with))),
+			Ty::Function(params, ret) => Ty::Function(all(params), Box::new(ret.substitute(with))),
+			Ty::Class(id, name, args) => Ty::Class(*id, name.clone(), all(args)),
+			Ty::Union(variants) => Ty::Union(all(variants)),
+			Ty::Static(inner) => Ty::Static(Box::new(inner.substitute(with))),
+			other => other.clone(),
+		};
+	}
+}
+
+impl fmt::Display for Ty {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let list = |types: &[Ty], separator: &str| types.iter().map(|t| t.to_string()).collect::<Vec<String>>().join(separator);
+
+		return match self {
+			Ty::Int(int) => f.write_str(int.name()),
+			Ty::Float(float) => f.write_str(float.name()),
+			Ty::Bool => f.write_str("bool"),
+			Ty::String => f.write_str("string"),
+			Ty::Char => f.write_str("char"),
+			Ty::Void => f.write_str("void"),
+			Ty::Array(inner) if matches!(**inner, Ty::Union(_) | Ty::Function(..)) => write!(f, "({})[]", inner),
+			Ty::Array(inner) => write!(f, "{}[]", inner),
+			Ty::Function(params, ret) => write!(f, "fn({}): {}", list(params, ", "), ret),
+			Ty::Class(_, name, args) if args.is_empty() => f.write_str(name),
+			Ty::Class(_, name, args) => write!(f, "{}<{}>", name, list(args, ", ")),
+			Ty::Enum(_, name) | Ty::Param(_, name) => f.write_str(name),
+			Ty::Union(variants) => f.write_str(&list(variants, " | ")),
+			Ty::Static(inner) => write!(f, "typeof {}", inner),
+			Ty::Infer(InferTy::Var(_)) => f.write_str("_"),
+			Ty::Infer(InferTy::Int(_)) => f.write_str("{integer}"),
+			Ty::Infer(InferTy::Float(_)) => f.write_str("{float}"),
+			Ty::Unknown => f.write_str("{unknown}"),
+		};
+	}
+}
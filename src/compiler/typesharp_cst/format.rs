@@ -0,0 +1,68 @@
+use super::kind::SyntaxKind;
+use super::syntax::SyntaxToken;
+use super::tree::parse;
+
+/// Formats a file, only whitespace is changed:
+/// - Lines are indented with a tab for each open `(`, `[` or `{`
+/// - Trailing whitespace is removed, and the file ends with a single newline
+/// - Runs of blank lines are collapsed in to one
+///
+/// Comments, strings and templates are kept exactly as they were written,
+/// even when they go over multiple lines.
+pub fn format(source: &str) -> String {
+	let mut out: String = String::with_capacity(source.len());
+	let mut depth: usize = 0;
+	let mut whitespace: String = String::new();
+	let mut line_start: bool = true;
+
+	for token in parse(source).tokens().iter() {
+		if token.kind() == SyntaxKind::WhiteSpace {
+			whitespace.push_str(token.text());
+			continue;
+		}
+
+		let newlines: usize = whitespace.matches('\n').count();
+		if newlines > 0 && !out.is_empty() {
+			trim_end(&mut out);
+			out.push_str(if newlines > 1 { "\n\n" } else { "\n" });
+			line_start = true;
+		} else if !line_start {
+			// spacing inside of a line is left to the author
+			out.push_str(&whitespace);
+		}
+		whitespace.clear();
+
+		if is_close(token) {
+			depth = depth.saturating_sub(1);
+		}
+		if line_start {
+			out.push_str(&"\t".repeat(depth));
+			line_start = false;
+		}
+
+		out.push_str(token.text());
+		if is_open(token) {
+			depth += 1;
+		}
+	}
+
+	trim_end(&mut out);
+	if !out.is_empty() {
+		out.push('\n');
+	}
+
+	return out;
+}
+
+fn is_open(token: &SyntaxToken) -> bool {
+	return matches!(token.kind(), SyntaxKind::LParen | SyntaxKind::LBracket | SyntaxKind::LBrace);
+}
+
+fn is_close(token: &SyntaxToken) -> bool {
+	return matches!(token.kind(), SyntaxKind::RParen | SyntaxKind::RBracket | SyntaxKind::RBrace);
+}
+
+fn trim_end(out: &mut String) {
+	let len: usize = out.trim_end_matches([' ', '\t', '\r', '\n']).len();
+	out.truncate(len);
+}
@@ -0,0 +1,189 @@
+use super::kind::SyntaxKind;
+use std::{fmt, rc::Rc};
+
+/// An immutable token in the green tree, it knows its text but not where it is.
+/// Identical tokens can be shared between trees.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct GreenToken {
+	kind: SyntaxKind,
+	text: String,
+}
+
+impl GreenToken {
+	pub fn new(kind: SyntaxKind, text: impl Into<String>) -> Self {
+		return GreenToken {
+			kind: kind,
+			text: text.into(),
+		};
+	}
+
+	pub fn kind(&self) -> SyntaxKind {
+		return self.kind;
+	}
+
+	pub fn text(&self) -> &str {
+		return &self.text;
+	}
+
+	/// The length of the token in bytes.
+	pub fn len(&self) -> usize {
+		return self.text.len();
+	}
+
+	pub fn is_empty(&self) -> bool {
+		return self.text.is_empty();
+	}
+}
+
+/// An immutable node in the green tree.
+/// Green nodes only know their kind, children and length, so unchanged
+/// subtrees can be shared between versions of a file.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct GreenNode {
+	kind: SyntaxKind,
+	children: Vec<GreenElement>,
+	len: usize,
+}
+
+impl GreenNode {
+	pub fn new(kind: SyntaxKind, children: Vec<GreenElement>) -> Self {
+		let len: usize = children.iter().map(|c| c.len()).sum();
+
+		return GreenNode {
+			kind: kind,
+			children: children,
+			len: len,
+		};
+	}
+
+	pub fn kind(&self) -> SyntaxKind {
+		return self.kind;
+	}
+
+	pub fn children(&self) -> &[GreenElement] {
+		return &self.children;
+	}
+
+	/// The length of all the text in the node, in bytes.
+	pub fn len(&self) -> usize {
+		return self.len;
+	}
+
+	pub fn is_empty(&self) -> bool {
+		return self.len == 0;
+	}
+}
+
+impl fmt::Display for GreenNode {
+	/// Writes the exact text the node was built from.
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for child in self.children.iter() {
+			match child {
+				GreenElement::Node(node) => write!(f, "{}", node)?,
+				GreenElement::Token(token) => f.write_str(token.text())?,
+			}
+		}
+
+		return Ok(());
+	}
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum GreenElement {
+	Node(Rc<GreenNode>),
+	Token(Rc<GreenToken>),
+}
+
+impl GreenElement {
+	pub fn kind(&self) -> SyntaxKind {
+		return match self {
+			GreenElement::Node(node) => node.kind(),
+			GreenElement::Token(token) => token.kind(),
+		};
+	}
+
+	pub fn len(&self) -> usize {
+		return match self {
+			GreenElement::Node(node) => node.len(),
+			GreenElement::Token(token) => token.len(),
+		};
+	}
+
+	pub fn is_empty(&self) -> bool {
+		return self.len() == 0;
+	}
+}
+
+/// A point in the builder that a node can be started at later,
+/// once it is known that the tokens after it belong to a node.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Checkpoint(usize);
+
+/// Builds a green tree from the top down.
+/// EG:
+/// ```rust
+/// use typesharp::compiler::typesharp_cst::{GreenNodeBuilder, SyntaxKind};
+///
+/// let mut builder = GreenNodeBuilder::new();
+/// builder.start_node(SyntaxKind::Module);
+/// builder.token(SyntaxKind::Identifier, "foo");
+/// builder.finish_node();
+/// assert_eq!(builder.finish().to_string(), "foo");
+/// ```
+#[derive(Default)]
+pub struct GreenNodeBuilder {
+	/// The nodes that have been started, and the index of their first child.
+	parents: Vec<(SyntaxKind, usize)>,
+	children: Vec<GreenElement>,
+}
+
+impl GreenNodeBuilder {
+	pub fn new() -> Self {
+		return GreenNodeBuilder {
+			parents: Vec::new(),
+			children: Vec::new(),
+		};
+	}
+
+	pub fn start_node(&mut self, kind: SyntaxKind) {
+		self.parents.push((kind, self.children.len()));
+	}
+
+	/// Adds a token to the node that was started last.
+	pub fn token(&mut self, kind: SyntaxKind, text: &str) {
+		self.children.push(GreenElement::Token(Rc::new(GreenToken::new(kind, text))));
+	}
+
+	/// Adds an existing node or token, eg: one reused from a previous tree.
+	pub fn element(&mut self, element: GreenElement) {
+		self.children.push(element);
+	}
+
+	pub fn finish_node(&mut self) {
+		let (kind, first) = self.parents.pop().expect("finish_node called without a matching start_node");
+		let children: Vec<GreenElement> = self.children.drain(first..).collect();
+		self.children.push(GreenElement::Node(Rc::new(GreenNode::new(kind, children))));
+	}
+
+	pub fn checkpoint(&self) -> Checkpoint {
+		return Checkpoint(self.children.len());
+	}
+
+	/// Starts a node that wraps everything added since the checkpoint.
+	pub fn start_node_at(&mut self, checkpoint: Checkpoint, kind: SyntaxKind) {
+		let Checkpoint(first) = checkpoint;
+		assert!(first <= self.children.len(), "checkpoint is no longer valid");
+		self.parents.push((kind, first));
+	}
+
+	/// Finishes the tree, there should be exactly one node left.
+	pub fn finish(mut self) -> GreenNode {
+		assert!(self.parents.is_empty(), "not every node was finished");
+		assert_eq!(self.children.len(), 1, "a tree needs exactly one root node");
+
+		return match self.children.pop() {
+			Some(GreenElement::Node(node)) => Rc::try_unwrap(node).unwrap_or_else(|node| (*node).clone()),
+			_ => panic!("the root of a tree must be a node"),
+		};
+	}
+}
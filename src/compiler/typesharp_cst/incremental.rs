@@ -0,0 +1,157 @@
+use super::green::GreenElement;
+use super::kind::SyntaxKind;
+use super::syntax::{SyntaxElement, SyntaxNode};
+use super::tree::{from_tokens, items, TreeBuilder};
+use crate::compiler::typesharp_ast::{FileId, Position, SourceFile, Span, TextEdit};
+use crate::compiler::typesharp_lexer::{relex, tokenize_file, Relexed, Token};
+use crate::error::{Diagnostic, DiagnosticSink};
+
+/// Rebuilds the tree of a file after it was re-lexed.
+/// Only the top level statements touched by the re-lexed tokens are
+/// built again, every other statement is reused from `old`.
+pub fn reparse(old: &SyntaxNode, source: &str, relexed: &Relexed) -> SyntaxNode {
+	let children: Vec<SyntaxElement> = old.children_with_tokens();
+	let green: &[GreenElement] = old.green().children();
+
+	// the first statement that could change, the statement before an edit
+	// is included as it may go on in to the edit, eg: `if x {}` + ` else {}`
+	let mut first: usize = children
+		.iter()
+		.position(|c| c.text_range().end >= relexed.old_range.start)
+		.unwrap_or(children.len());
+	while first > 0 && children[first - 1].kind().is_trivia() {
+		first -= 1;
+	}
+	first = first.saturating_sub(1);
+
+	let start: usize = children.get(first).map_or(relexed.old_range.start, |c| c.text_range().start);
+
+	// statements after the edit, and where they start now
+	let reusable: Vec<(usize, usize)> = children
+		.iter()
+		.enumerate()
+		.filter(|(_, c)| c.text_range().start >= relexed.old_range.end)
+		.map(|(i, c)| (i, c.text_range().start - relexed.from.offset() + relexed.to.offset()))
+		.collect();
+
+	let mut tree: TreeBuilder = TreeBuilder::new(items(source, &relexed.tokens));
+	while tree.offset().is_some_and(|offset| offset < start) {
+		tree.pos += 1;
+	}
+
+	tree.builder.start_node(SyntaxKind::Module);
+	for element in green[..first].iter() {
+		tree.builder.element(element.clone());
+	}
+
+	let edit_end: usize = relexed.new_range.end;
+	tree.items_until(false, |offset| offset >= edit_end && reusable.iter().any(|r| r.1 == offset));
+
+	if let Some(offset) = tree.offset() {
+		if let Some((index, _)) = reusable.iter().find(|r| r.1 == offset) {
+			for element in green[*index..].iter() {
+				tree.builder.element(element.clone());
+			}
+		}
+	}
+
+	tree.builder.finish_node();
+	return SyntaxNode::new_root(tree.builder.finish());
+}
+
+/// A file open in an editor, that is re-lexed and re-parsed as it is edited.
+pub struct Document {
+	file: FileId,
+	source: String,
+	tokens: Vec<Token>,
+	diagnostics: DiagnosticSink,
+	tree: SyntaxNode,
+}
+
+impl Document {
+	pub fn new(source: impl Into<String>) -> Self {
+		return Document::with_file(FileId::default(), source);
+	}
+
+	/// Creates a document for a file loaded in a `SourceMap`,
+	/// every span in the document is given the file's id.
+	pub fn with_file(file: FileId, source: impl Into<String>) -> Self {
+		let source: String = source.into();
+		let (tokens, diagnostics) = tokenize_file(&SourceFile::new(file, "", source.as_str()));
+		let tree: SyntaxNode = SyntaxNode::new_root(from_tokens(&source, &tokens));
+
+		return Document {
+			file: file,
+			source: source,
+			tokens: tokens,
+			diagnostics: diagnostics,
+			tree: tree,
+		};
+	}
+
+	pub fn source(&self) -> &str {
+		return &self.source;
+	}
+
+	pub fn tokens(&self) -> &[Token] {
+		return &self.tokens;
+	}
+
+	/// Problems found while lexing the document.
+	pub fn diagnostics(&self) -> &DiagnosticSink {
+		return &self.diagnostics;
+	}
+
+	pub fn tree(&self) -> &SyntaxNode {
+		return &self.tree;
+	}
+
+	/// Applies an edit, re-lexing and re-parsing only the code it touched.
+	/// Returns false, and leaves the document as is, if the edit is not in the document.
+	pub fn edit(&mut self, edit: &TextEdit) -> bool {
+		let source: String = match edit.apply(&self.source) {
+			Some(source) => source,
+			None => return false,
+		};
+
+		let mut relexed: Relexed = relex(&self.tokens, edit, &source, self.file);
+		self.tree = reparse(&self.tree, &source, &relexed);
+
+		// diagnostics in the re-lexed code were found again, the rest are kept
+		let mut diagnostics: DiagnosticSink = DiagnosticSink::new();
+		let mut after: DiagnosticSink = DiagnosticSink::new();
+		for diagnostic in std::mem::take(&mut self.diagnostics) {
+			let span: Span = diagnostic.span;
+			if span.end().offset() <= relexed.old_range.start {
+				diagnostics.push(diagnostic);
+			} else if span.start().offset() >= relexed.old_range.end {
+				after.push(moved(diagnostic, relexed.from, relexed.to));
+			}
+		}
+		diagnostics.append(&mut relexed.diagnostics);
+		diagnostics.append(&mut after);
+
+		self.source = source;
+		self.tokens = relexed.tokens;
+		self.diagnostics = diagnostics;
+		return true;
+	}
+}
+
+/// Moves every span in a diagnostic that comes after `from`, see `Span::moved`.
+fn moved(mut diagnostic: Diagnostic, from: Position, to: Position) -> Diagnostic {
+	let shift = |span: Span| match span.start() >= from {
+		true => span.moved(from, to),
+		false => span,
+	};
+
+	diagnostic.span = shift(diagnostic.span);
+	for label in diagnostic.labels.iter_mut() {
+		label.span = shift(label.span);
+	}
+	for suggestion in diagnostic.suggestions.iter_mut() {
+		suggestion.span = shift(suggestion.span);
+	}
+
+	return diagnostic;
+}
@@ -0,0 +1,95 @@
+use crate::compiler::typesharp_lexer::{Token, TokenKind};
+
+/// The kind of a node or token in the concrete syntax tree.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum SyntaxKind {
+	// Tokens
+	WhiteSpace,
+	Comment,
+	Keyword,
+	Identifier,
+	Number,
+	String,
+	Template,
+	Bool,
+	/// Any operator, eg: `+`, `&&` or `>>=`
+	Operator,
+	Accessor,
+	Semicolon,
+	Colon,
+	Comma,
+	/// `@`, which starts a directive
+	At,
+	LParen,
+	RParen,
+	LBracket,
+	RBracket,
+	LBrace,
+	RBrace,
+	/// A token that could not be lexed, it has already been reported by the lexer.
+	Error,
+	Unknown,
+
+	// Nodes
+	/// The root of every tree, a whole file.
+	Module,
+	/// A statement, from its first token up to and including its `;`.
+	Statement,
+	/// A block wrapped in `{}`
+	Block,
+	/// Tokens that do not belong anywhere, eg: an unmatched `}`
+	ErrorNode,
+}
+
+impl SyntaxKind {
+	/// Gets the kind of a lexed token, `text` is the code the token was lexed from.
+	pub fn from_token(token: &Token, text: &str) -> SyntaxKind {
+		return match &token.kind {
+			TokenKind::WhiteSpace | TokenKind::Indent => SyntaxKind::WhiteSpace,
+			TokenKind::CommentLiteral(_) => SyntaxKind::Comment,
+			TokenKind::Keyword(_) => SyntaxKind::Keyword,
+			TokenKind::Identifier(_) | TokenKind::GenericType(_) => SyntaxKind::Identifier,
+			TokenKind::NumberLiteral(_) => SyntaxKind::Number,
+			TokenKind::StringLiteral(_) => SyntaxKind::String,
+			TokenKind::TemplateLiteral(_) => SyntaxKind::Template,
+			TokenKind::BoolLiteral(_) => SyntaxKind::Bool,
+			TokenKind::BinaryOpLiteral(_)
+			| TokenKind::UnaryOpLiteral(_)
+			| TokenKind::AssignmentLiteral(_)
+			| TokenKind::LogicalOpLiteral(_)
+			| TokenKind::ComparisonOpLiteral(_) => SyntaxKind::Operator,
+			TokenKind::Accessor => SyntaxKind::Accessor,
+			TokenKind::ExpressionTerminator => SyntaxKind::Semicolon,
+			TokenKind::Colon => SyntaxKind::Colon,
+			TokenKind::Comma => SyntaxKind::Comma,
+			TokenKind::At => SyntaxKind::At,
+			TokenKind::ErrorLiteral => SyntaxKind::Error,
+			TokenKind::DelimiterLiteral(_) | TokenKind::Unknown(_) => SyntaxKind::from_delimiter(text).unwrap_or(SyntaxKind::Unknown),
+			TokenKind::EOF => SyntaxKind::Unknown,
+		};
+	}
+
+	fn from_delimiter(text: &str) -> Option<SyntaxKind> {
+		return Some(match text {
+			"(" => SyntaxKind::LParen,
+			")" => SyntaxKind::RParen,
+			"[" => SyntaxKind::LBracket,
+			"]" => SyntaxKind::RBracket,
+			"{" => SyntaxKind::LBrace,
+			"}" => SyntaxKind::RBrace,
+			_ => return None,
+		});
+	}
+
+	/// Whether the kind has no meaning to the parser, eg: whitespace and comments.
+	pub fn is_trivia(&self) -> bool {
+		return matches!(self, SyntaxKind::WhiteSpace | SyntaxKind::Comment);
+	}
+
+	pub fn is_node(&self) -> bool {
+		return matches!(
+			self,
+			SyntaxKind::Module | SyntaxKind::Statement | SyntaxKind::Block | SyntaxKind::ErrorNode
+		);
+	}
+}
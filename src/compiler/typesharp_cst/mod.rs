@@ -0,0 +1,15 @@
+pub mod format;
+pub mod green;
+pub mod incremental;
+pub mod kind;
+pub mod syntax;
+pub mod tree;
+
+pub use self::{
+	format::format,
+	green::{Checkpoint, GreenElement, GreenNode, GreenNodeBuilder, GreenToken},
+	incremental::{reparse, Document},
+	kind::SyntaxKind,
+	syntax::{SyntaxElement, SyntaxNode, SyntaxToken},
+	tree::{from_tokens, parse},
+};
@@ -0,0 +1,275 @@
+use super::green::{GreenElement, GreenNode, GreenToken};
+use super::kind::SyntaxKind;
+use std::{fmt, ops::Range, rc::Rc};
+
+struct NodeData {
+	green: Rc<GreenNode>,
+	parent: Option<SyntaxNode>,
+	/// The index of the node in its parent.
+	index: usize,
+	/// The byte offset of the node in the file.
+	offset: usize,
+}
+
+/// A node in the red tree, a view of a green node that knows its
+/// parent and where it is in the file.
+/// Red nodes are cheap to clone, and are created as the tree is walked.
+#[derive(Clone)]
+pub struct SyntaxNode {
+	data: Rc<NodeData>,
+}
+
+impl SyntaxNode {
+	/// Creates the root of a tree.
+	pub fn new_root(green: GreenNode) -> Self {
+		return SyntaxNode {
+			data: Rc::new(NodeData {
+				green: Rc::new(green),
+				parent: None,
+				index: 0,
+				offset: 0,
+			}),
+		};
+	}
+
+	fn new_child(green: Rc<GreenNode>, parent: SyntaxNode, index: usize, offset: usize) -> Self {
+		return SyntaxNode {
+			data: Rc::new(NodeData {
+				green: green,
+				parent: Some(parent),
+				index: index,
+				offset: offset,
+			}),
+		};
+	}
+
+	pub fn kind(&self) -> SyntaxKind {
+		return self.data.green.kind();
+	}
+
+	pub fn green(&self) -> &Rc<GreenNode> {
+		return &self.data.green;
+	}
+
+	pub fn parent(&self) -> Option<&SyntaxNode> {
+		return self.data.parent.as_ref();
+	}
+
+	/// The index of the node in its parent.
+	pub fn index(&self) -> usize {
+		return self.data.index;
+	}
+
+	/// The bytes of the file covered by the node.
+	pub fn text_range(&self) -> Range<usize> {
+		return self.data.offset..self.data.offset + self.data.green.len();
+	}
+
+	/// The exact text the node was built from, including trivia.
+	pub fn text(&self) -> String {
+		return self.data.green.to_string();
+	}
+
+	/// Every child node and token, in order.
+	pub fn children_with_tokens(&self) -> Vec<SyntaxElement> {
+		let mut offset: usize = self.data.offset;
+		let mut children: Vec<SyntaxElement> = Vec::new();
+
+		for (index, child) in self.data.green.children().iter().enumerate() {
+			children.push(match child {
+				GreenElement::Node(node) => {
+					SyntaxElement::Node(SyntaxNode::new_child(node.clone(), self.clone(), index, offset))
+				},
+				GreenElement::Token(token) => SyntaxElement::Token(SyntaxToken {
+					green: token.clone(),
+					parent: self.clone(),
+					index: index,
+					offset: offset,
+				}),
+			});
+			offset += child.len();
+		}
+
+		return children;
+	}
+
+	/// Every child node, skipping tokens.
+	pub fn children(&self) -> Vec<SyntaxNode> {
+		return self.children_with_tokens().into_iter().filter_map(|c| c.into_node()).collect();
+	}
+
+	/// This node, and every node below it, in order.
+	pub fn descendants(&self) -> Vec<SyntaxNode> {
+		let mut nodes: Vec<SyntaxNode> = vec![self.clone()];
+		for child in self.children() {
+			nodes.extend(child.descendants());
+		}
+
+		return nodes;
+	}
+
+	/// Every token below this node in order, including trivia.
+	pub fn tokens(&self) -> Vec<SyntaxToken> {
+		let mut tokens: Vec<SyntaxToken> = Vec::new();
+		for child in self.children_with_tokens() {
+			match child {
+				SyntaxElement::Node(node) => tokens.extend(node.tokens()),
+				SyntaxElement::Token(token) => tokens.push(token),
+			}
+		}
+
+		return tokens;
+	}
+
+	pub fn first_token(&self) -> Option<SyntaxToken> {
+		return self.tokens().into_iter().next();
+	}
+
+	pub fn last_token(&self) -> Option<SyntaxToken> {
+		return self.tokens().pop();
+	}
+
+	/// Finds the token that covers a byte offset.
+	pub fn token_at_offset(&self, offset: usize) -> Option<SyntaxToken> {
+		return self.tokens().into_iter().find(|t| t.text_range().contains(&offset));
+	}
+
+	/// Writes the tree with one element per line, eg:
+	/// ```text
+	/// Module@0..6
+	///   Statement@0..6
+	///     Keyword@0..3 "let"
+	/// ```
+	pub fn debug_tree(&self) -> String {
+		let mut out: String = String::new();
+		self.write_tree(&mut out, 0);
+		return out;
+	}
+
+	fn write_tree(&self, out: &mut String, depth: usize) {
+		out.push_str(&format!("{}{:?}\n", "  ".repeat(depth), self));
+		for child in self.children_with_tokens() {
+			match child {
+				SyntaxElement::Node(node) => node.write_tree(out, depth + 1),
+				SyntaxElement::Token(token) => out.push_str(&format!("{}{:?}\n", "  ".repeat(depth + 1), token)),
+			}
+		}
+	}
+}
+
+impl PartialEq for SyntaxNode {
+	fn eq(&self, other: &Self) -> bool {
+		return Rc::ptr_eq(&self.data.green, &other.data.green) && self.data.offset == other.data.offset;
+	}
+}
+
+impl fmt::Display for SyntaxNode {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		return write!(f, "{}", self.data.green);
+	}
+}
+
+impl fmt::Debug for SyntaxNode {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let range: Range<usize> = self.text_range();
+		return write!(f, "{:?}@{}..{}", self.kind(), range.start, range.end);
+	}
+}
+
+/// A token in the red tree.
+#[derive(Clone)]
+pub struct SyntaxToken {
+	green: Rc<GreenToken>,
+	parent: SyntaxNode,
+	/// The index of the token in its parent.
+	index: usize,
+	/// The byte offset of the token in the file.
+	offset: usize,
+}
+
+impl SyntaxToken {
+	pub fn kind(&self) -> SyntaxKind {
+		return self.green.kind();
+	}
+
+	pub fn text(&self) -> &str {
+		return self.green.text();
+	}
+
+	pub fn green(&self) -> &Rc<GreenToken> {
+		return &self.green;
+	}
+
+	pub fn parent(&self) -> &SyntaxNode {
+		return &self.parent;
+	}
+
+	/// The index of the token in its parent.
+	pub fn index(&self) -> usize {
+		return self.index;
+	}
+
+	/// The bytes of the file covered by the token.
+	pub fn text_range(&self) -> Range<usize> {
+		return self.offset..self.offset + self.green.len();
+	}
+
+	pub fn is_trivia(&self) -> bool {
+		return self.kind().is_trivia();
+	}
+}
+
+impl PartialEq for SyntaxToken {
+	fn eq(&self, other: &Self) -> bool {
+		return Rc::ptr_eq(&self.green, &other.green) && self.offset == other.offset;
+	}
+}
+
+impl fmt::Display for SyntaxToken {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		return f.write_str(self.text());
+	}
+}
+
+impl fmt::Debug for SyntaxToken {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let range: Range<usize> = self.text_range();
+		return write!(f, "{:?}@{}..{} {:?}", self.kind(), range.start, range.end, self.text());
+	}
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum SyntaxElement {
+	Node(SyntaxNode),
+	Token(SyntaxToken),
+}
+
+impl SyntaxElement {
+	pub fn kind(&self) -> SyntaxKind {
+		return match self {
+			SyntaxElement::Node(node) => node.kind(),
+			SyntaxElement::Token(token) => token.kind(),
+		};
+	}
+
+	pub fn text_range(&self) -> Range<usize> {
+		return match self {
+			SyntaxElement::Node(node) => node.text_range(),
+			SyntaxElement::Token(token) => token.text_range(),
+		};
+	}
+
+	pub fn into_node(self) -> Option<SyntaxNode> {
+		return match self {
+			SyntaxElement::Node(node) => Some(node),
+			SyntaxElement::Token(_) => None,
+		};
+	}
+
+	pub fn into_token(self) -> Option<SyntaxToken> {
+		return match self {
+			SyntaxElement::Node(_) => None,
+			SyntaxElement::Token(token) => Some(token),
+		};
+	}
+}
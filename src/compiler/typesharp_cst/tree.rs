@@ -0,0 +1,173 @@
+use super::green::{GreenNode, GreenNodeBuilder};
+use super::kind::SyntaxKind;
+use super::syntax::SyntaxNode;
+use crate::compiler::typesharp_lexer::{tokenize, Token};
+
+/// Lexes and builds the concrete syntax tree of a file.
+/// The tree keeps every byte of `source`, so `parse(source).text() == source`.
+pub fn parse(source: &str) -> SyntaxNode {
+	return SyntaxNode::new_root(from_tokens(source, &tokenize(source)));
+}
+
+/// Builds a green tree from tokens that were lexed from `source`.
+pub fn from_tokens(source: &str, tokens: &[Token]) -> GreenNode {
+	let mut tree: TreeBuilder = TreeBuilder::new(items(source, tokens));
+	tree.builder.start_node(SyntaxKind::Module);
+	tree.items(false);
+	tree.builder.finish_node();
+	return tree.builder.finish();
+}
+
+/// Gets the kind, text and byte offset of each token.
+/// Any code between tokens that the lexer skipped is kept as an `Unknown` token.
+pub(crate) fn items<'a>(source: &'a str, tokens: &[Token]) -> Vec<(SyntaxKind, &'a str, usize)> {
+	let mut items: Vec<(SyntaxKind, &str, usize)> = Vec::new();
+	let mut offset: usize = 0;
+
+	for token in tokens.iter() {
+		let range = token.span.byte_range();
+		if range.start < offset || range.end > source.len() {
+			continue;
+		}
+
+		if range.start > offset {
+			items.push((SyntaxKind::Unknown, &source[offset..range.start], offset));
+		}
+
+		let text: &str = &source[range.clone()];
+		items.push((SyntaxKind::from_token(token, text), text, range.start));
+		offset = range.end;
+	}
+
+	if offset < source.len() {
+		items.push((SyntaxKind::Unknown, &source[offset..], offset));
+	}
+
+	return items;
+}
+
+/// Groups tokens in to statements and blocks.
+/// This only looks at `;` and `{}`, so that any code (even code that
+/// does not parse) still builds a tree.
+pub(crate) struct TreeBuilder<'a> {
+	tokens: Vec<(SyntaxKind, &'a str, usize)>,
+	pub(crate) pos: usize,
+	pub(crate) builder: GreenNodeBuilder,
+}
+
+impl<'a> TreeBuilder<'a> {
+	pub(crate) fn new(tokens: Vec<(SyntaxKind, &'a str, usize)>) -> Self {
+		return TreeBuilder {
+			tokens: tokens,
+			pos: 0,
+			builder: GreenNodeBuilder::new(),
+		};
+	}
+
+	/// The byte offset of the current token.
+	pub(crate) fn offset(&self) -> Option<usize> {
+		return self.tokens.get(self.pos).map(|t| t.2);
+	}
+
+	/// Consumes statements until the end of the file, or a `}` in a block.
+	/// Trivia between statements belongs to the parent, not to either statement.
+	pub(crate) fn items(&mut self, in_block: bool) {
+		self.items_until(in_block, |_| false);
+	}
+
+	/// Consumes statements like `items`, but stops before any statement
+	/// or trivia that starts at an offset where `stop` returns true.
+	pub(crate) fn items_until(&mut self, in_block: bool, stop: impl Fn(usize) -> bool) {
+		while let Some(kind) = self.current() {
+			if self.offset().is_some_and(&stop) {
+				return;
+			}
+
+			match kind {
+				k if k.is_trivia() => self.bump(),
+				SyntaxKind::RBrace if in_block => return,
+				SyntaxKind::RBrace => {
+					self.builder.start_node(SyntaxKind::ErrorNode);
+					self.bump();
+					self.builder.finish_node();
+				},
+				_ => self.statement(),
+			}
+		}
+	}
+
+	/// Consumes a statement, up to and including its `;`.
+	/// A statement that ends with a block does not need a `;`, eg: `if x {}`
+	fn statement(&mut self) {
+		self.builder.start_node(SyntaxKind::Statement);
+
+		while let Some(kind) = self.current() {
+			match kind {
+				SyntaxKind::RBrace => break,
+				k if k.is_trivia() && self.at_end_of_statement() => break,
+				SyntaxKind::LBrace => {
+					self.block();
+					if !self.continues_after_block() {
+						break;
+					}
+				},
+				SyntaxKind::Semicolon => {
+					self.bump();
+					break;
+				},
+				_ => self.bump(),
+			}
+		}
+
+		self.builder.finish_node();
+	}
+
+	fn block(&mut self) {
+		self.builder.start_node(SyntaxKind::Block);
+		self.bump();
+		self.items(true);
+		if self.current() == Some(SyntaxKind::RBrace) {
+			self.bump();
+		}
+		self.builder.finish_node();
+	}
+
+	/// Whether only trivia is left before a `}` or the end of the file.
+	fn at_end_of_statement(&self) -> bool {
+		return matches!(self.next_significant(), None | Some((SyntaxKind::RBrace, _)));
+	}
+
+	/// Whether the statement goes on after a block, eg: `if x {} else {}` or `fn () {}();`
+	fn continues_after_block(&self) -> bool {
+		return match self.next_significant() {
+			Some((SyntaxKind::Keyword, text)) => matches!(text, "else" | "catch" | "finally"),
+			Some((kind, _)) => matches!(
+				kind,
+				SyntaxKind::Semicolon
+					| SyntaxKind::Accessor
+					| SyntaxKind::Operator
+					| SyntaxKind::Comma
+					| SyntaxKind::Colon
+					| SyntaxKind::LParen
+					| SyntaxKind::RParen
+					| SyntaxKind::RBracket
+			),
+			None => false,
+		};
+	}
+
+	fn next_significant(&self) -> Option<(SyntaxKind, &'a str)> {
+		return self.tokens[self.pos..].iter().find(|t| !t.0.is_trivia()).map(|t| (t.0, t.1));
+	}
+
+	fn current(&self) -> Option<SyntaxKind> {
+		return self.tokens.get(self.pos).map(|t| t.0);
+	}
+
+	fn bump(&mut self) {
+		if let Some((kind, text, _)) = self.tokens.get(self.pos) {
+			self.builder.token(*kind, text);
+			self.pos += 1;
+		}
+	}
+}
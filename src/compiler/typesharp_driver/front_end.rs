@@ -0,0 +1,46 @@
+use crate::compiler::{
+	typesharp_ast::{ ast::Module, Edition },
+	typesharp_check::{ check, TypeTable },
+	typesharp_feature::{ check_features, Features },
+	typesharp_lexer::{ apply_edition, confusable_identifiers, edition_keywords, flatten, token_trees, Token },
+	typesharp_parser::Parser,
+	typesharp_resolve::{ resolve, Resolutions },
+};
+use crate::error::DiagnosticSink;
+
+/// Parses the tokens of a file in `edition`, then rejects the experimental syntax whose feature is not enabled.
+/// The module is returned even when it has errors, the diagnostics are in the order they were found.
+pub fn parse_front_end(mut tokens: Vec<Token>, edition: Edition, features: &Features) -> (Module, DiagnosticSink) {
+	let mut diagnostics: DiagnosticSink = DiagnosticSink::new();
+	apply_edition(&mut tokens, edition);
+	diagnostics.append(&mut edition_keywords(&tokens, edition));
+	diagnostics.append(&mut confusable_identifiers(&tokens));
+
+	// the parser only sees balanced delimiters, they were already reported here
+	let (trees, mut found) = token_trees(&tokens);
+	diagnostics.append(&mut found);
+	let tokens: Vec<Token> = flatten(&trees);
+
+	let mut parser: Parser = Parser::new(&tokens);
+	let module: Module = parser.parse_module();
+	diagnostics.append(&mut parser.diagnostics);
+
+	let (_, mut found) = check_features(&module, &parser.gated, features);
+	diagnostics.append(&mut found);
+
+	return (module, diagnostics);
+}
+
+/// Everything before code generation: parses the tokens of a file, binds every name to its declaration and checks types.
+/// Both the command line and the language server compile a file with this, so they report the same problems.
+pub fn compile_front_end(tokens: Vec<Token>, edition: Edition, features: &Features) -> (Module, Resolutions, TypeTable, DiagnosticSink) {
+	let (module, mut diagnostics) = parse_front_end(tokens, edition, features);
+
+	let (resolutions, mut found) = resolve(&module);
+	diagnostics.append(&mut found);
+
+	let (types, mut found) = check(&module, &resolutions);
+	diagnostics.append(&mut found);
+
+	return (module, resolutions, types, diagnostics);
+}
@@ -0,0 +1,3 @@
+pub mod front_end;
+
+pub use self::front_end::{ compile_front_end, parse_front_end };
@@ -0,0 +1,130 @@
+use std::fmt;
+
+/// Whether a feature can be used without enabling it.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Status {
+	/// Part of the language, enabling it does nothing.
+	Stable,
+	/// Experimental, it may change or be removed, so it has to be enabled to be used.
+	Unstable,
+}
+
+impl fmt::Display for Status {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		return f.write_str(match self {
+			Status::Stable => "stable",
+			Status::Unstable => "unstable",
+		});
+	}
+}
+
+/// Declares `Feature` from a table of each feature, its name in `@compiler(...)`, its status,
+/// what it allows and the issue discussing it.
+macro_rules! features {
+	($( $(#[$attr:meta])* $variant:ident => $name:literal, $status:ident, $description:literal, $issue:expr; )*) => {
+		/// A part of the language that can be turned on, eg: with `@compiler(deleteOperator);`
+		#[derive(Clone, Copy, PartialEq, Debug)]
+		pub enum Feature {
+			$( $(#[$attr])* $variant, )*
+		}
+
+		impl Feature {
+			/// Every feature, in the order they are declared.
+			pub const ALL: &'static [Feature] = &[$( Self::$variant, )*];
+
+			/// How the feature is written, eg: `deleteOperator`
+			pub fn name(&self) -> &'static str {
+				return match self {
+					$( Self::$variant => $name, )*
+				};
+			}
+
+			pub fn status(&self) -> Status {
+				return match self {
+					$( Self::$variant => Status::$status, )*
+				};
+			}
+
+			/// What the feature allows, eg: "the `delete` operator"
+			pub fn description(&self) -> &'static str {
+				return match self {
+					$( Self::$variant => $description, )*
+				};
+			}
+
+			/// The number of the issue discussing the feature.
+			pub fn issue(&self) -> Option<u32> {
+				return match self {
+					$( Self::$variant => $issue, )*
+				};
+			}
+
+			/// The feature written as `name`, eg: `DeleteOperator` for `deleteOperator`
+			pub fn from_name(name: &str) -> Option<Feature> {
+				return match name {
+					$( $name => Some(Self::$variant), )*
+					_ => None,
+				};
+			}
+		}
+	};
+}
+
+features! {
+	/// Sized numeric types, eg: `u8` and `f32`
+	NumericTypes => "numericTypes", Stable, "sized numeric types", None;
+
+	/// `delete x`, it may be removed for memory safety.
+	DeleteOperator => "deleteOperator", Unstable, "the `delete` operator", Some(1);
+
+	/// `final` on class members.
+	FinalMembers => "finalMembers", Unstable, "`final` on class members", Some(10);
+
+	/// `override` on class members.
+	OverrideMembers => "overrideMembers", Unstable, "`override` on class members", Some(10);
+
+	/// `x &&= y`, `x ||= y` and `x ??= y`, `??=` may not be kept.
+	LogicalAssignment => "logicalAssignment", Unstable, "logical assignment", None;
+
+	/// `package geometry;`, the statements after it are in the package `geometry`.
+	Packages => "packages", Unstable, "the `package` declaration", None;
+}
+
+impl Feature {
+	pub fn is_stable(&self) -> bool {
+		return self.status() == Status::Stable;
+	}
+
+	/// A link to the issue discussing the feature.
+	pub fn issue_url(&self) -> Option<String> {
+		return self.issue().map(|issue| format!("https://github.com/TypeSharp/Typesharp/issues/{}", issue));
+	}
+}
+
+impl fmt::Display for Feature {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		return f.write_str(self.name());
+	}
+}
+
+/// The features enabled for a module, stable features are always enabled.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Features {
+	enabled: Vec<Feature>,
+}
+
+impl Features {
+	pub fn new() -> Self {
+		return Features::default();
+	}
+
+	pub fn enable(&mut self, feature: Feature) {
+		if !self.enabled.contains(&feature) {
+			self.enabled.push(feature);
+		}
+	}
+
+	pub fn is_enabled(&self, feature: Feature) -> bool {
+		return feature.is_stable() || self.enabled.contains(&feature);
+	}
+}
@@ -0,0 +1,64 @@
+use super::feature::{ Feature, Features };
+use crate::compiler::typesharp_ast::{ ast::Module, Span };
+use crate::error::{ codes, Diagnostic, DiagnosticSink };
+
+/// Enables the features named by the module's `@compiler(...)` directives, on top of the ones already `enabled`,
+/// then rejects each use of experimental syntax whose feature is not enabled.
+/// `gated` is the experimental syntax the parser found, and the feature each one needs.
+/// Unknown directives, and `@edition` directives after a statement, are reported as well.
+pub fn check_features(module: &Module, gated: &[(Feature, Span)], enabled: &Features) -> (Features, DiagnosticSink) {
+	let mut features: Features = enabled.clone();
+	let mut diagnostics: DiagnosticSink = DiagnosticSink::new();
+
+	let first: Option<Span> = module.statements.first().map(|s| s.loc);
+	for directive in module.directives.iter() {
+		// the edition is read before parsing, by `typesharp_lexer::edition_directive`
+		if directive.name.name == "edition" {
+			if let Some(first) = first.filter(|s| s.start().offset() < directive.loc.start().offset()) {
+				diagnostics.push(
+					Diagnostic::error("`@edition` has to come before every statement", directive.loc)
+						.with_code(codes::MISPLACED_EDITION)
+						.with_label(first, "the first statement is here"),
+				);
+			}
+			continue;
+		}
+
+		if directive.name.name != "compiler" {
+			diagnostics.push(
+				Diagnostic::error(format!("unknown directive `@{}`", directive.name.name), directive.loc.shrink_to_start().to(directive.name.loc))
+					.with_code(codes::UNKNOWN_DIRECTIVE),
+			);
+			continue;
+		}
+
+		for arg in directive.args.iter() {
+			match Feature::from_name(&arg.name) {
+				Some(feature) if feature.is_stable() => diagnostics.push(
+					Diagnostic::warning(format!("the feature `{}` is stable, it does not need to be enabled", feature), arg.loc)
+						.with_code(codes::STABLE_FEATURE),
+				),
+				Some(feature) => features.enable(feature),
+				None => diagnostics.push(
+					Diagnostic::error(format!("unknown feature `{}`", arg.name), arg.loc).with_code(codes::UNKNOWN_FEATURE),
+				),
+			}
+		}
+	}
+
+	for (feature, span) in gated.iter() {
+		if features.is_enabled(*feature) {
+			continue;
+		}
+
+		let mut diagnostic: Diagnostic = Diagnostic::error(format!("{} is experimental", feature.description()), *span)
+			.with_code(codes::UNSTABLE_FEATURE)
+			.with_note(format!("add `@compiler({});` to the file, or pass `--feature {}`, to enable it", feature, feature));
+		if let Some(url) = feature.issue_url() {
+			diagnostic = diagnostic.with_note(format!("see {} for more information", url));
+		}
+		diagnostics.push(diagnostic);
+	}
+
+	return (features, diagnostics);
+}
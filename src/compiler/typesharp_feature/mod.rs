@@ -0,0 +1,7 @@
+pub mod feature;
+pub mod gate;
+
+pub use self::{
+	feature::{Feature, Features, Status},
+	gate::check_features,
+};
@@ -0,0 +1,116 @@
+use super::token::{ Comment, Delimiter, TemplateChunk, Token, TokenKind, TokenValue };
+use crate::compiler::typesharp_ast::{ KeyWord, Position, Span };
+use crate::util::Json;
+
+/// Serialises tokens for tools, whitespace is left out.
+///
+/// Each token looks like:
+/// ```json
+/// {"kind":"Keyword","value":"let","text":"let","span":{"start":{"line":1,"column":1,"offset":0},"end":{...}}}
+/// ```
+/// `value` is what the lexer read, eg: a string without its quotes, or `null` for tokens without one.
+pub fn tokens_to_json(tokens: &[Token], source: &str) -> Json {
+	return Json::Array(
+		tokens
+			.iter()
+			.filter(|t| t.kind != TokenKind::WhiteSpace)
+			.map(|t| token_to_json(t, source))
+			.collect(),
+	);
+}
+
+pub fn token_to_json(token: &Token, source: &str) -> Json {
+	let mut json: Json = Json::object(vec![
+		("kind", Json::from(kind_name(&token.kind))),
+		("value", value(&token.kind)),
+		("text", Json::from(source.get(token.span.byte_range()).unwrap_or(""))),
+		("span", span_to_json(token.span)),
+	]);
+
+	if let TokenKind::TemplateLiteral(chunks) = &token.kind {
+		let chunks: Vec<Json> = chunks
+			.iter()
+			.map(|chunk| match chunk {
+				TemplateChunk::String(s) => Json::object(vec![("string", Json::from(s.as_str()))]),
+				TemplateChunk::Tokens(tokens) => Json::object(vec![("tokens", tokens_to_json(tokens, source))]),
+			})
+			.collect();
+		json.insert("chunks", Json::Array(chunks));
+	}
+
+	return json;
+}
+
+/// The table of every keyword, and how it is written.
+pub fn keywords_to_json() -> Json {
+	return Json::Array(
+		KeyWord::ALL
+			.iter()
+			.map(|k| Json::object(vec![("name", Json::from(format!("{:?}", k))), ("keyword", Json::from(k.as_str()))]))
+			.collect(),
+	);
+}
+
+pub fn span_to_json(span: Span) -> Json {
+	let position = |p: Position| {
+		Json::object(vec![
+			("line", Json::from(p.line())),
+			("column", Json::from(p.column())),
+			("offset", Json::from(p.offset())),
+		])
+	};
+
+	return Json::object(vec![("start", position(span.start())), ("end", position(span.end()))]);
+}
+
+/// The name of a kind of token, without its value.
+pub fn kind_name(kind: &TokenKind) -> &'static str {
+	return match kind {
+		TokenKind::Accessor => "Accessor",
+		TokenKind::BoolLiteral(_) => "BoolLiteral",
+		TokenKind::EOF => "EOF",
+		TokenKind::Keyword(_) => "Keyword",
+		TokenKind::Identifier(_) => "Identifier",
+		TokenKind::StringLiteral(_) => "StringLiteral",
+		TokenKind::ErrorLiteral => "ErrorLiteral",
+		TokenKind::NumberLiteral(_) => "NumberLiteral",
+		TokenKind::TemplateLiteral(_) => "TemplateLiteral",
+		TokenKind::CommentLiteral(_) => "CommentLiteral",
+		TokenKind::DelimiterLiteral(_) => "DelimiterLiteral",
+		TokenKind::BinaryOpLiteral(_) => "BinaryOpLiteral",
+		TokenKind::UnaryOpLiteral(_) => "UnaryOpLiteral",
+		TokenKind::GenericType(_) => "GenericType",
+		TokenKind::AssignmentLiteral(_) => "AssignmentLiteral",
+		TokenKind::LogicalOpLiteral(_) => "LogicalOpLiteral",
+		TokenKind::ComparisonOpLiteral(_) => "ComparisonOpLiteral",
+		TokenKind::ExpressionTerminator => "ExpressionTerminator",
+		TokenKind::Colon => "Colon",
+		TokenKind::Comma => "Comma",
+		TokenKind::At => "At",
+		TokenKind::Indent => "Indent",
+		TokenKind::WhiteSpace => "WhiteSpace",
+		TokenKind::Unknown(_) => "Unknown",
+	};
+}
+
+fn value(kind: &TokenKind) -> Json {
+	return match kind {
+		TokenKind::Keyword(k) => Json::from(k.as_str()),
+		TokenKind::BoolLiteral(s)
+		| TokenKind::Identifier(s)
+		| TokenKind::StringLiteral(s)
+		| TokenKind::GenericType(s)
+		| TokenKind::Unknown(s) => Json::from(s.as_str()),
+		TokenKind::NumberLiteral(n) => Json::from(n.to_string()),
+		TokenKind::CommentLiteral(c) => Json::from(c.get()),
+		TokenKind::DelimiterLiteral(Delimiter::NoDelim) => Json::Null,
+		TokenKind::DelimiterLiteral(d) => Json::from(d.get()),
+		TokenKind::BinaryOpLiteral(op) => Json::from(op.as_str()),
+		TokenKind::UnaryOpLiteral(op) => Json::from(op.as_str()),
+		TokenKind::AssignmentLiteral(op) => Json::from(op.as_str()),
+		TokenKind::LogicalOpLiteral(op) => Json::from(op.as_str()),
+		TokenKind::ComparisonOpLiteral(op) => Json::from(op.as_str()),
+		// the strings of a template are in its `chunks`
+		_ => Json::Null,
+	};
+}
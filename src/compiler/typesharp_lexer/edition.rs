@@ -0,0 +1,127 @@
+use super::token::{ TemplateChunk, Token, TokenKind };
+use crate::compiler::typesharp_ast::{ Edition, KeyWord, Span };
+use crate::error::{ codes, Diagnostic, DiagnosticSink };
+
+/// Finds the edition chosen by an `@edition(2024);` directive.
+/// Only the directives before the first statement are looked at, as the edition decides how everything after it is parsed.
+pub fn edition_directive(tokens: &[Token]) -> (Option<Edition>, DiagnosticSink) {
+	let mut diagnostics: DiagnosticSink = DiagnosticSink::new();
+	let mut edition: Option<Edition> = None;
+	let mut tokens = tokens.iter().filter(|t| !is_trivia(&t.kind)).peekable();
+
+	while let Some(at) = tokens.next_if(|t| t.kind == TokenKind::At) {
+		let is_edition: bool = matches!(tokens.peek(), Some(Token { kind: TokenKind::Identifier(name), .. }) if name == "edition");
+		let mut args: Vec<&Token> = Vec::new();
+		for token in tokens.by_ref() {
+			if token.kind == TokenKind::ExpressionTerminator {
+				break;
+			}
+			args.push(token);
+		}
+
+		if !is_edition {
+			continue;
+		}
+
+		let values: Vec<&Token> = args.iter().skip(1).filter(|t| !matches!(t.kind, TokenKind::DelimiterLiteral(_))).copied().collect();
+		match values.as_slice() {
+			[value] => match Edition::from_name(&value.kind.as_str()) {
+				Some(chosen) => edition = Some(chosen),
+				None => diagnostics.push(
+					Diagnostic::error(format!("unknown edition `{}`", value.kind.as_str()), value.span)
+						.with_code(codes::UNKNOWN_EDITION)
+						.with_note(format!("the editions are {}", Edition::expected())),
+				),
+			},
+			_ => {
+				let end: Span = args.last().map_or(at.span, |t| t.span);
+				diagnostics.push(
+					Diagnostic::error("expected a single edition, eg: `@edition(2024);`", at.span.to(end))
+						.with_code(codes::UNKNOWN_EDITION),
+				);
+			},
+		}
+	}
+
+	return (edition, diagnostics);
+}
+
+/// Turns keywords that are not reserved in `edition` back in to identifiers, eg: `do` in the 2021 edition.
+pub fn apply_edition(tokens: &mut [Token], edition: Edition) {
+	for token in tokens.iter_mut() {
+		match &mut token.kind {
+			TokenKind::Keyword(keyword) if !edition.is_reserved(keyword) => {
+				token.kind = TokenKind::Identifier(String::from(keyword.as_str()));
+			},
+			TokenKind::TemplateLiteral(chunks) => {
+				for chunk in chunks.iter_mut() {
+					if let TemplateChunk::Tokens(inner) = chunk {
+						apply_edition(inner, edition);
+					}
+				}
+			},
+			_ => (),
+		}
+	}
+}
+
+/// Warns about identifiers that are reserved as keywords in a later edition, suggesting a new name for each.
+/// Applying every suggestion lets the file move to the next edition without changing what it means.
+pub fn edition_keywords(tokens: &[Token], edition: Edition) -> DiagnosticSink {
+	let mut diagnostics: DiagnosticSink = DiagnosticSink::new();
+	let tokens: Vec<&Token> = all_tokens(tokens);
+	let names: Vec<&str> = tokens
+		.iter()
+		.filter_map(|t| match &t.kind {
+			TokenKind::Identifier(name) => Some(name.as_str()),
+			_ => None,
+		})
+		.collect();
+
+	for token in tokens.iter() {
+		let name: &str = match &token.kind {
+			TokenKind::Identifier(name) => name,
+			_ => continue,
+		};
+
+		let keyword: KeyWord = match KeyWord::from_str(name) {
+			Ok(keyword) if !edition.is_reserved(&keyword) => keyword,
+			_ => continue,
+		};
+
+		// the new name can not be one that is already used
+		let mut renamed: String = format!("{}_", name);
+		while names.contains(&renamed.as_str()) {
+			renamed.push('_');
+		}
+
+		diagnostics.push(
+			Diagnostic::warning(format!("`{}` is a keyword in the {} edition", name, keyword.edition()), token.span)
+				.with_code(codes::EDITION_KEYWORD)
+				.with_suggestion(token.span, "rename it", renamed),
+		);
+	}
+
+	return diagnostics;
+}
+
+fn is_trivia(kind: &TokenKind) -> bool {
+	return matches!(kind, TokenKind::WhiteSpace | TokenKind::Indent | TokenKind::CommentLiteral(_));
+}
+
+/// Every token, including the ones inside of templates.
+fn all_tokens(tokens: &[Token]) -> Vec<&Token> {
+	let mut all: Vec<&Token> = Vec::new();
+	for token in tokens.iter() {
+		all.push(token);
+		if let TokenKind::TemplateLiteral(chunks) = &token.kind {
+			for chunk in chunks.iter() {
+				if let TemplateChunk::Tokens(inner) = chunk {
+					all.extend(all_tokens(inner));
+				}
+			}
+		}
+	}
+
+	return all;
+}
@@ -0,0 +1,102 @@
+use super::token::Token;
+use crate::compiler::typesharp_ast::{Cursor, FileId, Position, TextEdit};
+use crate::error::DiagnosticSink;
+use std::ops::Range;
+
+/// The result of re-lexing a file after an edit.
+pub struct Relexed {
+	/// The tokens of the whole file after the edit.
+	pub tokens: Vec<Token>,
+	/// Problems found in the code that was re-lexed.
+	pub diagnostics: DiagnosticSink,
+	/// The indexes of the re-lexed tokens in `tokens`,
+	/// every other token was reused from before the edit.
+	pub relexed: Range<usize>,
+	/// The bytes that were re-lexed, in the file before the edit.
+	pub old_range: Range<usize>,
+	/// The bytes that were re-lexed, in the file after the edit.
+	pub new_range: Range<usize>,
+	/// Where the first reused token after the edit was, and where it is now.
+	/// Anything after `old_range` can be moved with `Span::moved(from, to)`.
+	pub from: Position,
+	pub to: Position,
+}
+
+/// Re-lexes only the tokens touched by an edit.
+/// `tokens` are the tokens before the edit, and `source` is the code after the edit.
+///
+/// Lexing starts at the first token that ends at, or after, the start of the edit,
+/// and stops as soon as a token ends where an unchanged token started before the edit.
+/// The tokens after that are moved rather than lexed again.
+pub fn relex(tokens: &[Token], edit: &TextEdit, source: &str, file: FileId) -> Relexed {
+	let first: usize = tokens
+		.iter()
+		.position(|t| t.span.end().offset() >= edit.range.start)
+		.unwrap_or(tokens.len());
+	let start: Position = match tokens.get(first) {
+		Some(token) => token.span.start(),
+		None => tokens.last().map_or(Position::default(), |t| t.span.end()),
+	};
+
+	// the first unchanged token after the edit, and where it starts now
+	let delta: isize = edit.delta();
+	let edit_end: usize = (edit.range.end as isize + delta) as usize;
+	let mut reused: usize = tokens[first..]
+		.iter()
+		.position(|t| t.span.start().offset() >= edit.range.end)
+		.map_or(tokens.len(), |i| first + i);
+
+	let mut cursor: Cursor = Cursor::resume(source, file, start);
+	let mut new_tokens: Vec<Token> = tokens[..first].to_vec();
+	let mut from: Position = cursor.pos;
+	let mut to: Position = cursor.pos;
+	let mut synced: bool = false;
+
+	while !cursor.is_eof() {
+		let c: char = cursor.peek().unwrap();
+		let token: Token = cursor.consume_token(&c);
+		new_tokens.push(token);
+
+		let offset: usize = cursor.pos.offset();
+		if offset < edit_end {
+			continue;
+		}
+
+		// skip unchanged tokens that the new token has lexed over
+		while reused < tokens.len() && new_offset(&tokens[reused], delta) < offset {
+			reused += 1;
+		}
+
+		if reused < tokens.len() && new_offset(&tokens[reused], delta) == offset {
+			from = tokens[reused].span.start();
+			to = cursor.pos;
+			synced = true;
+			break;
+		}
+	}
+
+	let relexed: Range<usize> = first..new_tokens.len();
+	let old_end: usize = match synced {
+		true => from.offset(),
+		false => tokens.last().map_or(0, |t| t.span.end().offset()).max(edit.range.end),
+	};
+
+	if synced {
+		new_tokens.extend(tokens[reused..].iter().cloned().map(|t| t.moved(from, to)));
+	}
+
+	return Relexed {
+		tokens: new_tokens,
+		diagnostics: cursor.diagnostics,
+		relexed: relexed,
+		old_range: start.offset()..old_end,
+		new_range: start.offset()..cursor.pos.offset(),
+		from: from,
+		to: to,
+	};
+}
+
+/// Where a token after the edit starts, once the edit has been made.
+fn new_offset(token: &Token, delta: isize) -> usize {
+	return (token.span.start().offset() as isize + delta) as usize;
+}
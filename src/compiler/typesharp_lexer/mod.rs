@@ -1,3 +1,12 @@
+pub mod dump;
+pub mod edition;
+pub mod incremental;
 pub mod token;
+pub mod tree;
+pub mod unicode;
 
-pub use self::token::{tokenize, Token, TokenKind};
+pub use self::edition::{apply_edition, edition_directive, edition_keywords};
+pub use self::incremental::{relex, Relexed};
+pub use self::token::{tokenize, tokenize_file, tokenize_with_diagnostics, Token, TokenKind};
+pub use self::tree::{flatten, token_trees, Delimited, TokenTree};
+pub use self::unicode::confusable_identifiers;
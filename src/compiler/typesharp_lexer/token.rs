@@ -1,7 +1,10 @@
 use crate::{
-	compiler::typesharp_ast::{Cursor, KeyWord, Position, Span},
+	compiler::typesharp_ast::{Cursor, KeyWord, Position, SourceFile, Span},
 	compiler::typesharp_parser::op::*,
+	error::{ codes, Diagnostic, DiagnosticSink, ErrCompiler },
 };
+use super::unicode::{ is_ident_continue, is_ident_start, normalize };
+use std::convert::TryFrom;
 
 pub type TokenType = (String, Box<str>);
 
@@ -17,13 +20,30 @@ impl Token {
 		return Token {
 			kind: kind,
 			span: span,
-			position: pos.unwrap_or(Position::new(0, 0)),
+			position: pos.unwrap_or(span.start()),
 		};
 	}
 
 	pub fn build(kind: TokenKind, pos: Position) -> Self {
 		return Token::new(kind, Span::from(pos), Some(pos));
 	}
+
+	/// Moves a token that comes after `from` so that it comes after `to` instead,
+	/// including any tokens inside of a template string.
+	pub fn moved(mut self, from: Position, to: Position) -> Self {
+		self.span = self.span.moved(from, to);
+		self.position = self.position.moved(from, to);
+
+		if let TokenKind::TemplateLiteral(chunks) = &mut self.kind {
+			for chunk in chunks.iter_mut() {
+				if let TemplateChunk::Tokens(tokens) = chunk {
+					*tokens = tokens.drain(..).map(|t| t.moved(from, to)).collect();
+				}
+			}
+		}
+
+		return self;
+	}
 }
 
 /// Public trait
@@ -63,11 +83,158 @@ pub enum Numeric {
 	Hexadecimal(usize),
 }
 
+/// Type suffixes a numeric may end with, eg: `10u8` or `1.5f32`
+const NUMERIC_SUFFIXES: [&str; 12] = [
+	"u128", "i128", "u16", "u32", "u64", "i16", "i32", "i64", "f32", "f64", "u8", "i8",
+];
+
+/// Reasons a numeric literal could not be classified.
+#[derive(Clone, PartialEq, Debug)]
+pub enum NumericError {
+	/// The value does not fit in the given type.
+	Overflow(&'static str),
+
+	/// A digit that isn't valid for the base of the literal, eg: `0b12`
+	InvalidDigit(char, u32),
+
+	/// An unknown type suffix, eg: `10u7`
+	InvalidSuffix(String),
+
+	/// A prefix without any digits, eg: `0x`
+	NoDigits,
+
+	/// An exponent without any digits, eg: `1e`
+	MissingExponent,
+
+	/// Digits that do not make up a single number, eg: `1.2.3`
+	Invalid(String),
+}
+
+impl std::fmt::Display for NumericError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			NumericError::Overflow(typ) => write!(f, "number literal does not fit in `{}`", typ),
+			NumericError::InvalidDigit(c, radix) => write!(f, "invalid digit `{}` in base {} literal", c, radix),
+			NumericError::InvalidSuffix(s) => write!(f, "invalid suffix `{}` for number literal", s),
+			NumericError::NoDigits => write!(f, "missing digits after the number prefix"),
+			NumericError::MissingExponent => write!(f, "expected at least one digit in the exponent"),
+			NumericError::Invalid(s) => write!(f, "invalid number literal `{}`", s),
+		}
+	}
+}
+
 impl Numeric {
-	fn new(s: String) -> Self {
-		match s {
-			_ => Numeric::Hexadecimal(0),
+	/// Classifies a numeric literal, eg: `0xff`, `1_000`, `1.5e3` or `10u8`
+	///
+	/// Integers without a suffix are the smallest of `i32`, `i64` and `i128` that fit,
+	/// and floats without a suffix are doubles.
+	pub fn new(s: &str) -> Result<Self, NumericError> {
+		let (radix, body): (u32, &str) = match s.get(0..2) {
+			Some("0x") | Some("0X") => (16, &s[2..]),
+			Some("0b") | Some("0B") => (2, &s[2..]),
+			Some("0o") | Some("0O") => (8, &s[2..]),
+			_ => (10, s),
+		};
+
+		// `f` is a hex digit, so hex literals can only have integer suffixes.
+		let suffix: Option<&'static str> = NUMERIC_SUFFIXES
+			.iter()
+			.find(|suffix| body.ends_with(*suffix) && (radix != 16 || !suffix.starts_with('f')))
+			.copied();
+		let digits: String = body[..body.len() - suffix.map_or(0, |s| s.len())]
+			.chars()
+			.filter(|c| *c != '_')
+			.collect();
+
+		if digits.is_empty() {
+			return Err(NumericError::NoDigits);
+		}
+
+		if radix == 10 {
+			let exponent: usize = digits.find(['e', 'E']).unwrap_or(digits.len());
+			if digits.matches('.').count() > 1 || digits[exponent..].contains('.') {
+				return Err(NumericError::Invalid(s.to_string()));
+			}
+			if exponent < digits.len() {
+				let power: &str = &digits[exponent + 1..];
+				// letters right after the `e` make it a suffix rather than an exponent, eg: `10em`
+				if power.starts_with(|c: char| c.is_alphabetic()) {
+					let at: usize = body.find(['e', 'E']).unwrap_or(0);
+					return Err(NumericError::InvalidSuffix(body[at..].to_string()));
+				}
+				if !power.trim_start_matches(['+', '-']).starts_with(|c: char| c.is_ascii_digit()) {
+					return Err(NumericError::MissingExponent);
+				}
+			}
+			if let Some(c) = digits.chars().find(|c| c.is_alphabetic() && *c != 'e' && *c != 'E') {
+				let at: usize = body.find(c).unwrap_or(0);
+				return Err(NumericError::InvalidSuffix(body[at..].to_string()));
+			}
+		} else if let Some(c) = digits.chars().find(|c| !c.is_digit(radix)) {
+			return Err(NumericError::InvalidDigit(c, radix));
+		}
+
+		let is_float: bool = radix == 10 && digits.contains(['.', 'e', 'E']);
+		if is_float || matches!(suffix, Some("f32") | Some("f64")) {
+			return Numeric::new_float(&digits, suffix);
+		}
+
+		let value: u128 = match u128::from_str_radix(&digits, radix) {
+			Ok(value) => value,
+			Err(_) => return Err(NumericError::Overflow(suffix.unwrap_or("u128"))),
+		};
+
+		if radix != 10 && suffix.is_none() {
+			let value: usize = usize::try_from(value).map_err(|_| NumericError::Overflow("usize"))?;
+			return Ok(match radix {
+				2 => Numeric::Binary(value),
+				8 => Numeric::Octal(value),
+				_ => Numeric::Hexadecimal(value),
+			});
+		}
+
+		let typ: &'static str = match suffix {
+			Some(typ) => typ,
+			None if value <= i32::MAX as u128 => "i32",
+			None if value <= i64::MAX as u128 => "i64",
+			None => "i128",
+		};
+		let max: u128 = match typ {
+			"u8" => u8::MAX as u128,
+			"u16" => u16::MAX as u128,
+			"u32" => u32::MAX as u128,
+			"u64" => u64::MAX as u128,
+			"i8" => i8::MAX as u128,
+			"i16" => i16::MAX as u128,
+			"i32" => i32::MAX as u128,
+			"i64" => i64::MAX as u128,
+			// u128 values larger than this can not be stored yet.
+			_ => i128::MAX as u128,
+		};
+
+		if value > max {
+			return Err(NumericError::Overflow(typ));
 		}
+
+		return Ok(match typ {
+			"u8" | "u16" | "i8" | "i16" | "i32" => Numeric::IntegerLiteral(value as i32),
+			"u32" | "i64" => Numeric::IntegerLiteralBig(value as i64),
+			_ => Numeric::ItegerLiteralSigned128(value as i128),
+		});
+	}
+
+	fn new_float(digits: &str, suffix: Option<&'static str>) -> Result<Self, NumericError> {
+		return match suffix {
+			Some("f32") => match digits.parse::<f32>() {
+				Ok(n) if n.is_finite() => Ok(Numeric::FloatLiteral(n)),
+				_ => Err(NumericError::Overflow("f32")),
+			},
+			None | Some("f64") => match digits.parse::<f64>() {
+				Ok(n) if n.is_finite() => Ok(Numeric::DoubleLiteral(n)),
+				_ => Err(NumericError::Overflow("f64")),
+			},
+			Some(suffix) => Err(NumericError::InvalidSuffix(suffix.to_string())),
+		};
 	}
 }
 
@@ -81,14 +248,23 @@ impl TokenValue<usize> for Numeric {
 			Numeric::IntegerLiteralBig(n) => n as usize,
 			Numeric::ItegerLiteralSigned128(n) => n as usize,
 			Numeric::Octal(n) => n,
-			_ => panic!("Unknown Numeric Type provided."),
+			Numeric::DoubleLiteral(n) => n as usize,
 		}
 	}
 }
 
 impl std::fmt::Display for Numeric {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		write!(f, "0") // lazy impl, todo: properly impl
+		match self {
+			Numeric::FloatLiteral(n) => write!(f, "{:?}", n),
+			Numeric::DoubleLiteral(n) => write!(f, "{:?}", n),
+			Numeric::IntegerLiteral(n) => write!(f, "{}", n),
+			Numeric::IntegerLiteralBig(n) => write!(f, "{}", n),
+			Numeric::ItegerLiteralSigned128(n) => write!(f, "{}", n),
+			Numeric::Binary(n) => write!(f, "0b{:b}", n),
+			Numeric::Octal(n) => write!(f, "0o{:o}", n),
+			Numeric::Hexadecimal(n) => write!(f, "0x{:x}", n),
+		}
 	}
 }
 
@@ -126,6 +302,32 @@ impl std::fmt::Display for Comment {
 	}
 }
 
+/// A piece of a template string.
+/// Templates always start and end with a `String` chunk,
+/// with a `Tokens` chunk between each of them.
+///
+/// EG: `a ${b} c` is: String("a "), Tokens([b]), String(" c")
+#[derive(Clone, PartialEq, Debug)]
+pub enum TemplateChunk {
+	/// Text of the template, with escapes already decoded.
+	String(String),
+
+	/// The tokens inside of a `${}`
+	Tokens(Vec<Token>),
+}
+
+impl std::fmt::Display for TemplateChunk {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			TemplateChunk::String(s) => write!(f, "{}", s),
+			TemplateChunk::Tokens(tokens) => {
+				let inner: String = tokens.iter().map(|t| t.kind.as_str()).collect();
+				write!(f, "${{{}}}", inner)
+			},
+		}
+	}
+}
+
 /// Delimiters are defined below, each delimiter is a type of scope delarator
 #[derive(Clone, PartialEq, Debug)]
 pub enum Delimiter {
@@ -147,12 +349,44 @@ impl TokenValue<String> for Delimiter {
 		match self {
 			Delimiter::Paren(t) => t.to_string(),
 			Delimiter::Bracket(t) => t.to_string(),
+			Delimiter::Brace(t) => t.to_string(),
 			Delimiter::NoDelim => String::from("None"),
-			_ => panic!("Unknown Delimiter"),
 		}
 	}
 }
 
+impl Delimiter {
+	/// The delimiter for a char, if it is one, eg: `Paren("(")` for `(`
+	pub fn from_char(c: char) -> Option<Delimiter> {
+		return Some(match c {
+			'(' | ')' => Delimiter::Paren(c.to_string()),
+			'[' | ']' => Delimiter::Bracket(c.to_string()),
+			'{' | '}' => Delimiter::Brace(c.to_string()),
+			_ => return None,
+		});
+	}
+
+	/// Whether this opens a scope, eg: `(` rather than `)`
+	pub fn is_open(&self) -> bool {
+		return matches!(self.get().as_str(), "(" | "[" | "{");
+	}
+
+	/// Whether this is the closing delimiter for `open`, eg: `)` closes `(`
+	pub fn closes(&self, open: &Delimiter) -> bool {
+		return open.is_open() && !self.is_open() && open.closing() == *self;
+	}
+
+	/// The closing delimiter of the same kind, eg: `]` for `[`
+	pub fn closing(&self) -> Delimiter {
+		return match self {
+			Delimiter::Paren(_) => Delimiter::Paren(String::from(")")),
+			Delimiter::Bracket(_) => Delimiter::Bracket(String::from("]")),
+			Delimiter::Brace(_) => Delimiter::Brace(String::from("}")),
+			Delimiter::NoDelim => Delimiter::NoDelim,
+		};
+	}
+}
+
 impl std::fmt::Display for Delimiter {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		return write!(f, "Delimiter {{ {} }}", self.get());
@@ -189,7 +423,7 @@ pub enum TokenKind {
 	//RegularExpressionLiteral,
 
 	// A template string, wrapped in ``
-	TemplateLiteral(String),
+	TemplateLiteral(Vec<TemplateChunk>),
 
 	// Comment literal
 	CommentLiteral(Comment),
@@ -206,8 +440,21 @@ pub enum TokenKind {
 
 	AssignmentLiteral(AssignmentOp),
 
+	LogicalOpLiteral(LogicalOp),
+
+	ComparisonOpLiteral(ComparisonOp),
+
 	ExpressionTerminator,
 
+	// A colon, used for type annotations. EG: ":"
+	Colon,
+
+	// A comma, used to separate items. EG: ","
+	Comma,
+
+	// An at sign, starts a directive. EG: "@" in "@compiler(numericTypes);"
+	At,
+
 	Indent,
 
 	WhiteSpace,
@@ -218,7 +465,7 @@ pub enum TokenKind {
 
 impl TokenKind {
 	/// Gets the token as a string value
-	fn as_str(&self) -> String {
+	pub fn as_str(&self) -> String {
 		match self {
 			TokenKind::Accessor => String::from("."),
 			TokenKind::BoolLiteral(v) => String::from(v),
@@ -227,15 +474,20 @@ impl TokenKind {
 			TokenKind::Identifier(v) => v.to_string(),
 			TokenKind::StringLiteral(v) => v.to_string(),
 			TokenKind::ErrorLiteral => String::from("Error"),
-			TokenKind::NumberLiteral(n) => format!("{}", n.get()),
-			TokenKind::TemplateLiteral(v) => v.to_string(),
+			TokenKind::NumberLiteral(n) => format!("{}", n),
+			TokenKind::TemplateLiteral(v) => v.iter().map(|c| c.to_string()).collect(),
 			TokenKind::CommentLiteral(c) => c.get(),
 			TokenKind::DelimiterLiteral(v) => v.get(),
-			TokenKind::BinaryOpLiteral(_v) => String::from("Op unknown"),
-			TokenKind::UnaryOpLiteral(_v) => String::from("Op unknown"),
+			TokenKind::BinaryOpLiteral(v) => String::from(v.as_str()),
+			TokenKind::UnaryOpLiteral(v) => String::from(v.as_str()),
 			TokenKind::GenericType(_v) => String::from("Op unknown"),
-			TokenKind::AssignmentLiteral(_v) => String::from("Op unknown"),
+			TokenKind::AssignmentLiteral(v) => String::from(v.as_str()),
+			TokenKind::LogicalOpLiteral(v) => String::from(v.as_str()),
+			TokenKind::ComparisonOpLiteral(v) => String::from(v.as_str()),
 			TokenKind::ExpressionTerminator => String::from("Expression Terminated"),
+			TokenKind::Colon => String::from(":"),
+			TokenKind::Comma => String::from(","),
+			TokenKind::At => String::from("@"),
 			TokenKind::Indent => String::from(""),
 			TokenKind::WhiteSpace => String::from(" "),
 			TokenKind::Unknown(v) => v.to_string()
@@ -258,10 +510,10 @@ impl std::fmt::Display for TokenKind {
 macro_rules! token {
 	// used for building without chaos
 	($kind: expr, $span: expr) => {
-		Token::new($kind, $span, None);
+		Token::new($kind, $span, None)
 	};
 	() => {
-		Token::build(TokenKind::Unknown(String::from("")), Position::new(0, 0));
+		Token::build(TokenKind::Unknown(String::from("")), Position::default())
 	};
 }
 
@@ -270,20 +522,69 @@ macro_rules! token {
 //
 // }
 
+/// The length of the longest operator, `>>>=`
+const MAX_OPERATOR_LENGTH: usize = 4;
+
+/// Gets the token for an operator, if the given string is one.
+fn operator_kind(op: &str) -> Option<TokenKind> {
+	return Some(match op {
+		"+" => TokenKind::BinaryOpLiteral(BinOp::Plus),
+		"-" => TokenKind::BinaryOpLiteral(BinOp::Minus),
+		"*" => TokenKind::BinaryOpLiteral(BinOp::Star),
+		"/" => TokenKind::BinaryOpLiteral(BinOp::Slash),
+		"%" => TokenKind::BinaryOpLiteral(BinOp::Percent),
+		"^" => TokenKind::BinaryOpLiteral(BinOp::Caret),
+		"&" => TokenKind::BinaryOpLiteral(BinOp::And),
+		"|" => TokenKind::BinaryOpLiteral(BinOp::Or),
+		"<<" => TokenKind::BinaryOpLiteral(BinOp::Sh1),
+		">>" => TokenKind::BinaryOpLiteral(BinOp::Shr),
+		">>>" => TokenKind::BinaryOpLiteral(BinOp::UShr),
+
+		// the parser decides whether these are prefix or postfix
+		"++" => TokenKind::UnaryOpLiteral(UnaryOp::Inc),
+		"--" => TokenKind::UnaryOpLiteral(UnaryOp::Dec),
+		"!" => TokenKind::UnaryOpLiteral(UnaryOp::Not),
+
+		"&&" => TokenKind::LogicalOpLiteral(LogicalOp::And),
+		"||" => TokenKind::LogicalOpLiteral(LogicalOp::Or),
+		"??" => TokenKind::LogicalOpLiteral(LogicalOp::Coalasce),
+
+		"==" => TokenKind::ComparisonOpLiteral(ComparisonOp::Eq),
+		"!=" => TokenKind::ComparisonOpLiteral(ComparisonOp::NotEq),
+		">" => TokenKind::ComparisonOpLiteral(ComparisonOp::GreaterThan),
+		">=" => TokenKind::ComparisonOpLiteral(ComparisonOp::GreaterThanOrEqual),
+		"<" => TokenKind::ComparisonOpLiteral(ComparisonOp::LessThan),
+		"<=" => TokenKind::ComparisonOpLiteral(ComparisonOp::LessThanOrEqual),
+
+		"=" => TokenKind::AssignmentLiteral(AssignmentOp::Assign),
+		"+=" => TokenKind::AssignmentLiteral(AssignmentOp::Add),
+		"-=" => TokenKind::AssignmentLiteral(AssignmentOp::Sub),
+		"*=" => TokenKind::AssignmentLiteral(AssignmentOp::Mul),
+		"/=" => TokenKind::AssignmentLiteral(AssignmentOp::Div),
+		"%=" => TokenKind::AssignmentLiteral(AssignmentOp::Rem),
+		"&=" => TokenKind::AssignmentLiteral(AssignmentOp::And),
+		"|=" => TokenKind::AssignmentLiteral(AssignmentOp::Or),
+		"^=" => TokenKind::AssignmentLiteral(AssignmentOp::Xor),
+		"<<=" => TokenKind::AssignmentLiteral(AssignmentOp::Sh1),
+		">>=" => TokenKind::AssignmentLiteral(AssignmentOp::Shr),
+		">>>=" => TokenKind::AssignmentLiteral(AssignmentOp::Ushr),
+		"&&=" => TokenKind::AssignmentLiteral(AssignmentOp::BoolAnd),
+		"||=" => TokenKind::AssignmentLiteral(AssignmentOp::BoolOr),
+		"??=" => TokenKind::AssignmentLiteral(AssignmentOp::Coalesce),
+		_ => return None,
+	});
+}
+
 impl Cursor<'_> {
 	/// Definitely tries to consume a token.
-	/// If it can't, we panic.
+	/// If it can't, an `Unknown` token is returned and reported.
 	pub fn consume_token(&mut self, init: &char) -> Token {
 		return match init {
 			// comments
 			'/' => match self.first() {
 				'/' => self.consume_comment(true),
 				'*' => self.consume_comment(false),
-				_ => Token::new(
-					TokenKind::BinaryOpLiteral(BinOp::Slash),
-					Span::from(self.pos),
-					None,
-				), // probably an op
+				_ => self.consume_operator(*init), // probably an op
 			},
 
 			// numbers (parser checks for numeric types later)
@@ -291,165 +592,294 @@ impl Cursor<'_> {
 
 			// whitespace (eg: space)
 			' ' | '\n' | '\r' | '\t' => {
-				Token::new(TokenKind::WhiteSpace, Span::from(self.pos), None)
+				Token::new(TokenKind::WhiteSpace, self.span(self.last_pos()), None)
 			},
 			'"' | '\'' => self.consume_any_string(Some(init)),
-			'A'..='z' => self.consume_keyword_or_identifier(Some(init)),
-			'.' => token!(TokenKind::Accessor, Span::from(self.pos)),
-			';' => token!(TokenKind::ExpressionTerminator, Span::from(self.pos)),
-			_ => token!(TokenKind::Unknown(init.to_string()), Span::from(self.pos)),
+			'`' => self.consume_template(),
+
+			// operators
+			'+' | '-' | '*' | '%' | '^' | '&' | '|' | '<' | '>' | '=' | '!' | '?' => self.consume_operator(*init),
+
+			// delimiters
+			'(' | ')' | '[' | ']' | '{' | '}' => token!(
+				TokenKind::DelimiterLiteral(Delimiter::from_char(*init).unwrap()),
+				self.span(self.last_pos())
+			),
+
+			c if is_ident_start(*c) => self.consume_keyword_or_identifier(Some(init)),
+			'.' => token!(TokenKind::Accessor, self.span(self.last_pos())),
+			';' => token!(TokenKind::ExpressionTerminator, self.span(self.last_pos())),
+			':' => token!(TokenKind::Colon, self.span(self.last_pos())),
+			',' => token!(TokenKind::Comma, self.span(self.last_pos())),
+			'@' => token!(TokenKind::At, self.span(self.last_pos())),
+			_ => self.unknown(*init, self.last_pos()),
 		};
 	}
 
-	/// Indefinitely consumes any word encapsulated in a string char
-	pub fn consume_any_string(&mut self, init: Option<&char>) -> Token {
-		let init_pos: Position = self.pos;
+	/// Reports a char that does not start any token.
+	fn unknown(&mut self, c: char, pos: Position) -> Token {
+		let span: Span = self.span(pos);
+		self.diagnostics.push(
+			Diagnostic::error(format!("unknown character `{}`", c.escape_debug()), span)
+				.with_code(codes::UNKNOWN_CHARACTER),
+		);
 
-		if init == None {
-			// there was no initial char
-			// we need to panic because it's impossible to know when we can terminate the string.
-			// to-do: Implement errors.
-			panic!("Unknown String");
-		}
+		return token!(TokenKind::Unknown(c.to_string()), span);
+	}
+
+	/// Consumes the longest operator that starts with `init`.
+	/// EG: `>>>=` is consumed as a single token rather than `>>`, `>=`.
+	pub fn consume_operator(&mut self, init: char) -> Token {
+		let init_pos: Position = self.last_pos();
+
+		for length in (1..=MAX_OPERATOR_LENGTH).rev() {
+			let mut op: String = String::from(init);
+			op.extend(self.chars().take(length - 1));
 
-		let mut previous = self.previous;
-		let string = self.consume_segment(|c| -> bool {
-			if c == *init.unwrap() {
-				if previous == '\\' {
-					return true;
+			if let Some(kind) = operator_kind(&op) {
+				for _ in 1..op.chars().count() {
+					self.peek();
 				}
-				return false;
-			} else {
-				previous = c;
-				return true;
+
+				return token!(kind, self.span(init_pos));
 			}
-		});
+		}
 
-		// consume the next char because it is a string terminator and has not been consumed, (possibly fix cursor?)
-		self.peek();
+		return self.unknown(init, init_pos);
+	}
+
+	/// Indefinitely consumes any word encapsulated in a string char
+	pub fn consume_any_string(&mut self, init: Option<&char>) -> Token {
+		let init_pos: Position = self.last_pos();
+
+		let quote: char = match init {
+			Some(quote) => *quote,
+			None => {
+				// there was no initial char
+				// it's impossible to know when we can terminate the string.
+				self.diagnostics.push(Diagnostic::new(
+					ErrCompiler::Unreachable,
+					"a string was consumed without an opening quote",
+					self.span_here(),
+				));
+				return token!(TokenKind::ErrorLiteral, self.span_here());
+			},
+		};
+		let open: Span = self.span(init_pos);
+		let mut string: String = String::new();
+
+		loop {
+			match self.peek() {
+				None => {
+					self.diagnostics.push(
+						Diagnostic::error("unterminated string", open)
+							.with_code(codes::UNTERMINATED_STRING)
+							.with_insertion(self.span_here(), "close the string", quote.to_string()),
+					);
+					break;
+				},
+				Some(c) if c == quote => break,
+				Some('\\') => {
+					if let Some(c) = self.consume_escape() {
+						string.push(c);
+					}
+				},
+				Some(c) => string.push(c),
+			}
+		}
 
 		return token!(
 			TokenKind::StringLiteral(string),
-			Span::new(init_pos, self.pos)
+			self.span(init_pos)
 		);
 	}
 
+	/// Consumes a template string, wrapped in "`".
+	/// Any `${}` in the template is lexed in to its own token stream.
+	pub fn consume_template(&mut self) -> Token {
+		let init_pos: Position = self.last_pos();
+		let open: Span = self.span(init_pos);
+		let mut chunks: Vec<TemplateChunk> = Vec::new();
+		let mut string: String = String::new();
+
+		loop {
+			match self.peek() {
+				None => {
+					self.diagnostics.push(
+						Diagnostic::error("unterminated template string", open)
+							.with_code(codes::UNTERMINATED_STRING)
+							.with_insertion(self.span_here(), "close the template string", "`"),
+					);
+					break;
+				},
+				Some('`') => break,
+				Some('\\') => {
+					if let Some(c) = self.consume_escape() {
+						string.push(c);
+					}
+				},
+				Some('$') if self.first() == '{' => {
+					let dollar: Position = self.last_pos();
+					self.peek();
+					let interpolation: Span = self.span(dollar);
+					chunks.push(TemplateChunk::String(std::mem::take(&mut string)));
+					chunks.push(TemplateChunk::Tokens(self.consume_interpolation(interpolation)));
+				},
+				Some(c) => string.push(c),
+			}
+		}
+
+		chunks.push(TemplateChunk::String(string));
+		return token!(
+			TokenKind::TemplateLiteral(chunks),
+			self.span(init_pos)
+		);
+	}
+
+	/// Consumes tokens inside of a `${}` until the matching `}`.
+	/// The opening `${` should already be consumed, `open` is its span.
+	fn consume_interpolation(&mut self, open: Span) -> Vec<Token> {
+		let mut tokens: Vec<Token> = Vec::new();
+		let mut depth: usize = 0;
+
+		loop {
+			let c: char = match self.peek() {
+				Some(c) => c,
+				None => {
+					self.diagnostics.push(
+						Diagnostic::error("unterminated `${` in template string", open)
+							.with_code(codes::UNTERMINATED_STRING)
+							.with_note("every `${` in a template string needs a matching `}`"),
+					);
+					return tokens;
+				},
+			};
+
+			match c {
+				'{' => depth += 1,
+				'}' if depth == 0 => return tokens,
+				'}' => depth -= 1,
+				_ => {},
+			}
+
+			tokens.push(self.consume_token(&c));
+		}
+	}
+
+	/// Consumes an escape sequence, the `\` should already be consumed.
+	/// Unknown escapes are reported, and the escaped char is kept as is.
+	pub fn consume_escape(&mut self) -> Option<char> {
+		let start: Position = self.last_pos();
+		let c: char = self.peek()?;
+
+		return match c {
+			'n' => Some('\n'),
+			't' => Some('\t'),
+			'r' => Some('\r'),
+			'0' => Some('\0'),
+			'\\' | '"' | '\'' | '`' | '$' => Some(c),
+			'u' => self.consume_unicode_escape(start),
+			_ => {
+				self.diagnostics.push(
+					Diagnostic::error(format!("unknown escape sequence `\\{}`", c), self.span(start))
+						.with_code(codes::UNKNOWN_ESCAPE)
+						.with_note("valid escapes are `\\n`, `\\t`, `\\r`, `\\0`, `\\\\`, `\\\"`, `\\'`, `\\``, `\\$` and `\\u{...}`"),
+				);
+				Some(c)
+			},
+		};
+	}
+
+	/// Consumes the `{...}` of a `\u{...}` escape.
+	fn consume_unicode_escape(&mut self, start: Position) -> Option<char> {
+		if self.first() != '{' {
+			self.diagnostics.push(
+				Diagnostic::error("expected `{` after `\\u`, eg: `\\u{1F600}`", self.span(start))
+					.with_code(codes::INVALID_UNICODE_ESCAPE),
+			);
+			return None;
+		}
+
+		self.peek();
+		let hex: String = self.consume_segment(|c| c.is_ascii_hexdigit());
+		let closed: bool = self.first() == '}';
+		if closed {
+			self.peek();
+		}
+
+		let c: Option<char> = u32::from_str_radix(&hex, 16).ok().and_then(std::char::from_u32);
+		if !closed || c.is_none() {
+			self.diagnostics.push(
+				Diagnostic::error(format!("invalid unicode escape `\\u{{{}}}`", hex), self.span(start))
+					.with_code(codes::INVALID_UNICODE_ESCAPE),
+			);
+		}
+
+		return c;
+	}
+
 	/// Indefinitely consume until we match whitespace.
 	/// This will resolve keywords into tokens if found,
 	/// if not found, will return a token in the form of a
 	/// **VALID** identifier.
 	pub fn consume_keyword_or_identifier(&mut self, init: Option<&char>) -> Token {
-		let init_pos: Position = self.pos;
-		// consume and preserve until next space
+		let init_pos: Position = match init {
+			Some(_) => self.last_pos(),
+			None => self.pos,
+		};
 		let mut identifier: String = String::new();
-		if init == None {
-			identifier = self.consume_segment(|c| !c.is_whitespace() && c.is_alphanumeric());
-		} else {
-			identifier.push(*init.unwrap());
-			identifier.push_str(
-				self.consume_segment(|c| !c.is_whitespace() && c.is_alphanumeric())
-					.chars()
-					.as_str(),
-			);
+		if let Some(init) = init {
+			identifier.push(*init);
 		}
-		let span: Span = Span::new(init_pos, self.pos);
-
-		match &identifier[..] {
-			// refer to keywords for this
-			"as" => token!(TokenKind::Keyword(KeyWord::As), span),
-			"asm" => token!(TokenKind::Keyword(KeyWord::Asm), span),
-			"async" => token!(TokenKind::Keyword(KeyWord::Async), span),
-			"await" => token!(TokenKind::Keyword(KeyWord::Await), span),
-			"break" => token!(TokenKind::Keyword(KeyWord::Break), span),
-			"case" => token!(TokenKind::Keyword(KeyWord::Case), span),
-			"catch" => token!(TokenKind::Keyword(KeyWord::Catch), span),
-			"class" => token!(TokenKind::Keyword(KeyWord::Class), span),
-			"continue" => token!(TokenKind::Keyword(KeyWord::Continue), span),
-			"const" => token!(TokenKind::Keyword(KeyWord::Const), span),
-			"default" => token!(TokenKind::Keyword(KeyWord::Default), span),
-			// [EXPERIMENT] See: https://github.com/TypeSharp/Typesharp/issues/1
-			"delete" => token!(TokenKind::Keyword(KeyWord::Delete), span),
-			"else" => token!(TokenKind::Keyword(KeyWord::Else), span),
-			"enum" => token!(TokenKind::Keyword(KeyWord::Enum), span),
-			"export" => token!(TokenKind::Keyword(KeyWord::Export), span),
-			"extern" => token!(TokenKind::Keyword(KeyWord::Extern), span),
-			// May change in the future
-			"external" => token!(TokenKind::Keyword(KeyWord::Extern), span),
-			"extends" => token!(TokenKind::Keyword(KeyWord::Extends), span),
-			"for" => token!(TokenKind::Keyword(KeyWord::For), span),
-			"function" => token!(TokenKind::Keyword(KeyWord::Function), span),
-			"fn" => token!(TokenKind::Keyword(KeyWord::Fn), span),
-			"if" => token!(TokenKind::Keyword(KeyWord::If), span),
-			"in" => token!(TokenKind::Keyword(KeyWord::In), span),
-			"instanceof" => token!(TokenKind::Keyword(KeyWord::InstanceOf), span),
-			"import" => token!(TokenKind::Keyword(KeyWord::Import), span),
-			"let" => token!(TokenKind::Keyword(KeyWord::Let), span),
-			"new" => token!(TokenKind::Keyword(KeyWord::New), span),
-			"of" => token!(TokenKind::Keyword(KeyWord::Of), span),
-			// [EXPERIMENT]
-			// Todo: Add checks and feature gates
-			"package" => token!(TokenKind::Keyword(KeyWord::Package), span),
-			"return" => token!(TokenKind::Keyword(KeyWord::Return), span),
-			"self" => token!(TokenKind::Keyword(KeyWord::SelfKeyword), span),
-			"static" => token!(TokenKind::Keyword(KeyWord::Static), span),
-			"super" => token!(TokenKind::Keyword(KeyWord::Super), span),
-			"switch" => token!(TokenKind::Keyword(KeyWord::Switch), span),
-			"trait" => token!(TokenKind::Keyword(KeyWord::Trait), span),
-			"this" => token!(TokenKind::Keyword(KeyWord::This), span),
-			"throw" => token!(TokenKind::Keyword(KeyWord::Throw), span),
-			"type" => token!(TokenKind::Keyword(KeyWord::Type), span),
-			"try" => token!(TokenKind::Keyword(KeyWord::Try), span),
-			"where" => token!(TokenKind::Keyword(KeyWord::Where), span),
-			"while" => token!(TokenKind::Keyword(KeyWord::While), span),
-
-			// Abstractions are here as i dont plan on implementing them until all of the above is done.
-			// I also believe this will change a lot so that is antoher reason I am leaving this here.
-
-			// [EXPERIMENTAL] https://github.com/TypeSharp/Typesharp/issues/10
-			"final" => token!(TokenKind::Keyword(KeyWord::Final), span),
-			"finally" => token!(TokenKind::Keyword(KeyWord::Finally), span),
-			"override" => token!(TokenKind::Keyword(KeyWord::Override), span),
-			"typeof" => token!(TokenKind::Keyword(KeyWord::Typeof), span),
-			"yield" => token!(TokenKind::Keyword(KeyWord::Yield), span),
-			"public" => token!(TokenKind::Keyword(KeyWord::Public), span),
-			// Pub may not be used, it is reserved under keyword incase it is
-			// the pub keyword will be treated as "public" for now.
-			"pub" => token!(TokenKind::Keyword(KeyWord::Public), span),
-			"private" => token!(TokenKind::Keyword(KeyWord::Private), span),
-			"protected" => token!(TokenKind::Keyword(KeyWord::Protected), span),
-
-			// Types that aren't compiled are here as well for the same reason that abstractions
-			// are below everything else.
-
-			// Unions are complex types, and are currently reserved.
-			"union" => token!(TokenKind::Keyword(KeyWord::Union), span),
-			"implements" => token!(TokenKind::Keyword(KeyWord::Implements), span),
-			"interface" => token!(TokenKind::Keyword(KeyWord::Interface), span),
-
+		identifier.push_str(&self.consume_segment(is_ident_continue));
+		let identifier: String = normalize(identifier);
+		let span: Span = self.span(init_pos);
+
+		// refer to keywords for this
+		return match KeyWord::from_str(&identifier) {
+			Ok(KeyWord::True) | Ok(KeyWord::False) => token!(TokenKind::BoolLiteral(identifier), span),
+			Ok(keyword) => token!(TokenKind::Keyword(keyword), span),
 			// Wasn't a keyword, it was an identifier
-			_ => token!(TokenKind::Identifier(identifier), span),
-		}
+			Err(_) => token!(TokenKind::Identifier(identifier), span),
+		};
 	}
 
 	/// Consumes an inline or multiline comment.
 	pub fn consume_comment(&mut self, inline: bool) -> Token {
-		if inline == true {
-			// consume while
-			let initpos: Position = self.pos; // maniuplation of this really doesn't affect anything
-			let block: String = self.consume_segment(|c| c != '\n');
+		let initpos: Position = self.last_pos();
+		if inline {
+			self.peek(); // the second `/` of `//`, it is not part of the comment.
+			let line: String = self.consume_segment(|c| c != '\n');
 			return token!(
-				TokenKind::CommentLiteral(Comment::Block(block)),
-				Span::new(initpos, self.pos)
+				TokenKind::CommentLiteral(Comment::Line(line)),
+				self.span(initpos)
 			);
 		} else {
-			let initpos: Position = self.pos; // maniuplation of this really doesn't affect anything
-			self.peek(); // we need this to consume this old char.
-			let seg: String = self.consume_segment(|c| c != '*');
-			self.peek();
-			self.peek(); // this is hacky, pls find fix
+			self.peek(); // we need this to consume the `*` of `/*`.
+			let open: Span = self.span(initpos);
+			let mut block: String = String::new();
+
+			loop {
+				match self.peek() {
+					None => {
+						self.diagnostics.push(
+							Diagnostic::error("unterminated block comment", open)
+								.with_code(codes::UNTERMINATED_COMMENT)
+								.with_insertion(self.span_here(), "close the comment", "*/"),
+						);
+						break;
+					},
+					Some('*') if self.first() == '/' => {
+						self.peek();
+						break;
+					},
+					Some(c) => block.push(c),
+				}
+			}
+
 			return token!(
-				TokenKind::CommentLiteral(Comment::Line(seg)),
-				Span::new(initpos, self.pos)
+				TokenKind::CommentLiteral(Comment::Block(block)),
+				self.span(initpos)
 			);
 		}
 	}
@@ -460,18 +890,42 @@ impl Cursor<'_> {
 	/// Please note that this documentation may be outdated
 	pub fn consume_any_numeric(&mut self, initial: char) -> Token {
 		let mut number: String = String::from(initial);
-		let init_pos: Position = self.pos;
-		// immediately check next char but don't consume
-		number.push_str(
-			self.consume_segment(|c| c.is_numeric() || c == '.')
-				.chars()
-				.as_str(),
-		);
-		return Token::new(
-			TokenKind::NumberLiteral(Numeric::new(number)),
-			Span::new(init_pos, self.pos),
-			None,
-		);
+		let init_pos: Position = self.last_pos();
+
+		if initial == '0' && matches!(self.first(), 'x' | 'X' | 'b' | 'B' | 'o' | 'O') {
+			number.push(self.peek().unwrap());
+			number.push_str(&self.consume_segment(|c| c.is_ascii_alphanumeric() || c == '_'));
+		} else {
+			number.push_str(&self.consume_segment(|c| c.is_ascii_digit() || c == '_'));
+
+			// a `.` not followed by a digit is an accessor, eg: `1.toString()`
+			// any more `.` and digits are kept to report the number as invalid, eg: `1.2.3`
+			while self.first() == '.' && self.second().is_ascii_digit() {
+				number.push(self.peek().unwrap());
+				number.push_str(&self.consume_segment(|c| c.is_ascii_digit() || c == '_'));
+			}
+
+			let signed: bool = matches!(self.second(), '+' | '-') && self.nth_char(2).is_ascii_digit();
+			if matches!(self.first(), 'e' | 'E') && (self.second().is_ascii_digit() || signed) {
+				number.push(self.peek().unwrap());
+				if signed {
+					number.push(self.peek().unwrap());
+				}
+				number.push_str(&self.consume_segment(|c| c.is_ascii_digit() || c == '_'));
+			}
+
+			// any suffix, invalid suffixes are reported rather than split in to an identifier
+			number.push_str(&self.consume_segment(|c| c.is_ascii_alphanumeric() || c == '_'));
+		}
+
+		let span: Span = self.span(init_pos);
+		return match Numeric::new(&number) {
+			Ok(numeric) => token!(TokenKind::NumberLiteral(numeric), span),
+			Err(err) => {
+				self.diagnostics.push(Diagnostic::error(err.to_string(), span).with_code(codes::INVALID_NUMBER));
+				token!(TokenKind::ErrorLiteral, span)
+			},
+		};
 	}
 
 	pub fn last_is_escape_char(&self) -> bool {
@@ -481,7 +935,20 @@ impl Cursor<'_> {
 
 /// Tokenize an input into a iterator of tokens.
 pub fn tokenize(input: &str) -> Vec<Token> {
-	let mut cursor = Cursor::new(input);
+	return tokenize_with_diagnostics(input).0;
+}
+
+/// Tokenize an input, also returning any problems found while lexing.
+pub fn tokenize_with_diagnostics(input: &str) -> (Vec<Token>, DiagnosticSink) {
+	return tokenize_cursor(Cursor::new(input));
+}
+
+/// Tokenize a file from a `SourceMap`, every span is given the file's id.
+pub fn tokenize_file(file: &SourceFile) -> (Vec<Token>, DiagnosticSink) {
+	return tokenize_cursor(Cursor::with_file(file.source(), file.id()));
+}
+
+fn tokenize_cursor(mut cursor: Cursor) -> (Vec<Token>, DiagnosticSink) {
 	let mut tokens: Vec<Token> = Vec::new();
 
 	while !cursor.is_eof() {
@@ -490,5 +957,5 @@ pub fn tokenize(input: &str) -> Vec<Token> {
 		tokens.push(token);
 	}
 
-	return tokens;
+	return (tokens, cursor.diagnostics);
 }
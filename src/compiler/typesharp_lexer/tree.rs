@@ -0,0 +1,161 @@
+use super::token::{ Delimiter, Token, TokenKind, TokenValue };
+use crate::compiler::typesharp_ast::Span;
+use crate::error::{ codes, Diagnostic, DiagnosticSink };
+
+/// Tokens grouped by their delimiters, eg: `f(a, [b])` is `f` followed by a group of `(` holding `a`, `,` and a group of `[`.
+#[derive(Clone, PartialEq, Debug)]
+pub enum TokenTree {
+	Token(Token),
+	Delimited(Delimited),
+}
+
+/// Tokens between an opening delimiter and the delimiter that closes it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Delimited {
+	/// The opening delimiter, eg: `Paren("(")`
+	pub delimiter: Delimiter,
+	pub open: Span,
+	/// `None` if the group was never closed, eg: the file ended first.
+	pub close: Option<Span>,
+	pub trees: Vec<TokenTree>,
+}
+
+impl Delimited {
+	/// The span from the opening delimiter to the closing one, or to the last token if it was not closed.
+	pub fn span(&self) -> Span {
+		return match (self.close, self.trees.last()) {
+			(Some(close), _) => self.open.to(close),
+			(None, Some(last)) => self.open.to(last.span()),
+			(None, None) => self.open,
+		};
+	}
+}
+
+impl TokenTree {
+	pub fn span(&self) -> Span {
+		return match self {
+			TokenTree::Token(token) => token.span,
+			TokenTree::Delimited(group) => group.span(),
+		};
+	}
+}
+
+/// A group that has been opened and not closed yet.
+struct Frame {
+	delimiter: Delimiter,
+	open: Span,
+	trees: Vec<TokenTree>,
+}
+
+impl Frame {
+	fn close(self, close: Option<Span>) -> TokenTree {
+		return TokenTree::Delimited(Delimited {
+			delimiter: self.delimiter,
+			open: self.open,
+			close: close,
+			trees: self.trees,
+		});
+	}
+}
+
+/// Groups tokens by their delimiters, reporting delimiters that are never closed,
+/// closing delimiters that close nothing and closing delimiters of the wrong kind.
+///
+/// A closing delimiter of the wrong kind closes every group up to the one it matches if there is one,
+/// otherwise it closes the innermost group, as it is most likely a typo, eg: `[1, 2)`
+pub fn token_trees(tokens: &[Token]) -> (Vec<TokenTree>, DiagnosticSink) {
+	let mut diagnostics: DiagnosticSink = DiagnosticSink::new();
+	let mut stack: Vec<Frame> = Vec::new();
+	let mut trees: Vec<TokenTree> = Vec::new();
+
+	for token in tokens.iter() {
+		let delimiter: &Delimiter = match &token.kind {
+			TokenKind::DelimiterLiteral(delimiter) if *delimiter != Delimiter::NoDelim => delimiter,
+			_ => {
+				stack.last_mut().map_or(&mut trees, |f| &mut f.trees).push(TokenTree::Token(token.clone()));
+				continue;
+			},
+		};
+
+		if delimiter.is_open() {
+			stack.push(Frame { delimiter: delimiter.clone(), open: token.span, trees: Vec::new() });
+			continue;
+		}
+
+		let top: &Frame = match stack.last() {
+			Some(top) => top,
+			None => {
+				diagnostics.push(
+					Diagnostic::error(format!("unexpected closing delimiter `{}`", delimiter.get()), token.span)
+						.with_code(codes::UNEXPECTED_CLOSING_DELIMITER),
+				);
+				trees.push(TokenTree::Token(token.clone()));
+				continue;
+			},
+		};
+
+		let matching: Option<usize> = stack.iter().rposition(|f| delimiter.closes(&f.delimiter));
+		if matching != Some(stack.len() - 1) {
+			diagnostics.push(
+				Diagnostic::error(
+					format!("mismatched closing delimiter: expected `{}`, found `{}`", top.delimiter.closing().get(), delimiter.get()),
+					token.span,
+				)
+				.with_code(codes::MISMATCHED_DELIMITER)
+				.with_label(top.open, format!("`{}` opened here", top.delimiter.get())),
+			);
+		}
+
+		// the groups inside of the one it closes were never closed
+		let index: usize = matching.unwrap_or(stack.len() - 1);
+		while stack.len() > index + 1 {
+			let tree: TokenTree = stack.pop().unwrap().close(None);
+			stack.last_mut().unwrap().trees.push(tree);
+		}
+
+		let tree: TokenTree = stack.pop().unwrap().close(Some(token.span));
+		stack.last_mut().map_or(&mut trees, |f| &mut f.trees).push(tree);
+	}
+
+	let end: Span = tokens
+		.iter()
+		.rev()
+		.find(|t| !matches!(t.kind, TokenKind::WhiteSpace | TokenKind::Indent | TokenKind::CommentLiteral(_) | TokenKind::EOF))
+		.map_or(Span::default(), |t| t.span.shrink_to_end());
+	while let Some(frame) = stack.pop() {
+		diagnostics.push(
+			Diagnostic::error(format!("unclosed delimiter `{}`", frame.delimiter.get()), frame.open)
+				.with_code(codes::UNCLOSED_DELIMITER)
+				.with_label(end, format!("expected `{}` before the end of the file", frame.delimiter.closing().get())),
+		);
+		let tree: TokenTree = frame.close(None);
+		stack.last_mut().map_or(&mut trees, |f| &mut f.trees).push(tree);
+	}
+
+	return (trees, diagnostics);
+}
+
+/// The tokens of token trees in order, so that every delimiter is balanced:
+/// groups that were not closed are closed after their last token, and closing delimiters that close nothing are left out.
+/// Parsing these rather than the tokens the lexer produced stops one missing delimiter from causing an error in everything after it.
+pub fn flatten(trees: &[TokenTree]) -> Vec<Token> {
+	let mut tokens: Vec<Token> = Vec::new();
+	flatten_into(trees, &mut tokens);
+	return tokens;
+}
+
+fn flatten_into(trees: &[TokenTree], tokens: &mut Vec<Token>) {
+	for tree in trees.iter() {
+		match tree {
+			TokenTree::Token(token) if matches!(token.kind, TokenKind::DelimiterLiteral(_)) => (),
+			TokenTree::Token(token) => tokens.push(token.clone()),
+			TokenTree::Delimited(group) => {
+				tokens.push(Token::new(TokenKind::DelimiterLiteral(group.delimiter.clone()), group.open, None));
+				flatten_into(&group.trees, tokens);
+
+				let close: Span = group.close.unwrap_or_else(|| group.span().shrink_to_end());
+				tokens.push(Token::new(TokenKind::DelimiterLiteral(group.delimiter.closing()), close, None));
+			},
+		}
+	}
+}
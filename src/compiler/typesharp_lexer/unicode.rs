@@ -0,0 +1,86 @@
+use super::token::{ Token, TokenKind };
+use crate::compiler::typesharp_ast::Span;
+use crate::error::{ codes, Diagnostic, DiagnosticSink };
+use unicode_normalization::{ is_nfc_quick, IsNormalized, UnicodeNormalization };
+use unicode_security::{ mixed_script::AugmentedScriptSet, skeleton, MixedScript };
+use unicode_xid::UnicodeXID;
+
+/// Whether an identifier can start with `c`, eg: `_`, `a` or `é` but not `1`
+pub fn is_ident_start(c: char) -> bool {
+	return c == '_' || c.is_xid_start();
+}
+
+/// Whether an identifier can contain `c` after its first char, eg: `_`, `a` or `1`
+pub fn is_ident_continue(c: char) -> bool {
+	return c.is_xid_continue();
+}
+
+/// The NFC form of an identifier, so that names which look the same are the same,
+/// eg: `é` written as one char or as `e` followed by a combining accent.
+pub fn normalize(identifier: String) -> String {
+	if is_nfc_quick(identifier.chars()) == IsNormalized::Yes {
+		return identifier;
+	}
+
+	return identifier.nfc().collect();
+}
+
+/// Warns about identifiers that could be mistaken for something else:
+/// identifiers mixing characters of different scripts, eg: `pаypal` with a Cyrillic `а`,
+/// and different identifiers that look the same, eg: `pаypal` and `paypal`.
+pub fn confusable_identifiers(tokens: &[Token]) -> DiagnosticSink {
+	let mut diagnostics: DiagnosticSink = DiagnosticSink::new();
+	// the first use of each identifier, and what it looks like
+	let mut seen: Vec<(&str, Span, String)> = Vec::new();
+
+	for token in tokens.iter() {
+		let name: &str = match &token.kind {
+			TokenKind::Identifier(name) => name,
+			_ => continue,
+		};
+
+		if seen.iter().any(|(other, ..)| *other == name) {
+			continue;
+		}
+
+		if !name.is_single_script() {
+			diagnostics.push(
+				Diagnostic::warning(format!("identifier `{}` mixes characters from different scripts", name), token.span)
+					.with_code(codes::MIXED_SCRIPT_IDENTIFIER)
+					.with_note(format!("it has characters from {}", scripts(name).join(" and "))),
+			);
+		}
+
+		let looks: String = skeleton(name).collect();
+		let confused = seen.iter().find(|(other, _, other_looks)| *other_looks == looks && !(name.is_ascii() && other.is_ascii()));
+		if let Some((other, span, _)) = confused {
+			diagnostics.push(
+				Diagnostic::warning(format!("identifier `{}` looks the same as `{}`", name, other), token.span)
+					.with_code(codes::CONFUSABLE_IDENTIFIERS)
+					.with_label(*span, format!("`{}` is used here", other)),
+			);
+		}
+
+		seen.push((name, token.span, looks));
+	}
+
+	return diagnostics;
+}
+
+/// The names of the scripts used in an identifier, eg: `Latin` and `Cyrillic`
+fn scripts(name: &str) -> Vec<String> {
+	let mut scripts: Vec<String> = Vec::new();
+	for c in name.chars() {
+		let set: AugmentedScriptSet = AugmentedScriptSet::for_char(c);
+		if set.is_all() {
+			continue;
+		}
+
+		let script: String = set.to_string();
+		if !scripts.contains(&script) {
+			scripts.push(script);
+		}
+	}
+
+	return scripts;
+}
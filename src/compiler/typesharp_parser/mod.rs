@@ -3,9 +3,3 @@ pub mod parser;
 
 pub use self::{ op::*, parser::* };
 use crate::{ compiler::typesharp_lexer::Token };
-
-pub struct Parser;
-
-impl Parser {
-	// fn singleton_parse(stream: Vec<Token>) {}
-}
\ No newline at end of file
@@ -109,6 +109,9 @@ pub enum ComparisonOp {
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum AssignmentOp {
+	// x = y
+	Assign,
+
 	// x += y
 	Add,
 
@@ -145,3 +148,172 @@ pub enum AssignmentOp {
 	// [EXPERIMENT] x ??= y : Support may not be in future versions
 	Coalesce,
 }
+
+/// Whether an operator groups to the left, `(a - b) - c`
+/// or to the right, `a = (b = c)`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Associativity {
+	Left,
+	Right,
+}
+
+/// The binding power of operators, operators with a higher
+/// precedence are grouped before operators with a lower precedence.
+pub mod precedence {
+	pub const ASSIGNMENT: u8 = 1;
+	pub const LOGICAL_OR: u8 = 2;
+	pub const LOGICAL_AND: u8 = 3;
+	pub const BIT_OR: u8 = 4;
+	pub const BIT_XOR: u8 = 5;
+	pub const BIT_AND: u8 = 6;
+	pub const EQUALITY: u8 = 7;
+	pub const RELATIONAL: u8 = 8;
+	pub const SHIFT: u8 = 9;
+	pub const ADDITIVE: u8 = 10;
+	pub const MULTIPLICATIVE: u8 = 11;
+	pub const PREFIX: u8 = 12;
+	pub const POSTFIX: u8 = 13;
+}
+
+impl AnyOp {
+	pub fn as_str(&self) -> &'static str {
+		return match self {
+			AnyOp::BinOp(op) => op.as_str(),
+			AnyOp::UnaryOp(op) => op.as_str(),
+			AnyOp::LogicalOp(op) => op.as_str(),
+			AnyOp::ComparisonOp(op) => op.as_str(),
+			AnyOp::AssignmentOp(op) => op.as_str(),
+		};
+	}
+
+	pub fn precedence(&self) -> u8 {
+		return match self {
+			AnyOp::BinOp(op) => match op {
+				BinOp::Star | BinOp::Slash | BinOp::Percent => precedence::MULTIPLICATIVE,
+				BinOp::Plus | BinOp::Minus => precedence::ADDITIVE,
+				BinOp::Sh1 | BinOp::Shr | BinOp::UShr => precedence::SHIFT,
+				BinOp::And => precedence::BIT_AND,
+				BinOp::Caret => precedence::BIT_XOR,
+				BinOp::Or => precedence::BIT_OR,
+			},
+			AnyOp::UnaryOp(op) => match op {
+				UnaryOp::Inc | UnaryOp::Dec => precedence::POSTFIX,
+				UnaryOp::IncP
+				| UnaryOp::DecP
+				| UnaryOp::Neg
+				| UnaryOp::Pos
+				| UnaryOp::Not
+				| UnaryOp::Delete
+				| UnaryOp::Object => precedence::PREFIX,
+			},
+			AnyOp::LogicalOp(op) => match op {
+				LogicalOp::And => precedence::LOGICAL_AND,
+				LogicalOp::Or | LogicalOp::Coalasce => precedence::LOGICAL_OR,
+			},
+			AnyOp::ComparisonOp(op) => match op {
+				ComparisonOp::Eq | ComparisonOp::NotEq => precedence::EQUALITY,
+				ComparisonOp::GreaterThan
+				| ComparisonOp::GreaterThanOrEqual
+				| ComparisonOp::LessThan
+				| ComparisonOp::LessThanOrEqual
+				| ComparisonOp::Contains
+				| ComparisonOp::In
+				| ComparisonOp::InstanceOf => precedence::RELATIONAL,
+			},
+			AnyOp::AssignmentOp(_) => precedence::ASSIGNMENT,
+		};
+	}
+
+	pub fn associativity(&self) -> Associativity {
+		return match self {
+			AnyOp::AssignmentOp(_) => Associativity::Right,
+			AnyOp::UnaryOp(op) if op.is_prefix() => Associativity::Right,
+			_ => Associativity::Left,
+		};
+	}
+}
+
+impl BinOp {
+	pub fn as_str(&self) -> &'static str {
+		return match self {
+			BinOp::Plus => "+",
+			BinOp::Minus => "-",
+			BinOp::Star => "*",
+			BinOp::Slash => "/",
+			BinOp::Percent => "%",
+			BinOp::Caret => "^",
+			BinOp::And => "&",
+			BinOp::Or => "|",
+			BinOp::Sh1 => "<<",
+			BinOp::Shr => ">>",
+			BinOp::UShr => ">>>",
+		};
+	}
+}
+
+impl UnaryOp {
+	pub fn as_str(&self) -> &'static str {
+		return match self {
+			UnaryOp::IncP | UnaryOp::Inc => "++",
+			UnaryOp::DecP | UnaryOp::Dec => "--",
+			UnaryOp::Neg => "-",
+			UnaryOp::Pos => "+",
+			UnaryOp::Not => "!",
+			UnaryOp::Delete => "delete",
+			UnaryOp::Object => "{}",
+		};
+	}
+
+	/// Whether the operator is written before its operand.
+	pub fn is_prefix(&self) -> bool {
+		return !matches!(self, UnaryOp::Inc | UnaryOp::Dec);
+	}
+}
+
+impl LogicalOp {
+	pub fn as_str(&self) -> &'static str {
+		return match self {
+			LogicalOp::And => "&&",
+			LogicalOp::Or => "||",
+			LogicalOp::Coalasce => "??",
+		};
+	}
+}
+
+impl ComparisonOp {
+	pub fn as_str(&self) -> &'static str {
+		return match self {
+			ComparisonOp::Eq => "==",
+			ComparisonOp::NotEq => "!=",
+			ComparisonOp::GreaterThan => ">",
+			ComparisonOp::GreaterThanOrEqual => ">=",
+			ComparisonOp::LessThan => "<",
+			ComparisonOp::LessThanOrEqual => "<=",
+			ComparisonOp::Contains => "contains",
+			ComparisonOp::In => "in",
+			ComparisonOp::InstanceOf => "instanceof",
+		};
+	}
+}
+
+impl AssignmentOp {
+	pub fn as_str(&self) -> &'static str {
+		return match self {
+			AssignmentOp::Assign => "=",
+			AssignmentOp::Add => "+=",
+			AssignmentOp::Sub => "-=",
+			AssignmentOp::Mul => "*=",
+			AssignmentOp::Div => "/=",
+			AssignmentOp::Rem => "%=",
+			AssignmentOp::And => "&=",
+			AssignmentOp::Or => "|=",
+			AssignmentOp::Xor => "^=",
+			AssignmentOp::Sh1 => "<<=",
+			AssignmentOp::Shr => ">>=",
+			AssignmentOp::Ushr => ">>>=",
+			AssignmentOp::BoolAnd => "&&=",
+			AssignmentOp::BoolOr => "||=",
+			AssignmentOp::Coalesce => "??=",
+		};
+	}
+}
@@ -1,44 +1,1164 @@
-use crate::compiler::{typesharp_ast::ast, typesharp_lexer::Token};
-
-// pub struct TypeSharpRCTX {
-// 	pub resources: Vec<ast::IContextResource>,
-// 	pub scopes: Vec<ast::ASTStatement>,
-// 	pub current: Option<ast::IContextResource>
-// }
-
-// impl ast::IContext for TypeSharpRCTX {
-// 	fn new(&self) -> Self {
-// 		return Self {
-// 			resources: vec!(),
-// 			scopes: vec!(),
-// 			current: None
-// 		}
-// 	}
-
-// 	// Gets the current resource to be parsed.
-// 	fn getCurrentResource(&self) -> Option<ast::IContextResource> {
-// 		return self.current.clone();
-// 	}
-
-// 	/// Gets the current AST scope
-// 	fn getCurrentScope(&self) -> Option<&ast::ASTStatement> {
-// 		if self.scopes.len() > 0 {
-// 			self.scopes.get(self.scopes.len())
-// 		} else {
-// 			None
-// 		}
-// 	}
-
-// 	/// Gets all AST scopes
-// 	fn getScopes(&self) -> Vec<ast::ASTStatement> {
-// 		self.scopes.clone()
-// 	}
-
-// 	fn nextResource(&self) -> bool {
-// 		true
-// 	}
-// }
-
-// // Typesharp parser
-// pub fn parse(mut curr: &ast::AST, tokens: &Vec<Token>) {
-// }
+use crate::compiler::{
+	typesharp_ast::{ ast, ast::*, node::NodeId, types, KeyWord, Position, Span },
+	typesharp_feature::Feature,
+	typesharp_lexer::{ token::{ Delimiter, TemplateChunk }, Token, TokenKind },
+	typesharp_parser::op::*,
+};
+use crate::error::{ codes, Diagnostic, DiagnosticSink };
+use std::borrow::Cow;
+
+/// How deeply expressions, blocks and types can be nested, deeper code is reported instead of overflowing the stack.
+pub const MAX_DEPTH: usize = 64;
+
+/// Typesharp parser
+/// Turns the output of `tokenize` into a `Module`.
+///
+/// Whitespace and comments are skipped, and when a statement can not be parsed
+/// a diagnostic is recorded and the parser skips ahead to the next statement.
+pub struct Parser<'a> {
+	tokens: Vec<Cow<'a, Token>>,
+	cursor: usize,
+	next_id: u32,
+	eof: Span,
+	/// How many expressions, blocks and types the cursor is inside of.
+	depth: usize,
+	/// Experimental syntax that was parsed, and the feature it needs.
+	/// It is only rejected once the features enabled by the module's directives are known.
+	pub gated: Vec<(Feature, Span)>,
+	pub diagnostics: DiagnosticSink,
+}
+
+impl<'a> Parser<'a> {
+	pub fn new(tokens: &'a [Token]) -> Self {
+		let eof: Span = match tokens.last() {
+			Some(token) => token.span.shrink_to_end(),
+			None => Span::default(),
+		};
+
+		return Parser {
+			tokens: tokens.iter().filter(|t| !Parser::is_trivia(&t.kind)).map(Cow::Borrowed).collect(),
+			cursor: 0,
+			next_id: 0,
+			eof: eof,
+			depth: 0,
+			gated: Vec::new(),
+			diagnostics: DiagnosticSink::new(),
+		};
+	}
+
+	/// Parses a stream of tokens in to a module.
+	/// If any errors were found, all diagnostics are returned instead.
+	pub fn parse(tokens: &[Token]) -> Result<Module, Vec<Diagnostic>> {
+		let mut parser = Parser::new(tokens);
+		let module: Module = parser.parse_module();
+
+		if parser.diagnostics.has_errors() {
+			return Err(parser.diagnostics.into_vec());
+		}
+
+		return Ok(module);
+	}
+
+	pub fn parse_module(&mut self) -> Module {
+		let start: Span = self.peek_span();
+		let mut directives: Vec<Directive> = Vec::new();
+		let mut statements: Vec<Statement> = Vec::new();
+
+		while !self.is_eof() {
+			if self.peek_kind() == Some(&TokenKind::At) {
+				let start: usize = self.cursor;
+				match self.parse_directive() {
+					Ok(directive) => directives.push(directive),
+					Err(err) => {
+						self.diagnostics.push(err);
+						self.recover(start);
+					},
+				}
+				continue;
+			}
+
+			if self.is_delim("}") {
+				let err = self.unexpected("a statement");
+				self.diagnostics.push(err);
+				self.bump();
+				continue;
+			}
+
+			if let Some(statement) = self.parse_statement_or_recover() {
+				statements.push(statement);
+			}
+		}
+
+		return Module {
+			id: self.next_id(),
+			directives: directives,
+			statements: statements,
+			loc: start.to(self.prev_span()),
+		};
+	}
+
+	/// Parses a directive, eg: `@compiler(numericTypes);`
+	fn parse_directive(&mut self) -> Result<Directive, Diagnostic> {
+		let start: Span = self.peek_span();
+		self.bump();
+		let name: Identifier = self.expect_identifier()?;
+
+		let args: Vec<Identifier> = if self.eat_delim("(") {
+			self.parse_delimited(")", |p| match p.peek_kind() {
+				Some(TokenKind::NumberLiteral(number)) => {
+					let name: String = number.to_string();
+					p.bump();
+					Ok(Identifier { name: name, loc: p.prev_span(), tokens: None })
+				},
+				_ => p.expect_identifier(),
+			})?
+		} else {
+			Vec::new()
+		};
+		self.expect_terminator()?;
+
+		return Ok(Directive {
+			id: self.next_id(),
+			name: name,
+			args: args,
+			loc: start.to(self.prev_span()),
+		});
+	}
+
+	/// Parses a statement, if this fails the error is recorded
+	/// and tokens are skipped until the start of the next statement.
+	fn parse_statement_or_recover(&mut self) -> Option<Statement> {
+		if self.eat(&TokenKind::ExpressionTerminator) {
+			return None;
+		}
+
+		// the syntax of a union declaration is not decided yet, it is skipped as a whole so only it is reported
+		if self.is_keyword(KeyWord::Union) {
+			let start: Span = self.peek_span();
+			self.bump();
+			self.skip_declaration();
+			self.diagnostics.push(
+				Diagnostic::error("`union` declarations are not supported yet", start)
+					.with_code(codes::UNSUPPORTED_SYNTAX)
+					.with_note("use a union type instead, eg: `let x: u8 | string = 0;`"),
+			);
+			return None;
+		}
+
+		let start: usize = self.cursor;
+		match self.parse_statement() {
+			Ok(statement) => return Some(statement),
+			Err(err) => {
+				self.diagnostics.push(err);
+				self.recover(start);
+				return None;
+			}
+		}
+	}
+
+	/// Skips the rest of a declaration, up to its `;` or to the end of its `{}` body.
+	fn skip_declaration(&mut self) {
+		while !self.is_eof() && !self.is_delim("}") {
+			if self.eat(&TokenKind::ExpressionTerminator) {
+				return;
+			}
+
+			if self.eat_delim("{") {
+				let mut depth: usize = 1;
+				while depth > 0 && !self.is_eof() {
+					if self.is_delim("{") {
+						depth += 1;
+					} else if self.is_delim("}") {
+						depth -= 1;
+					}
+					self.bump();
+				}
+				self.eat(&TokenKind::ExpressionTerminator);
+				return;
+			}
+			self.bump();
+		}
+	}
+
+	/// Skips tokens until a statement terminator or the start of a new statement.
+	fn recover(&mut self, start: usize) {
+		if self.cursor == start {
+			self.bump();
+		}
+
+		while !self.is_eof() && !self.is_delim("}") {
+			if self.eat(&TokenKind::ExpressionTerminator) {
+				return;
+			}
+
+			match self.peek_kind() {
+				Some(TokenKind::Keyword(KeyWord::Let))
+				| Some(TokenKind::Keyword(KeyWord::Const))
+				| Some(TokenKind::Keyword(KeyWord::Function))
+				| Some(TokenKind::Keyword(KeyWord::Class))
+				| Some(TokenKind::Keyword(KeyWord::Enum))
+				| Some(TokenKind::Keyword(KeyWord::If))
+				| Some(TokenKind::Keyword(KeyWord::While))
+				| Some(TokenKind::Keyword(KeyWord::For))
+				| Some(TokenKind::Keyword(KeyWord::Return))
+				| Some(TokenKind::Keyword(KeyWord::Package)) => return,
+				_ => self.bump(),
+			};
+		}
+	}
+
+	pub fn parse_statement(&mut self) -> Result<Statement, Diagnostic> {
+		let start: Span = self.peek_span();
+		let kind: StatementKind = match self.peek_kind() {
+			Some(TokenKind::Keyword(KeyWord::Let)) => {
+				self.bump();
+				StatementKind::Let(self.parse_local()?)
+			},
+			Some(TokenKind::Keyword(KeyWord::Const)) => {
+				self.bump();
+				StatementKind::Constant(self.parse_local()?)
+			},
+			Some(TokenKind::Keyword(KeyWord::Function)) | Some(TokenKind::Keyword(KeyWord::Fn))
+				if matches!(self.nth_kind(1), Some(TokenKind::Identifier(_))) =>
+			{
+				StatementKind::Function(self.parse_function()?)
+			},
+			Some(TokenKind::Keyword(KeyWord::Class)) => StatementKind::Class(self.parse_class()?),
+			Some(TokenKind::Keyword(KeyWord::Enum)) => StatementKind::Enum(self.parse_enum()?),
+			Some(TokenKind::Keyword(KeyWord::If)) => self.parse_if()?,
+			Some(TokenKind::Keyword(KeyWord::While)) => {
+				self.bump();
+				let cond: Expression = self.parse_expression()?;
+				StatementKind::While(cond, self.parse_block()?)
+			},
+			Some(TokenKind::Keyword(KeyWord::For)) => StatementKind::For(Box::new(self.parse_for()?)),
+			Some(TokenKind::Keyword(KeyWord::Return)) => {
+				self.bump();
+				let value: Option<Expression> = if self.at_terminator() {
+					None
+				} else {
+					Some(self.parse_expression()?)
+				};
+				self.expect_terminator()?;
+				StatementKind::Return(value)
+			},
+			Some(TokenKind::Keyword(KeyWord::Break)) => {
+				self.bump();
+				self.expect_terminator()?;
+				StatementKind::Break
+			},
+			Some(TokenKind::Keyword(KeyWord::Continue)) => {
+				self.bump();
+				self.expect_terminator()?;
+				StatementKind::Continue
+			},
+			Some(TokenKind::Keyword(KeyWord::Package)) => {
+				self.gated.push((Feature::Packages, start));
+				// the statements of a block are always nested, those of the file are not
+				if self.depth > 0 {
+					return Err(
+						Diagnostic::error("a `package` declaration can only be at the top level of a file", start)
+							.with_code(codes::UNEXPECTED_TOKEN),
+					);
+				}
+
+				self.bump();
+				let mut path: Vec<PackageName> = Vec::new();
+				loop {
+					let name: Identifier = self.expect_identifier()?;
+					path.push(PackageName { id: self.next_id(), name: name });
+					if !self.eat(&TokenKind::Accessor) {
+						break;
+					}
+				}
+				self.expect_terminator()?;
+				StatementKind::Package(path)
+			},
+			_ if self.is_delim("{") => StatementKind::Scope(self.parse_block()?),
+			_ => {
+				let expr: Expression = self.parse_expression()?;
+				self.expect_terminator()?;
+				StatementKind::Expression(expr)
+			},
+		};
+
+		return Ok(Statement {
+			id: self.next_id(),
+			kind: kind,
+			loc: start.to(self.prev_span()),
+		});
+	}
+
+	/// Parses the rest of a `let` or `const` declaration, after the keyword.
+	/// EG: `x: u8 = 0;`
+	/// If the value can not be parsed the name is still declared, so its uses are not reported as well.
+	fn parse_local(&mut self) -> Result<Local, Diagnostic> {
+		let start: usize = self.cursor;
+		let name: Identifier = self.expect_identifier()?;
+		let typ: Option<types::Type> = self.parse_type_annotation()?;
+		let value: Option<Expression> = if self.eat(&TokenKind::AssignmentLiteral(AssignmentOp::Assign)) {
+			let value_start: Span = self.peek_span();
+			match self.parse_expression() {
+				Ok(value) => Some(value),
+				Err(err) => {
+					self.diagnostics.push(err);
+					self.recover(start);
+					let value: Expression = self.expression(ExpressionKind::Err, value_start.to(self.prev_span()));
+					return Ok(Local {
+						id: self.next_id(),
+						name: name,
+						typ: typ,
+						value: Some(value),
+					});
+				},
+			}
+		} else {
+			None
+		};
+		self.expect_terminator()?;
+
+		return Ok(Local {
+			id: self.next_id(),
+			name: name,
+			typ: typ,
+			value: value,
+		});
+	}
+
+	/// Parses a function, starting at the `function` or `fn` keyword.
+	/// The name of the function is optional.
+	fn parse_function(&mut self) -> Result<Function, Diagnostic> {
+		let start: Span = self.peek_span();
+		self.bump();
+
+		let name: Option<Identifier> = match self.peek_kind() {
+			Some(TokenKind::Identifier(_)) => Some(self.expect_identifier()?),
+			_ => None,
+		};
+
+		return self.parse_function_rest(start, name);
+	}
+
+	/// Parses the parameters, return type and body of a function, after its name.
+	fn parse_function_rest(&mut self, start: Span, name: Option<Identifier>) -> Result<Function, Diagnostic> {
+		let generics: Vec<GenericParam> = self.parse_generics()?;
+		self.expect_delim("(")?;
+		let params: Vec<Param> = self.parse_delimited(")", |p| p.parse_param())?;
+		let ret: Option<types::Type> = self.parse_type_annotation()?;
+		let body: Block = self.parse_block()?;
+
+		return Ok(Function {
+			id: self.next_id(),
+			name: name,
+			generics: generics,
+			params: params,
+			ret: ret,
+			body: body,
+			loc: start.to(self.prev_span()),
+		});
+	}
+
+	fn parse_param(&mut self) -> Result<Param, Diagnostic> {
+		let name: Identifier = self.expect_identifier()?;
+		let typ: Option<types::Type> = self.parse_type_annotation()?;
+
+		return Ok(Param {
+			id: self.next_id(),
+			loc: name.loc.to(self.prev_span()),
+			name: name,
+			typ: typ,
+		});
+	}
+
+	/// Parses a class, starting at the `class` keyword.
+	fn parse_class(&mut self) -> Result<Class, Diagnostic> {
+		let start: Span = self.peek_span();
+		self.bump();
+
+		let name: Identifier = self.expect_identifier()?;
+		let generics: Vec<GenericParam> = self.parse_generics()?;
+		let extends: Option<types::Type> = match self.eat_keyword(KeyWord::Extends) {
+			true => Some(self.parse_type()?),
+			false => None,
+		};
+
+		let open: Span = self.expect_delim("{")?;
+		let mut members: Vec<Member> = Vec::new();
+		while !self.is_eof() && !self.is_delim("}") {
+			if self.eat(&TokenKind::ExpressionTerminator) {
+				continue;
+			}
+
+			let member_start: usize = self.cursor;
+			match self.parse_member() {
+				Ok(member) => members.push(member),
+				Err(err) => {
+					self.diagnostics.push(err);
+					self.recover(member_start);
+				},
+			}
+		}
+		self.expect_delim("}").map_err(|err| err.with_label(open, "class starts here"))?;
+
+		return Ok(Class {
+			id: self.next_id(),
+			name: name,
+			generics: generics,
+			extends: extends,
+			members: members,
+			loc: start.to(self.prev_span()),
+		});
+	}
+
+	/// Parses a field or method of a class, eg: `x: u8 = 0;` or `public foo(): u8 {}`
+	fn parse_member(&mut self) -> Result<Member, Diagnostic> {
+		let start: Span = self.peek_span();
+		let mut modifiers: Vec<KeyWord> = Vec::new();
+		while let Some(TokenKind::Keyword(
+			k @ (KeyWord::Public
+			| KeyWord::Pub
+			| KeyWord::Private
+			| KeyWord::Protected
+			| KeyWord::Static
+			| KeyWord::Override
+			| KeyWord::Final
+			| KeyWord::Async),
+		)) = self.peek_kind().cloned()
+		{
+			match k {
+				KeyWord::Final => self.gated.push((Feature::FinalMembers, self.peek_span())),
+				KeyWord::Override => self.gated.push((Feature::OverrideMembers, self.peek_span())),
+				_ => (),
+			}
+			modifiers.push(k);
+			self.bump();
+		}
+
+		let kind: MemberKind = if self.is_keyword(KeyWord::Function) || self.is_keyword(KeyWord::Fn) {
+			MemberKind::Method(self.parse_function()?)
+		} else if self.nth_is_delim(1, "(") || self.nth_kind(1) == Some(&TokenKind::ComparisonOpLiteral(ComparisonOp::LessThan)) {
+			let method_start: Span = self.peek_span();
+			let name: Identifier = self.expect_identifier()?;
+			MemberKind::Method(self.parse_function_rest(method_start, Some(name))?)
+		} else {
+			MemberKind::Field(self.parse_local()?)
+		};
+
+		return Ok(Member {
+			id: self.next_id(),
+			modifiers: modifiers,
+			kind: kind,
+			loc: start.to(self.prev_span()),
+		});
+	}
+
+	/// Parses an enum, starting at the `enum` keyword.
+	fn parse_enum(&mut self) -> Result<Enum, Diagnostic> {
+		let start: Span = self.peek_span();
+		self.bump();
+
+		let name: Identifier = self.expect_identifier()?;
+		self.expect_delim("{")?;
+		let variants: Vec<Variant> = self.parse_delimited("}", |p| {
+			let name: Identifier = p.expect_identifier()?;
+			let value: Option<Expression> = match p.eat(&TokenKind::AssignmentLiteral(AssignmentOp::Assign)) {
+				true => Some(p.parse_expression()?),
+				false => None,
+			};
+
+			return Ok(Variant {
+				id: p.next_id(),
+				loc: name.loc.to(p.prev_span()),
+				name: name,
+				value: value,
+			});
+		})?;
+
+		return Ok(Enum {
+			id: self.next_id(),
+			name: name,
+			variants: variants,
+			loc: start.to(self.prev_span()),
+		});
+	}
+
+	/// Parses `if x {} else {}`, `else if` is parsed as a nested `if` statement.
+	fn parse_if(&mut self) -> Result<StatementKind, Diagnostic> {
+		self.bump();
+		let cond: Expression = self.parse_expression()?;
+		let then: Block = self.parse_block()?;
+
+		if !self.eat_keyword(KeyWord::Else) {
+			return Ok(StatementKind::If(cond, then, None));
+		}
+
+		let otherwise: Statement = if self.is_keyword(KeyWord::If) {
+			let start: Span = self.peek_span();
+			let kind: StatementKind = self.parse_if()?;
+			Statement {
+				id: self.next_id(),
+				kind: kind,
+				loc: start.to(self.prev_span()),
+			}
+		} else {
+			let block: Block = self.parse_block()?;
+			Statement {
+				id: self.next_id(),
+				loc: block.loc,
+				kind: StatementKind::Scope(block),
+			}
+		};
+
+		return Ok(StatementKind::If(cond, then, Some(Box::new(otherwise))));
+	}
+
+	/// Parses `for let x in y {}` and `for (const x of y) {}`
+	fn parse_for(&mut self) -> Result<ForLoop, Diagnostic> {
+		self.bump();
+		let parens: bool = self.eat_delim("(");
+
+		if !self.eat_keyword(KeyWord::Let) {
+			self.eat_keyword(KeyWord::Const);
+		}
+
+		let name: Identifier = self.expect_identifier()?;
+		let typ: Option<types::Type> = self.parse_type_annotation()?;
+		let of: bool = if self.eat_keyword(KeyWord::Of) {
+			true
+		} else if self.eat_keyword(KeyWord::In) {
+			false
+		} else {
+			return Err(self.unexpected("`in` or `of`"));
+		};
+
+		let iter: Expression = self.parse_expression()?;
+		if parens {
+			self.expect_delim(")")?;
+		}
+
+		return Ok(ForLoop {
+			binding: Local {
+				id: self.next_id(),
+				name: name,
+				typ: typ,
+				value: None,
+			},
+			of: of,
+			iter: iter,
+			body: self.parse_block()?,
+		});
+	}
+
+	pub fn parse_block(&mut self) -> Result<Block, Diagnostic> {
+		return self.nested(|p| p.parse_block_inner());
+	}
+
+	fn parse_block_inner(&mut self) -> Result<Block, Diagnostic> {
+		let start: Span = self.expect_delim("{")?;
+		let mut statements: Vec<Statement> = Vec::new();
+
+		while !self.is_eof() && !self.is_delim("}") {
+			if let Some(statement) = self.parse_statement_or_recover() {
+				statements.push(statement);
+			}
+		}
+
+		let end: Span = self
+			.expect_delim("}")
+			.map_err(|err| err.with_label(start, "block starts here"))?;
+
+		return Ok(Block {
+			id: self.next_id(),
+			statements: statements,
+			loc: start.to(end),
+		});
+	}
+
+	/// Parses an optional type annotation, eg: `: u8[]`
+	fn parse_type_annotation(&mut self) -> Result<Option<types::Type>, Diagnostic> {
+		if !self.eat(&TokenKind::Colon) {
+			return Ok(None);
+		}
+
+		return Ok(Some(self.parse_type()?));
+	}
+
+	/// Parses a type, eg: `u8[]`, `Box<string>`, `u8 | string` or `fn(u8): bool`
+	pub fn parse_type(&mut self) -> Result<types::Type, Diagnostic> {
+		return self.nested(|p| p.parse_type_inner());
+	}
+
+	fn parse_type_inner(&mut self) -> Result<types::Type, Diagnostic> {
+		let first: types::Type = self.parse_type_atom()?;
+		if self.peek_kind() != Some(&TokenKind::BinaryOpLiteral(BinOp::Or)) {
+			return Ok(first);
+		}
+
+		let mut variants: Vec<types::Type> = vec![first];
+		while self.eat(&TokenKind::BinaryOpLiteral(BinOp::Or)) {
+			variants.push(self.parse_type_atom()?);
+		}
+
+		return Ok(types::Type {
+			loc: variants[0].loc.to(self.prev_span()),
+			kind: types::TypeKinds::Union(variants),
+			tokens: None,
+		});
+	}
+
+	/// Parses a type that is not a union, along with any `[]` after it.
+	fn parse_type_atom(&mut self) -> Result<types::Type, Diagnostic> {
+		let start: Span = self.peek_span();
+		let mut typ: types::Type = if self.eat_keyword(KeyWord::Fn) || self.eat_keyword(KeyWord::Function) {
+			self.expect_delim("(")?;
+			let params: Vec<types::Type> = self.parse_delimited(")", |p| p.parse_type())?;
+			let ret: Option<types::Type> = self.parse_type_annotation()?;
+
+			types::Type {
+				loc: start.to(self.prev_span()),
+				kind: types::TypeKinds::Function(params, ret.map(Box::new)),
+				tokens: None,
+			}
+		} else if self.eat_delim("(") {
+			// parentheses only group, eg: `(u8 | string)[]`
+			let inner: types::Type = self.parse_type()?;
+			self.expect_delim(")")?;
+			types::Type { loc: start.to(self.prev_span()), ..inner }
+		} else {
+			let name: Identifier = self.expect_identifier()?;
+			if self.eat(&TokenKind::ComparisonOpLiteral(ComparisonOp::LessThan)) {
+				let mut args: Vec<types::Type> = vec![self.parse_type()?];
+				while self.eat(&TokenKind::Comma) {
+					args.push(self.parse_type()?);
+				}
+				if !self.eat_close_angle() {
+					return Err(self.unexpected("`,` or `>`"));
+				}
+
+				types::Type {
+					loc: name.loc.to(self.prev_span()),
+					kind: types::TypeKinds::Generic(name, args),
+					tokens: None,
+				}
+			} else {
+				types::Type {
+					loc: name.loc,
+					kind: types::TypeKinds::Named(name.name),
+					tokens: None,
+				}
+			}
+		};
+
+		while self.is_delim("[") && self.nth_is_delim(1, "]") {
+			self.bump();
+			self.bump();
+			typ = types::Type {
+				loc: typ.loc.to(self.prev_span()),
+				kind: types::TypeKinds::Array(Box::new(typ)),
+				tokens: None,
+			};
+		}
+
+		return Ok(typ);
+	}
+
+	/// Parses the type parameters of a function or class, eg: `<T, U>`
+	fn parse_generics(&mut self) -> Result<Vec<GenericParam>, Diagnostic> {
+		let mut generics: Vec<GenericParam> = Vec::new();
+		if !self.eat(&TokenKind::ComparisonOpLiteral(ComparisonOp::LessThan)) {
+			return Ok(generics);
+		}
+
+		loop {
+			let name: Identifier = self.expect_identifier()?;
+			generics.push(GenericParam { id: self.next_id(), name: name });
+			if !self.eat(&TokenKind::Comma) {
+				break;
+			}
+		}
+		if !self.eat_close_angle() {
+			return Err(self.unexpected("`,` or `>`"));
+		}
+
+		return Ok(generics);
+	}
+
+	/// Eats a `>` closing type arguments.
+	/// Operators starting with `>` are lexed as a single token, eg: `>>` in `Box<Box<u8>>` or `>=` in `Box<u8>= x`,
+	/// so their first `>` is split off and the rest of the operator is left to be parsed.
+	fn eat_close_angle(&mut self) -> bool {
+		let rest: TokenKind = match self.peek_kind() {
+			Some(TokenKind::ComparisonOpLiteral(ComparisonOp::GreaterThan)) => {
+				self.bump();
+				return true;
+			},
+			Some(TokenKind::BinaryOpLiteral(BinOp::Shr)) => TokenKind::ComparisonOpLiteral(ComparisonOp::GreaterThan),
+			Some(TokenKind::BinaryOpLiteral(BinOp::UShr)) => TokenKind::BinaryOpLiteral(BinOp::Shr),
+			Some(TokenKind::ComparisonOpLiteral(ComparisonOp::GreaterThanOrEqual)) => TokenKind::AssignmentLiteral(AssignmentOp::Assign),
+			Some(TokenKind::AssignmentLiteral(AssignmentOp::Shr)) => TokenKind::ComparisonOpLiteral(ComparisonOp::GreaterThanOrEqual),
+			Some(TokenKind::AssignmentLiteral(AssignmentOp::Ushr)) => TokenKind::AssignmentLiteral(AssignmentOp::Shr),
+			_ => return false,
+		};
+
+		let span: Span = self.peek_span();
+		let mut middle: Position = span.start();
+		middle.increment('>');
+		let close: Token = Token::new(
+			TokenKind::ComparisonOpLiteral(ComparisonOp::GreaterThan),
+			Span::new(span.start(), middle).in_file(span.file()),
+			None,
+		);
+		let rest: Token = Token::new(rest, Span::new(middle, span.end()).in_file(span.file()), None);
+
+		self.tokens[self.cursor] = Cow::Owned(close);
+		self.tokens.insert(self.cursor + 1, Cow::Owned(rest));
+		self.bump();
+		return true;
+	}
+
+	pub fn parse_expression(&mut self) -> Result<Expression, Diagnostic> {
+		return self.parse_expression_with(precedence::ASSIGNMENT);
+	}
+
+	/// Parses an expression, only consuming operators that have
+	/// a precedence of at least `min`.
+	fn parse_expression_with(&mut self, min: u8) -> Result<Expression, Diagnostic> {
+		return self.nested(|p| p.parse_expression_inner(min));
+	}
+
+	fn parse_expression_inner(&mut self, min: u8) -> Result<Expression, Diagnostic> {
+		let mut lhs: Expression = self.parse_prefix()?;
+
+		loop {
+			let op: AnyOp = match self.peek_infix_op() {
+				Some(op) if op.precedence() >= min => op,
+				_ => return Ok(lhs),
+			};
+			if let AnyOp::AssignmentOp(AssignmentOp::BoolAnd | AssignmentOp::BoolOr | AssignmentOp::Coalesce) = op {
+				self.gated.push((Feature::LogicalAssignment, self.peek_span()));
+			}
+			self.bump();
+
+			let next: u8 = match op.associativity() {
+				Associativity::Left => op.precedence() + 1,
+				Associativity::Right => op.precedence(),
+			};
+			let rhs: Expression = self.parse_expression_with(next)?;
+			let loc: Span = lhs.loc.to(rhs.loc);
+
+			let kind: ExpressionKind = match op {
+				AnyOp::BinOp(op) => ExpressionKind::Binary(op, Box::new(lhs), Box::new(rhs)),
+				AnyOp::LogicalOp(op) => ExpressionKind::Logical(op, Box::new(lhs), Box::new(rhs)),
+				AnyOp::ComparisonOp(op) => ExpressionKind::Compare(op, Box::new(lhs), Box::new(rhs)),
+				AnyOp::AssignmentOp(op) => {
+					if !is_assignable(&lhs) {
+						self.diagnostics.push(
+							Diagnostic::error("invalid left-hand side of assignment", lhs.loc)
+								.with_code(codes::INVALID_ASSIGNMENT)
+								.with_note("only names, fields and indexes can be assigned to"),
+						);
+					}
+					ExpressionKind::Assign(op, Box::new(lhs), Box::new(rhs))
+				},
+				AnyOp::UnaryOp(_) => unreachable!("unary operators are never infix"),
+			};
+			lhs = self.expression(kind, loc);
+		}
+	}
+
+	/// Operators that can appear between two expressions.
+	fn peek_infix_op(&self) -> Option<AnyOp> {
+		return match self.peek_kind()? {
+			TokenKind::BinaryOpLiteral(op) => Some(AnyOp::BinOp(op.clone())),
+			TokenKind::AssignmentLiteral(op) => Some(AnyOp::AssignmentOp(op.clone())),
+			TokenKind::LogicalOpLiteral(op) => Some(AnyOp::LogicalOp(op.clone())),
+			TokenKind::ComparisonOpLiteral(op) => Some(AnyOp::ComparisonOp(op.clone())),
+			TokenKind::Keyword(KeyWord::In) => Some(AnyOp::ComparisonOp(ComparisonOp::In)),
+			TokenKind::Keyword(KeyWord::InstanceOf) => Some(AnyOp::ComparisonOp(ComparisonOp::InstanceOf)),
+			_ => None,
+		};
+	}
+
+	/// Parses prefix operators, eg: `-x`, `!x` or `++x`
+	fn parse_prefix(&mut self) -> Result<Expression, Diagnostic> {
+		let op: UnaryOp = match self.peek_kind() {
+			Some(TokenKind::BinaryOpLiteral(BinOp::Minus)) => UnaryOp::Neg,
+			Some(TokenKind::BinaryOpLiteral(BinOp::Plus)) => UnaryOp::Pos,
+			Some(TokenKind::UnaryOpLiteral(UnaryOp::Inc)) | Some(TokenKind::UnaryOpLiteral(UnaryOp::IncP)) => UnaryOp::IncP,
+			Some(TokenKind::UnaryOpLiteral(UnaryOp::Dec)) | Some(TokenKind::UnaryOpLiteral(UnaryOp::DecP)) => UnaryOp::DecP,
+			Some(TokenKind::UnaryOpLiteral(op)) => op.clone(),
+			Some(TokenKind::Keyword(KeyWord::Delete)) => UnaryOp::Delete,
+			_ => return self.parse_postfix(),
+		};
+		let start: Span = self.peek_span();
+		self.bump();
+		if op == UnaryOp::Delete {
+			self.gated.push((Feature::DeleteOperator, start));
+		}
+
+		let operand: Expression = self.parse_expression_with(precedence::PREFIX)?;
+		let loc: Span = start.to(operand.loc);
+		return Ok(self.expression(ExpressionKind::Unary(op, Box::new(operand)), loc));
+	}
+
+	/// Parses calls, indexing, accessors and postfix operators following a primary expression.
+	fn parse_postfix(&mut self) -> Result<Expression, Diagnostic> {
+		let mut expr: Expression = self.parse_primary()?;
+
+		loop {
+			let start: Span = expr.loc;
+			let kind: ExpressionKind = if self.eat(&TokenKind::Accessor) {
+				let name: Identifier = self.expect_identifier()?;
+				if self.eat_delim("(") {
+					let args: Vec<Expression> = self.parse_delimited(")", |p| p.parse_expression())?;
+					let loc: Span = name.loc.to(self.prev_span());
+					ExpressionKind::Method(Box::new(expr), name, args, loc)
+				} else {
+					ExpressionKind::Field(Box::new(expr), name)
+				}
+			} else if self.eat_delim("(") {
+				let args: Vec<Expression> = self.parse_delimited(")", |p| p.parse_expression())?;
+				ExpressionKind::Call(Box::new(expr), args)
+			} else if self.eat_delim("[") {
+				let index: Expression = self.parse_expression()?;
+				self.expect_delim("]")?;
+				ExpressionKind::Index(Box::new(expr), Box::new(index))
+			} else if let Some(TokenKind::UnaryOpLiteral(op)) = self.peek_kind() {
+				let op: UnaryOp = match op {
+					UnaryOp::Inc | UnaryOp::IncP => UnaryOp::Inc,
+					UnaryOp::Dec | UnaryOp::DecP => UnaryOp::Dec,
+					_ => return Ok(expr),
+				};
+				self.bump();
+				ExpressionKind::Unary(op, Box::new(expr))
+			} else {
+				return Ok(expr);
+			};
+
+			let loc: Span = start.to(self.prev_span());
+			expr = self.expression(kind, loc);
+		}
+	}
+
+	fn parse_primary(&mut self) -> Result<Expression, Diagnostic> {
+		// cloning a token that was not split only copies the reference to it
+		let token: Cow<'a, Token> = match self.tokens.get(self.cursor) {
+			Some(token) => token.clone(),
+			None => return Err(self.unexpected("an expression")),
+		};
+
+		let kind: ExpressionKind = match &token.kind {
+			TokenKind::NumberLiteral(n) => ExpressionKind::Literal(Literal::Number(n.clone())),
+			TokenKind::StringLiteral(s) => ExpressionKind::Literal(Literal::String(s.clone())),
+			TokenKind::ErrorLiteral => ExpressionKind::Err,
+			TokenKind::TemplateLiteral(chunks) => ExpressionKind::Template(self.parse_template(chunks, token.span)),
+			TokenKind::BoolLiteral(b) => ExpressionKind::Literal(Literal::Bool(b == "true")),
+			TokenKind::Identifier(_)
+			| TokenKind::Keyword(KeyWord::This)
+			| TokenKind::Keyword(KeyWord::SelfKeyword)
+			| TokenKind::Keyword(KeyWord::Super) => ExpressionKind::Identifier(self.expect_identifier()?),
+			TokenKind::Keyword(KeyWord::New) => return self.parse_new(),
+			TokenKind::Keyword(KeyWord::Function) | TokenKind::Keyword(KeyWord::Fn) => {
+				let function: Function = self.parse_function()?;
+				let loc: Span = function.loc;
+				return Ok(self.expression(ExpressionKind::Function(Box::new(function)), loc));
+			},
+			_ if self.is_delim("(") => {
+				let start: Span = token.span;
+				self.bump();
+				let inner: Expression = self.parse_expression()?;
+				let end: Span = self.expect_delim(")")?;
+				return Ok(self.expression(ExpressionKind::Paren(Box::new(inner)), start.to(end)));
+			},
+			_ if self.is_delim("[") => {
+				let start: Span = token.span;
+				self.bump();
+				let items: Vec<Expression> = self.parse_delimited("]", |p| p.parse_expression())?;
+				return Ok(self.expression(ExpressionKind::Array(items), start.to(self.prev_span())));
+			},
+			_ => return Err(self.unexpected("an expression")),
+		};
+
+		if !matches!(kind, ExpressionKind::Identifier(_)) {
+			self.bump();
+		}
+
+		return Ok(self.expression(kind, token.span.to(self.prev_span())));
+	}
+
+	/// Parses `new Foo(a, b)`, the class can be a field, eg: `new shapes.Point()`, and the arguments can be left out.
+	fn parse_new(&mut self) -> Result<Expression, Diagnostic> {
+		let start: Span = self.peek_span();
+		self.bump();
+
+		let name: Identifier = self.expect_identifier()?;
+		let loc: Span = name.loc;
+		let mut callee: Expression = self.expression(ExpressionKind::Identifier(name), loc);
+		while self.eat(&TokenKind::Accessor) {
+			let name: Identifier = self.expect_identifier()?;
+			let loc: Span = callee.loc.to(name.loc);
+			callee = self.expression(ExpressionKind::Field(Box::new(callee), name), loc);
+		}
+
+		let args: Vec<Expression> = match self.eat_delim("(") {
+			true => self.parse_delimited(")", |p| p.parse_expression())?,
+			false => Vec::new(),
+		};
+		return Ok(self.expression(ExpressionKind::New(Box::new(callee), args), start.to(self.prev_span())));
+	}
+
+	/// Parses the expressions inside of each `${}` of a template string.
+	fn parse_template(&mut self, chunks: &[TemplateChunk], span: Span) -> Vec<TemplatePart> {
+		let mut parts: Vec<TemplatePart> = Vec::new();
+
+		for chunk in chunks {
+			let tokens: &Vec<Token> = match chunk {
+				TemplateChunk::String(s) => {
+					parts.push(TemplatePart::String(s.clone()));
+					continue;
+				},
+				TemplateChunk::Tokens(tokens) => tokens,
+			};
+
+			let mut parser: Parser = Parser::new(tokens);
+			parser.next_id = self.next_id;
+			parser.depth = self.depth;
+			if tokens.is_empty() {
+				parser.eof = span;
+			}
+
+			let expr: Expression = match parser.parse_expression() {
+				Ok(expr) if parser.is_eof() => expr,
+				result => {
+					let err: Diagnostic = result.err().unwrap_or_else(|| parser.unexpected("`}`"));
+					parser.diagnostics.push(err);
+					parser.expression(ExpressionKind::Err, span)
+				},
+			};
+
+			self.next_id = parser.next_id;
+			self.diagnostics.append(&mut parser.diagnostics);
+			self.gated.append(&mut parser.gated);
+			parts.push(TemplatePart::Expression(expr));
+		}
+
+		return parts;
+	}
+
+	/// Parses a comma separated list of items until `close` is found.
+	/// The opening delimiter should already be consumed.
+	fn parse_delimited<T>(
+		&mut self,
+		close: &str,
+		mut item: impl FnMut(&mut Self) -> Result<T, Diagnostic>,
+	) -> Result<Vec<T>, Diagnostic> {
+		let mut items: Vec<T> = Vec::new();
+
+		while !self.is_eof() && !self.is_delim(close) {
+			items.push(item(self)?);
+			if !self.eat(&TokenKind::Comma) {
+				break;
+			}
+		}
+
+		self.expect_delim(close)?;
+		return Ok(items);
+	}
+
+	/// Parses something nested one level deeper, eg: the operand of `-x` or the body of a block.
+	fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T, Diagnostic>) -> Result<T, Diagnostic> {
+		if self.depth >= MAX_DEPTH {
+			return Err(Diagnostic::error(format!("code is nested more than {} levels deep", MAX_DEPTH), self.peek_span())
+				.with_code(codes::NESTED_TOO_DEEPLY)
+				.with_note("move some of it in to a variable or a function"));
+		}
+
+		self.depth += 1;
+		let result: Result<T, Diagnostic> = parse(self);
+		self.depth -= 1;
+		return result;
+	}
+
+	fn expression(&mut self, kind: ExpressionKind, loc: Span) -> Expression {
+		return Expression {
+			id: self.next_id(),
+			kind: kind,
+			loc: loc,
+		};
+	}
+
+	fn next_id(&mut self) -> NodeId {
+		let id: NodeId = NodeId::new(self.next_id);
+		self.next_id += 1;
+		return id;
+	}
+
+	/// Tokens that have no meaning to the parser.
+	/// Unknown chars are skipped too, the lexer has already reported them.
+	fn is_trivia(kind: &TokenKind) -> bool {
+		return matches!(
+			kind,
+			TokenKind::WhiteSpace
+				| TokenKind::Indent
+				| TokenKind::CommentLiteral(_)
+				| TokenKind::Unknown(_)
+				| TokenKind::EOF
+		);
+	}
+
+	fn peek(&self) -> Option<&Token> {
+		return self.tokens.get(self.cursor).map(|t| t.as_ref());
+	}
+
+	fn peek_kind(&self) -> Option<&TokenKind> {
+		return self.nth_kind(0);
+	}
+
+	fn nth_kind(&self, n: usize) -> Option<&TokenKind> {
+		return self.tokens.get(self.cursor + n).map(|t| &t.kind);
+	}
+
+	fn peek_span(&self) -> Span {
+		return self.peek().map(|t| t.span).unwrap_or(self.eof);
+	}
+
+	/// The span of the last consumed token.
+	fn prev_span(&self) -> Span {
+		if self.cursor == 0 {
+			return self.peek_span();
+		}
+
+		return self.tokens[self.cursor - 1].span;
+	}
+
+	fn bump(&mut self) {
+		if self.cursor < self.tokens.len() {
+			self.cursor += 1;
+		}
+	}
+
+	pub fn is_eof(&self) -> bool {
+		return self.cursor >= self.tokens.len();
+	}
+
+	fn eat(&mut self, kind: &TokenKind) -> bool {
+		if self.peek_kind() == Some(kind) {
+			self.bump();
+			return true;
+		}
+
+		return false;
+	}
+
+	fn is_keyword(&self, keyword: KeyWord) -> bool {
+		return self.peek_kind() == Some(&TokenKind::Keyword(keyword));
+	}
+
+	fn eat_keyword(&mut self, keyword: KeyWord) -> bool {
+		return self.eat(&TokenKind::Keyword(keyword));
+	}
+
+	fn is_delim(&self, delim: &str) -> bool {
+		return self.nth_is_delim(0, delim);
+	}
+
+	fn nth_is_delim(&self, n: usize, delim: &str) -> bool {
+		return match self.nth_kind(n) {
+			Some(TokenKind::DelimiterLiteral(Delimiter::Paren(d)))
+			| Some(TokenKind::DelimiterLiteral(Delimiter::Bracket(d)))
+			| Some(TokenKind::DelimiterLiteral(Delimiter::Brace(d))) => d == delim,
+			_ => false,
+		};
+	}
+
+	fn eat_delim(&mut self, delim: &str) -> bool {
+		if self.is_delim(delim) {
+			self.bump();
+			return true;
+		}
+
+		return false;
+	}
+
+	fn expect_delim(&mut self, delim: &str) -> Result<Span, Diagnostic> {
+		if self.eat_delim(delim) {
+			return Ok(self.prev_span());
+		}
+
+		return Err(self.unexpected(&format!("`{}`", delim)));
+	}
+
+	/// Identifiers, as well as `this`, `self` and `super`.
+	fn expect_identifier(&mut self) -> Result<Identifier, Diagnostic> {
+		let name: String = match self.peek_kind() {
+			Some(TokenKind::Identifier(name)) => name.clone(),
+			Some(TokenKind::Keyword(k @ KeyWord::This))
+			| Some(TokenKind::Keyword(k @ KeyWord::SelfKeyword))
+			| Some(TokenKind::Keyword(k @ KeyWord::Super)) => String::from(k.as_str()),
+			_ => return Err(self.unexpected("an identifier")),
+		};
+		self.bump();
+
+		return Ok(Identifier {
+			name: name,
+			loc: self.prev_span(),
+			tokens: None,
+		});
+	}
+
+	fn at_terminator(&self) -> bool {
+		return self.is_eof() || self.is_delim("}") || self.peek_kind() == Some(&TokenKind::ExpressionTerminator);
+	}
+
+	/// Statements end in `;`, which may be left out before a `}`,
+	/// at the end of the file, or after a block.
+	fn expect_terminator(&mut self) -> Result<(), Diagnostic> {
+		if self.eat(&TokenKind::ExpressionTerminator) || self.at_terminator() {
+			return Ok(());
+		}
+
+		if self.cursor > 0 && matches!(
+			&self.tokens[self.cursor - 1].kind,
+			TokenKind::DelimiterLiteral(Delimiter::Brace(d)) if d == "}"
+		) {
+			return Ok(());
+		}
+
+		return Err(self.unexpected("`;`").with_insertion(self.prev_span(), "add a `;` here", ";"));
+	}
+
+	/// Creates a diagnostic for an unexpected token at the cursor.
+	fn unexpected(&self, expected: &str) -> Diagnostic {
+		return Diagnostic::error(
+			format!("expected {}, found {}", expected, describe(self.peek())),
+			self.peek_span(),
+		)
+		.with_code(codes::UNEXPECTED_TOKEN);
+	}
+}
+
+/// Whether an expression can be on the left side of an assignment.
+fn is_assignable(expr: &Expression) -> bool {
+	return match &expr.kind {
+		ExpressionKind::Identifier(_) | ExpressionKind::Field(..) | ExpressionKind::Index(..) => true,
+		ExpressionKind::Paren(inner) => is_assignable(inner),
+		_ => false,
+	};
+}
+
+/// Describes a token in a way that is readable in diagnostics.
+fn describe(token: Option<&Token>) -> String {
+	return match token.map(|t| &t.kind) {
+		None | Some(TokenKind::EOF) => String::from("end of file"),
+		Some(TokenKind::Keyword(k)) => format!("keyword `{}`", k),
+		Some(TokenKind::Identifier(name)) => format!("identifier `{}`", name),
+		Some(TokenKind::NumberLiteral(_)) => String::from("a number"),
+		Some(TokenKind::ExpressionTerminator) => String::from("`;`"),
+		Some(TokenKind::StringLiteral(_)) | Some(TokenKind::TemplateLiteral(_)) => String::from("a string"),
+		Some(TokenKind::DelimiterLiteral(Delimiter::Paren(d)))
+		| Some(TokenKind::DelimiterLiteral(Delimiter::Bracket(d)))
+		| Some(TokenKind::DelimiterLiteral(Delimiter::Brace(d))) => format!("`{}`", d),
+		Some(kind) => format!("`{}`", kind.as_str()),
+	};
+}
+
@@ -0,0 +1,7 @@
+pub mod resolver;
+pub mod scope;
+
+pub use self::{
+	resolver::{resolve, Resolver, PRIMITIVES},
+	scope::{Def, DefKind, Resolutions, Scope, ScopeKind, Use},
+};
@@ -0,0 +1,476 @@
+use super::scope::{ Def, DefKind, Resolutions, Scope, ScopeKind, Use };
+use crate::compiler::typesharp_ast::{ ast::*, node::NodeId, types::{ Type, TypeKinds }, Span };
+use crate::error::{ codes, Diagnostic, DiagnosticSink };
+
+/// Types built in to the language, they are never declared.
+pub const PRIMITIVES: [&str; 18] = [
+	"u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize", "f32", "f64", "bool", "string", "char",
+	"void",
+];
+
+/// Resolves every name used in a module to its declaration.
+pub fn resolve(module: &Module) -> (Resolutions, DiagnosticSink) {
+	let mut resolver: Resolver = Resolver::new();
+	resolver.module(module);
+	return (resolver.resolutions, resolver.diagnostics);
+}
+
+/// What looking up a name found.
+enum Lookup {
+	Found(NodeId),
+	/// A `let` or `const` of an enclosing scope, used before it is declared.
+	Early(Def),
+	Missing,
+}
+
+/// Walks a module with a stack of scopes, binding each name to the node that declares it.
+///
+/// Functions, classes and enums are hoisted to the top of their block, so they can be used anywhere in it.
+/// `let` and `const` can only be used after they are declared, except from inside of a function
+/// declared before them, as the function can only be called once they are.
+#[derive(Default)]
+pub struct Resolver {
+	scopes: Vec<Scope>,
+	pub resolutions: Resolutions,
+	pub diagnostics: DiagnosticSink,
+}
+
+impl Resolver {
+	pub fn new() -> Self {
+		return Resolver::default();
+	}
+
+	pub fn module(&mut self, module: &Module) {
+		let is_package = |s: &Statement| matches!(s.kind, StatementKind::Package(_));
+		self.scopes.push(Scope::new(ScopeKind::Module, None));
+
+		// each package has the statements after it, up to the next package
+		let first: usize = module.statements.iter().position(is_package).unwrap_or(module.statements.len());
+		self.items(&module.statements[..first]);
+		let mut rest: &[Statement] = &module.statements[first..];
+		while let Some((package, statements)) = rest.split_first() {
+			let end: usize = statements.iter().position(is_package).unwrap_or(statements.len());
+			if let StatementKind::Package(path) = &package.kind {
+				self.package(path, &statements[..end]);
+			}
+			rest = &statements[end..];
+		}
+
+		self.scopes.pop();
+	}
+
+	/// Resolves the statements of a package in its own scope.
+	/// A package can be declared more than once, each declaration adds to it and sees what is already in it.
+	fn package(&mut self, path: &[PackageName], statements: &[Statement]) {
+		let mut parent: Option<NodeId> = None;
+		for package in path.iter() {
+			let existing: Option<NodeId> = self
+				.resolutions
+				.defs
+				.values()
+				.find(|d| d.kind == DefKind::Package && d.parent == parent && d.name == package.name.name)
+				.map(|d| d.id);
+			match (existing, parent) {
+				(Some(existing), _) => self.bind(package.id, existing, package.name.loc),
+				// only the outermost package is a name of the module, the others are reached through it
+				(None, None) => self.declare(&package.name, DefKind::Package, package.id, None),
+				(None, Some(_)) => drop(self.resolutions.defs.insert(package.id, def(&package.name, DefKind::Package, package.id, parent))),
+			}
+			parent = Some(existing.unwrap_or(package.id));
+		}
+
+		let mut scope: Scope = Scope::new(ScopeKind::Package, parent);
+		scope.names = self
+			.resolutions
+			.defs
+			.values()
+			.filter(|d| d.parent == parent && d.kind != DefKind::Package)
+			.map(|d| (d.name.clone(), d.id))
+			.collect();
+		self.scopes.push(scope);
+		self.items(statements);
+		self.scopes.pop();
+	}
+
+	fn block(&mut self, block: &Block) {
+		self.scopes.push(Scope::new(ScopeKind::Block, None));
+		self.items(&block.statements);
+		self.scopes.pop();
+	}
+
+	/// Resolves statements in the innermost scope.
+	fn items(&mut self, statements: &[Statement]) {
+		for statement in statements.iter() {
+			match &statement.kind {
+				StatementKind::Function(function) => {
+					if let Some(name) = &function.name {
+						self.declare(name, DefKind::Function, function.id, self.package_scope());
+					}
+				},
+				StatementKind::Class(class) => self.declare(&class.name, DefKind::Class, class.id, self.package_scope()),
+				StatementKind::Enum(item) => self.declare(&item.name, DefKind::Enum, item.id, self.package_scope()),
+				StatementKind::Let(local) | StatementKind::Constant(local) => {
+					let kind: DefKind = if matches!(statement.kind, StatementKind::Let(_)) { DefKind::Variable } else { DefKind::Constant };
+					let def: Def = def(&local.name, kind, local.id, self.package_scope());
+					self.scopes.last_mut().unwrap().pending.push(def);
+				},
+				_ => (),
+			}
+		}
+
+		for statement in statements.iter() {
+			self.statement(statement);
+		}
+	}
+
+	fn statement(&mut self, statement: &Statement) {
+		match &statement.kind {
+			StatementKind::Let(local) => self.local(local, DefKind::Variable),
+			StatementKind::Constant(local) => self.local(local, DefKind::Constant),
+			StatementKind::Function(function) => self.function(function),
+			StatementKind::If(condition, then, otherwise) => {
+				self.expression(condition);
+				self.block(then);
+				if let Some(otherwise) = otherwise {
+					self.statement(otherwise);
+				}
+			},
+			StatementKind::While(condition, body) => {
+				self.expression(condition);
+				self.block(body);
+			},
+			StatementKind::For(for_loop) => {
+				self.expression(&for_loop.iter);
+				self.scopes.push(Scope::new(ScopeKind::Block, None));
+				self.local(&for_loop.binding, DefKind::Variable);
+				self.block(&for_loop.body);
+				self.scopes.pop();
+			},
+			StatementKind::Return(Some(value)) | StatementKind::Expression(value) => self.expression(value),
+			StatementKind::Scope(block) => self.block(block),
+			StatementKind::Class(class) => self.class(class),
+			StatementKind::Enum(item) => self.enumeration(item),
+			StatementKind::TypeDeclaration(typ) => self.typ(typ),
+			StatementKind::Return(None)
+			| StatementKind::Break
+			| StatementKind::Continue
+			| StatementKind::Item
+			| StatementKind::Label
+			| StatementKind::Package(_) => (),
+		}
+	}
+
+	/// Resolves the type and value of a binding, and then declares it.
+	fn local(&mut self, local: &Local, kind: DefKind) {
+		if let Some(typ) = &local.typ {
+			self.typ(typ);
+		}
+		if let Some(value) = &local.value {
+			self.expression(value);
+		}
+
+		self.scopes.last_mut().unwrap().pending.retain(|d| d.id != local.id);
+		self.declare(&local.name, kind, local.id, self.package_scope());
+	}
+
+	fn function(&mut self, function: &Function) {
+		self.generics(&function.generics, function.id);
+		self.scopes.push(Scope::new(ScopeKind::Function, Some(function.id)));
+		for param in function.params.iter() {
+			if let Some(typ) = &param.typ {
+				self.typ(typ);
+			}
+			self.declare(&param.name, DefKind::Parameter, param.id, None);
+		}
+		if let Some(ret) = &function.ret {
+			self.typ(ret);
+		}
+
+		// the body shares the scope of the parameters, so a `let` can not redeclare one
+		self.items(&function.body.statements);
+		self.scopes.pop();
+		self.scopes.pop();
+	}
+
+	/// Opens a scope with the type parameters of a function or class, it is closed along with the function or class.
+	fn generics(&mut self, generics: &[GenericParam], owner: NodeId) {
+		self.scopes.push(Scope::new(ScopeKind::Generics, Some(owner)));
+		for generic in generics.iter() {
+			self.declare(&generic.name, DefKind::TypeParam, generic.id, Some(owner));
+		}
+	}
+
+	fn class(&mut self, class: &Class) {
+		self.generics(&class.generics, class.id);
+		if let Some(extends) = &class.extends {
+			self.typ(extends);
+		}
+
+		self.scopes.push(Scope::new(ScopeKind::Class, Some(class.id)));
+		for member in class.members.iter() {
+			match &member.kind {
+				MemberKind::Field(local) => self.declare(&local.name, DefKind::Field, local.id, Some(class.id)),
+				MemberKind::Method(function) => {
+					if let Some(name) = &function.name {
+						self.declare(name, DefKind::Method, function.id, Some(class.id));
+					}
+				},
+			}
+		}
+
+		for member in class.members.iter() {
+			match &member.kind {
+				MemberKind::Field(local) => {
+					if let Some(typ) = &local.typ {
+						self.typ(typ);
+					}
+					if let Some(value) = &local.value {
+						self.expression(value);
+					}
+				},
+				MemberKind::Method(function) => self.function(function),
+			}
+		}
+		self.scopes.pop();
+		self.scopes.pop();
+	}
+
+	fn enumeration(&mut self, item: &Enum) {
+		self.scopes.push(Scope::new(ScopeKind::Enum, Some(item.id)));
+		for variant in item.variants.iter() {
+			if let Some(value) = &variant.value {
+				self.expression(value);
+			}
+			self.declare(&variant.name, DefKind::Variant, variant.id, Some(item.id));
+		}
+		self.scopes.pop();
+	}
+
+	fn expression(&mut self, expression: &Expression) {
+		match &expression.kind {
+			ExpressionKind::Identifier(name) => self.name(expression.id, name),
+			ExpressionKind::Field(receiver, field) => {
+				self.expression(receiver);
+				self.member(expression.id, receiver, field);
+			},
+			ExpressionKind::Method(receiver, name, args, _) => {
+				self.expression(receiver);
+				self.member(expression.id, receiver, name);
+				args.iter().for_each(|a| self.expression(a));
+			},
+			ExpressionKind::Array(items) => items.iter().for_each(|i| self.expression(i)),
+			ExpressionKind::Function(function) => self.function(function),
+			ExpressionKind::Call(callee, args) | ExpressionKind::New(callee, args) => {
+				self.expression(callee);
+				args.iter().for_each(|a| self.expression(a));
+			},
+			ExpressionKind::Index(a, b)
+			| ExpressionKind::Binary(_, a, b)
+			| ExpressionKind::Assign(_, a, b)
+			| ExpressionKind::Logical(_, a, b)
+			| ExpressionKind::Compare(_, a, b) => {
+				self.expression(a);
+				self.expression(b);
+			},
+			ExpressionKind::Paren(inner) | ExpressionKind::Unary(_, inner) => self.expression(inner),
+			ExpressionKind::Template(parts) => {
+				for part in parts.iter() {
+					if let TemplatePart::Expression(inner) = part {
+						self.expression(inner);
+					}
+				}
+			},
+			ExpressionKind::Scope | ExpressionKind::Literal(_) | ExpressionKind::Err => (),
+		}
+	}
+
+	/// Resolves a name used as a value.
+	fn name(&mut self, id: NodeId, name: &Identifier) {
+		if matches!(name.name.as_str(), "this" | "self" | "super") {
+			match self.class_scope() {
+				// `super` refers to the class being extended, which is only known once types are checked
+				Some(class) if name.name != "super" => self.bind(id, class, name.loc),
+				Some(_) => (),
+				None => {
+					let message: String = format!("`{}` can only be used inside of a class", name.name);
+					self.diagnostics.push(Diagnostic::error(message, name.loc).with_code(codes::UNDEFINED_NAME));
+				},
+			}
+			return;
+		}
+
+		match self.lookup(&name.name) {
+			Lookup::Found(def) => self.bind(id, def, name.loc),
+			Lookup::Early(def) => {
+				self.bind(id, def.id, name.loc);
+				self.diagnostics.push(
+					Diagnostic::error(format!("`{}` is used before it is declared", name.name), name.loc)
+						.with_code(codes::USE_BEFORE_DEFINITION)
+						.with_label(def.span, format!("`{}` is declared here", def.name)),
+				);
+			},
+			Lookup::Missing => {
+				self.diagnostics.push(
+					Diagnostic::error(format!("cannot find `{}` in this scope", name.name), name.loc)
+						.with_code(codes::UNDEFINED_NAME),
+				);
+			},
+		}
+	}
+
+	/// Resolves `Enum.Variant`, `package.Name`, and the members of a class used through `this` or the class itself.
+	fn member(&mut self, id: NodeId, receiver: &Expression, name: &Identifier) {
+		let owner: &Def = match self.resolutions.def_of(receiver.id) {
+			Some(owner) if matches!(owner.kind, DefKind::Enum | DefKind::Class | DefKind::Package) => owner,
+			_ => return,
+		};
+
+		// the type parameters of a class have it as their parent too, but they are not members of it
+		let is_member = |d: &Def| owner.kind == DefKind::Package || matches!(d.kind, DefKind::Field | DefKind::Method | DefKind::Variant);
+		let member: Option<NodeId> = self
+			.resolutions
+			.defs
+			.values()
+			.find(|d| d.parent == Some(owner.id) && d.name == name.name && is_member(d))
+			.map(|d| d.id);
+		match member {
+			Some(member) => self.bind(id, member, name.loc),
+			None if owner.kind == DefKind::Package => {
+				self.diagnostics.push(
+					Diagnostic::error(format!("cannot find `{}` in package `{}`", name.name, owner.name), name.loc)
+						.with_code(codes::UNDEFINED_NAME),
+				);
+			},
+			None if owner.kind == DefKind::Enum => {
+				let (message, span) = (format!("no variant named `{}` in enum `{}`", name.name, owner.name), owner.span);
+				let label: String = format!("`{}` is declared here", owner.name);
+				self.diagnostics.push(
+					Diagnostic::error(message, name.loc)
+						.with_code(codes::UNDEFINED_NAME)
+						.with_label(span, label),
+				);
+			},
+			// the members of the class being extended are only known once types are checked
+			None => (),
+		}
+	}
+
+	/// Resolves the names in a type to the classes, enums and type parameters they refer to.
+	fn typ(&mut self, typ: &Type) {
+		match &typ.kind {
+			TypeKinds::Named(name) => self.type_name(name, typ.loc),
+			TypeKinds::Generic(name, args) => {
+				self.type_name(&name.name, name.loc);
+				args.iter().for_each(|a| self.typ(a));
+			},
+			TypeKinds::Array(inner) => self.typ(inner),
+			TypeKinds::Union(variants) => variants.iter().for_each(|v| self.typ(v)),
+			TypeKinds::Function(params, ret) => {
+				params.iter().for_each(|p| self.typ(p));
+				if let Some(ret) = ret {
+					self.typ(ret);
+				}
+			},
+			TypeKinds::RawPtr | TypeKinds::AClass | TypeKinds::ARef => (),
+		}
+	}
+
+	fn type_name(&mut self, name: &str, span: Span) {
+		if PRIMITIVES.contains(&name) {
+			return;
+		}
+
+		let def: Option<&Def> = match self.lookup(name) {
+			Lookup::Found(def) => self.resolutions.defs.get(&def),
+			Lookup::Early(_) | Lookup::Missing => None,
+		};
+		match def {
+			Some(def) if def.kind.is_type() => self.resolutions.type_uses.push(Use { def: def.id, span: span }),
+			Some(def) => {
+				let (message, declared) = (format!("`{}` is not a type", name), def.span);
+				self.diagnostics.push(
+					Diagnostic::error(message, span)
+						.with_code(codes::UNDEFINED_TYPE)
+						.with_label(declared, format!("`{}` is declared here", name)),
+				);
+			},
+			// `Array<T>` is built in, unless a class of the same name is declared
+			None if name == "Array" => (),
+			None => {
+				self.diagnostics.push(
+					Diagnostic::error(format!("cannot find type `{}` in this scope", name), span).with_code(codes::UNDEFINED_TYPE),
+				);
+			},
+		}
+	}
+
+	/// Adds a declaration to the innermost scope, reporting it if the scope already has one with the same name.
+	fn declare(&mut self, name: &Identifier, kind: DefKind, id: NodeId, parent: Option<NodeId>) {
+		let scope: &mut Scope = self.scopes.last_mut().unwrap();
+		if let Some(previous) = scope.get(&name.name) {
+			let place: &str = match scope.kind {
+				ScopeKind::Class => "class",
+				ScopeKind::Enum => "enum",
+				_ => "scope",
+			};
+			let span: Span = self.resolutions.defs[&previous].span;
+			self.diagnostics.push(
+				Diagnostic::error(format!("`{}` is already declared in this {}", name.name, place), name.loc)
+					.with_code(codes::DUPLICATE_DEFINITION)
+					.with_label(span, format!("`{}` is first declared here", name.name)),
+			);
+		}
+
+		scope.names.push((name.name.clone(), id));
+		self.resolutions.defs.insert(id, def(name, kind, id, parent));
+	}
+
+	fn bind(&mut self, id: NodeId, def: NodeId, span: Span) {
+		self.resolutions.uses.insert(id, Use { def: def, span: span });
+	}
+
+	/// Finds the declaration a name refers to, innermost scope first.
+	/// Members of classes and enums are skipped, they are never used by their name alone.
+	fn lookup(&self, name: &str) -> Lookup {
+		let mut in_function: bool = false;
+		for scope in self.scopes.iter().rev() {
+			if matches!(scope.kind, ScopeKind::Class | ScopeKind::Enum) {
+				continue;
+			}
+
+			if let Some(def) = scope.get(name) {
+				return Lookup::Found(def);
+			}
+			if let Some(def) = scope.pending.iter().find(|d| d.name == name) {
+				return match in_function {
+					true => Lookup::Found(def.id),
+					false => Lookup::Early(def.clone()),
+				};
+			}
+
+			in_function |= scope.kind == ScopeKind::Function;
+		}
+
+		return Lookup::Missing;
+	}
+
+	/// The package being declared in, if the innermost scope is one.
+	fn package_scope(&self) -> Option<NodeId> {
+		return self.scopes.last().filter(|s| s.kind == ScopeKind::Package).and_then(|s| s.owner);
+	}
+
+	/// The class whose members are being resolved, if any.
+	fn class_scope(&self) -> Option<NodeId> {
+		return self.scopes.iter().rev().find(|s| s.kind == ScopeKind::Class).and_then(|s| s.owner);
+	}
+}
+
+fn def(name: &Identifier, kind: DefKind, id: NodeId, parent: Option<NodeId>) -> Def {
+	return Def {
+		id: id,
+		name: name.name.clone(),
+		kind: kind,
+		span: name.loc,
+		parent: parent,
+	};
+}
@@ -0,0 +1,122 @@
+use crate::compiler::typesharp_ast::{ node::NodeId, Span };
+use std::collections::BTreeMap;
+
+/// What a name was declared as.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DefKind {
+	Variable,
+	Constant,
+	Function,
+	Parameter,
+	Class,
+	Field,
+	Method,
+	Enum,
+	Variant,
+	/// A type parameter of a generic function or class, eg: `T` in `class Box<T> {}`
+	TypeParam,
+	/// A package, eg: `shapes` in `package geometry.shapes;`
+	Package,
+}
+
+impl DefKind {
+	/// Whether the name can be used as a type, eg: `let x: Animal`
+	pub fn is_type(&self) -> bool {
+		return matches!(self, DefKind::Class | DefKind::Enum | DefKind::TypeParam);
+	}
+}
+
+/// A declaration, eg: a function or a `let` binding.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Def {
+	/// The id of the node that declares the name, eg: the `Local` of a `let` or the `Function` of a function.
+	pub id: NodeId,
+	pub name: String,
+	pub kind: DefKind,
+	/// The span of the name itself.
+	pub span: Span,
+	/// The class or enum a member belongs to, the function or class a type parameter belongs to,
+	/// or the package a declaration is in.
+	pub parent: Option<NodeId>,
+}
+
+/// A name that was resolved to its declaration.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Use {
+	pub def: NodeId,
+	/// The span of the name, eg: `Red` in `Color.Red`
+	pub span: Span,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ScopeKind {
+	/// The top level of a module, functions, classes and enums declared in it are visible in the whole module.
+	Module,
+	/// The statements after a `package` declaration, what they declare is reached from outside through the package
+	/// eg: `geometry.shapes.Circle`
+	Package,
+	/// A block, eg: the body of an `if` or a loop.
+	Block,
+	/// The parameters and body of a function.
+	Function,
+	/// The members of a class, these are only reached through `this`, never by their name alone.
+	Class,
+	/// The variants of an enum, reached through the enum eg: `Color.Red`
+	Enum,
+	/// The type parameters of a generic function or class.
+	Generics,
+}
+
+/// A lexical scope, the names declared directly in it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Scope {
+	pub kind: ScopeKind,
+	/// The class, enum, function or package that opened the scope.
+	pub owner: Option<NodeId>,
+	pub names: Vec<(String, NodeId)>,
+	/// `let` and `const` bindings of the scope that have not been declared yet, using them is an error.
+	pub pending: Vec<Def>,
+}
+
+impl Scope {
+	pub fn new(kind: ScopeKind, owner: Option<NodeId>) -> Self {
+		return Scope {
+			kind: kind,
+			owner: owner,
+			names: Vec::new(),
+			pending: Vec::new(),
+		};
+	}
+
+	pub fn get(&self, name: &str) -> Option<NodeId> {
+		return self.names.iter().rev().find(|(n, _)| n == name).map(|(_, id)| *id);
+	}
+}
+
+/// What name resolution found in a module.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Resolutions {
+	/// Every declaration, by the id of the node declaring it.
+	pub defs: BTreeMap<NodeId, Def>,
+	/// The id of each expression that names a declaration (eg: `x`, `Color.Red` or `this.x`), and what it names.
+	pub uses: BTreeMap<NodeId, Use>,
+	/// Names written in types, types do not have ids so these are only known by their span.
+	pub type_uses: Vec<Use>,
+}
+
+impl Resolutions {
+	/// Gets the declaration an expression names.
+	pub fn def_of(&self, expression: NodeId) -> Option<&Def> {
+		return self.uses.get(&expression).and_then(|u| self.defs.get(&u.def));
+	}
+
+	/// Gets the declaration a name written in a type refers to, by the span of the name.
+	pub fn type_def(&self, span: Span) -> Option<&Def> {
+		return self.type_uses.iter().find(|u| u.span == span).and_then(|u| self.defs.get(&u.def));
+	}
+
+	/// Every use of a name, both in expressions and in types.
+	pub fn all_uses(&self) -> impl Iterator<Item = &Use> {
+		return self.uses.values().chain(self.type_uses.iter());
+	}
+}
@@ -0,0 +1,232 @@
+use super::error::ErrCompiler;
+use crate::compiler::typesharp_ast::Span;
+use std::fmt;
+
+/// Codes given to each kind of diagnostic, so they can be looked up
+/// (and filtered by tools) without relying on the message.
+pub mod codes {
+	// Lexing
+	pub const INVALID_NUMBER: &str = "E0001";
+	pub const UNTERMINATED_STRING: &str = "E0002";
+	pub const UNKNOWN_ESCAPE: &str = "E0003";
+	pub const INVALID_UNICODE_ESCAPE: &str = "E0004";
+	pub const UNKNOWN_CHARACTER: &str = "E0005";
+	pub const UNTERMINATED_COMMENT: &str = "E0006";
+	pub const UNCLOSED_DELIMITER: &str = "E0007";
+	pub const UNEXPECTED_CLOSING_DELIMITER: &str = "E0008";
+	pub const MISMATCHED_DELIMITER: &str = "E0009";
+
+	// Parsing
+	pub const UNEXPECTED_TOKEN: &str = "E0100";
+	pub const INVALID_ASSIGNMENT: &str = "E0101";
+	pub const NESTED_TOO_DEEPLY: &str = "E0102";
+	pub const UNSUPPORTED_SYNTAX: &str = "E0103";
+
+	// Name resolution
+	pub const UNDEFINED_NAME: &str = "E0200";
+	pub const UNDEFINED_TYPE: &str = "E0201";
+	pub const DUPLICATE_DEFINITION: &str = "E0202";
+	pub const USE_BEFORE_DEFINITION: &str = "E0203";
+
+	// Type checking
+	pub const MISMATCHED_TYPES: &str = "E0300";
+	pub const WRONG_ARGUMENT_COUNT: &str = "E0301";
+	pub const INVALID_OPERANDS: &str = "E0302";
+	pub const NOT_CALLABLE: &str = "E0303";
+	pub const UNKNOWN_MEMBER: &str = "E0304";
+	pub const WRONG_TYPE_ARGUMENT_COUNT: &str = "E0305";
+	pub const LITERAL_OUT_OF_RANGE: &str = "E0306";
+	pub const MISSING_RETURN: &str = "E0307";
+	pub const ASSIGN_TO_CONSTANT: &str = "E0308";
+	pub const NOT_INDEXABLE: &str = "E0309";
+	pub const NOT_ITERABLE: &str = "E0310";
+	pub const CANNOT_INFER: &str = "E0311";
+	pub const CYCLIC_EXTENDS: &str = "E0312";
+
+	// Feature gates
+	pub const UNSTABLE_FEATURE: &str = "E0400";
+	pub const UNKNOWN_FEATURE: &str = "E0401";
+	pub const UNKNOWN_DIRECTIVE: &str = "E0402";
+
+	// Editions
+	pub const UNKNOWN_EDITION: &str = "E0500";
+	pub const MISPLACED_EDITION: &str = "E0501";
+
+	// Lints
+	pub const MIXED_SCRIPT_IDENTIFIER: &str = "W0001";
+	pub const CONFUSABLE_IDENTIFIERS: &str = "W0002";
+	pub const STABLE_FEATURE: &str = "W0003";
+	pub const EDITION_KEYWORD: &str = "W0004";
+}
+
+/// A secondary span of code that relates to a diagnostic.
+/// EG: where a block was opened when the closing brace is missing.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Label {
+	pub span: Span,
+	pub message: String,
+}
+
+/// A fix that can be applied to the source code,
+/// replacing the code at `span` with `replacement`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Suggestion {
+	pub span: Span,
+	pub message: String,
+	pub replacement: String,
+	/// Whether `replacement` is inserted after `span`, rather than replacing it.
+	pub insert: bool,
+}
+
+/// A message produced by the compiler about a specific section of code.
+/// Diagnostics are collected instead of panicking, so that as many
+/// problems as possible can be reported in a single pass.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Diagnostic {
+	pub code: Option<&'static str>,
+	pub severity: ErrCompiler,
+	pub message: String,
+	/// The span the diagnostic is about.
+	pub span: Span,
+	pub labels: Vec<Label>,
+	pub notes: Vec<String>,
+	pub suggestions: Vec<Suggestion>,
+}
+
+impl Diagnostic {
+	pub fn new(severity: ErrCompiler, message: impl Into<String>, span: Span) -> Self {
+		return Diagnostic {
+			code: None,
+			severity: severity,
+			message: message.into(),
+			span: span,
+			labels: Vec::new(),
+			notes: Vec::new(),
+			suggestions: Vec::new(),
+		};
+	}
+
+	/// Creates a general error.
+	pub fn error(message: impl Into<String>, span: Span) -> Self {
+		return Diagnostic::new(ErrCompiler::Error, message, span);
+	}
+
+	pub fn warning(message: impl Into<String>, span: Span) -> Self {
+		return Diagnostic::new(ErrCompiler::Warning, message, span);
+	}
+
+	pub fn with_code(mut self, code: &'static str) -> Self {
+		self.code = Some(code);
+		return self;
+	}
+
+	/// Adds a secondary span to the diagnostic.
+	pub fn with_label(mut self, span: Span, message: impl Into<String>) -> Self {
+		self.labels.push(Label {
+			span: span,
+			message: message.into(),
+		});
+		return self;
+	}
+
+	pub fn with_note(mut self, note: impl Into<String>) -> Self {
+		self.notes.push(note.into());
+		return self;
+	}
+
+	pub fn with_suggestion(mut self, span: Span, message: impl Into<String>, replacement: impl Into<String>) -> Self {
+		self.suggestions.push(Suggestion {
+			span: span,
+			message: message.into(),
+			replacement: replacement.into(),
+			insert: false,
+		});
+		return self;
+	}
+
+	/// Suggests inserting `text` directly after `span`.
+	pub fn with_insertion(mut self, span: Span, message: impl Into<String>, text: impl Into<String>) -> Self {
+		self.suggestions.push(Suggestion {
+			span: span,
+			message: message.into(),
+			replacement: text.into(),
+			insert: true,
+		});
+		return self;
+	}
+
+	pub fn is_error(&self) -> bool {
+		return matches!(self.severity, ErrCompiler::Error | ErrCompiler::Unreachable);
+	}
+}
+
+impl fmt::Display for Diagnostic {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.code {
+			Some(code) => write!(f, "{}[{}]: {}", self.severity.name(), code, self.message)?,
+			None => write!(f, "{}: {}", self.severity.name(), self.message)?,
+		}
+
+		return write!(f, " at {}", self.span.into_position());
+	}
+}
+
+/// Collects diagnostics as they are found by the lexer and parser.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct DiagnosticSink {
+	diagnostics: Vec<Diagnostic>,
+}
+
+impl DiagnosticSink {
+	pub fn new() -> Self {
+		return DiagnosticSink { diagnostics: Vec::new() };
+	}
+
+	pub fn push(&mut self, diagnostic: Diagnostic) {
+		self.diagnostics.push(diagnostic);
+	}
+
+	/// Moves all diagnostics from another sink in to this one.
+	pub fn append(&mut self, other: &mut DiagnosticSink) {
+		self.diagnostics.append(&mut other.diagnostics);
+	}
+
+	pub fn has_errors(&self) -> bool {
+		return self.diagnostics.iter().any(|d| d.is_error());
+	}
+
+	pub fn error_count(&self) -> usize {
+		return self.diagnostics.iter().filter(|d| d.is_error()).count();
+	}
+
+	pub fn len(&self) -> usize {
+		return self.diagnostics.len();
+	}
+
+	pub fn is_empty(&self) -> bool {
+		return self.diagnostics.is_empty();
+	}
+
+	pub fn iter(&self) -> std::slice::Iter<'_, Diagnostic> {
+		return self.diagnostics.iter();
+	}
+
+	pub fn into_vec(self) -> Vec<Diagnostic> {
+		return self.diagnostics;
+	}
+}
+
+impl From<Vec<Diagnostic>> for DiagnosticSink {
+	fn from(diagnostics: Vec<Diagnostic>) -> Self {
+		return DiagnosticSink { diagnostics: diagnostics };
+	}
+}
+
+impl IntoIterator for DiagnosticSink {
+	type Item = Diagnostic;
+	type IntoIter = std::vec::IntoIter<Diagnostic>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		return self.diagnostics.into_iter();
+	}
+}
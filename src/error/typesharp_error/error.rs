@@ -14,6 +14,7 @@ use crate::{
 /// that are related to that feature.
 /// 
 /// An example would be: IO::ErrUnsupported, IO::
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum ErrCompiler {
 	/// An error. (general error)
 	Error,
@@ -36,6 +37,7 @@ pub enum ErrCompiler {
 /// typesharps syntax checking.
 ///
 /// Note: These are not related to IO, or any other feature in the stdlib.
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum ErrFeature {
 	/// All went well, success.
 	Success,
@@ -54,8 +56,21 @@ pub enum ErrFeature {
 }
 
 /// Trait that all compiler-level errors adhere to.
-trait ErrImpl {
-    fn to_str(&self) -> &'static str;
+pub trait ErrImpl {
+	fn to_str(&self) -> &'static str;
+}
+
+impl ErrCompiler {
+	/// The short name shown before a diagnostic, eg: `error: ...`
+	pub fn name(&self) -> &'static str {
+		match *self {
+			ErrCompiler::Error => "error",
+			ErrCompiler::Unreachable => "internal compiler error",
+			ErrCompiler::Warning => "warning",
+			ErrCompiler::Suggestion => "help",
+			ErrCompiler::Note => "note",
+		}
+	}
 }
 
 
@@ -0,0 +1,86 @@
+use super::diagnostic::Diagnostic;
+use crate::compiler::typesharp_ast::{Position, SourceMap, Span};
+use crate::util::Json;
+
+/// Emits diagnostics as JSON, one object per line, for editors and CI tools.
+///
+/// Each object looks like:
+/// ```json
+/// {"code":"E0100","severity":"error","message":"...","file":"main.t#",
+///  "span":{"file":"main.t#","byte_start":9,"byte_end":10,"line_start":1,"column_start":10,"line_end":1,"column_end":11},
+///  "labels":[],"notes":[],"suggestions":[{"message":"...","replacement":";","span":{...}}]}
+/// ```
+/// Lines and columns start at 1, `byte_end` and `column_end` are exclusive.
+/// Suggestions that insert code have an empty span, directly after the span they follow.
+pub struct JsonEmitter<'a> {
+	files: &'a SourceMap,
+}
+
+impl<'a> JsonEmitter<'a> {
+	pub fn new(files: &'a SourceMap) -> Self {
+		return JsonEmitter { files: files };
+	}
+
+	/// Emits every diagnostic, each on its own line.
+	pub fn emit_all<'d>(&self, diagnostics: impl IntoIterator<Item = &'d Diagnostic>) -> String {
+		return diagnostics.into_iter().map(|d| self.emit(d) + "\n").collect();
+	}
+
+	/// Emits a single diagnostic as a JSON object, without a trailing newline.
+	pub fn emit(&self, diagnostic: &Diagnostic) -> String {
+		return self.to_json(diagnostic).to_string();
+	}
+
+	/// The diagnostic as a JSON object, strings are escaped by `util::Json`.
+	pub fn to_json(&self, diagnostic: &Diagnostic) -> Json {
+		let labels: Vec<Json> = diagnostic
+			.labels
+			.iter()
+			.map(|l| Json::object(vec![("message", Json::from(l.message.as_str())), ("span", self.span(l.span))]))
+			.collect();
+		let suggestions: Vec<Json> = diagnostic
+			.suggestions
+			.iter()
+			.map(|s| {
+				Json::object(vec![
+					("message", Json::from(s.message.as_str())),
+					("replacement", Json::from(s.replacement.as_str())),
+					("span", match s.insert {
+						true => self.span(s.span.shrink_to_end()),
+						false => self.span(s.span),
+					}),
+				])
+			})
+			.collect();
+
+		return Json::object(vec![
+			("code", Json::from(diagnostic.code)),
+			("severity", Json::from(diagnostic.severity.name())),
+			("message", Json::from(diagnostic.message.as_str())),
+			("file", Json::from(self.file(diagnostic.span))),
+			("span", self.span(diagnostic.span)),
+			("labels", Json::Array(labels)),
+			("notes", Json::from(diagnostic.notes.clone())),
+			("suggestions", Json::Array(suggestions)),
+		]);
+	}
+
+	fn file(&self, span: Span) -> &str {
+		return self.files.get(span.file()).map_or("", |f| f.name());
+	}
+
+	fn span(&self, span: Span) -> Json {
+		let start: Position = span.start();
+		let end: Position = span.end();
+
+		return Json::object(vec![
+			("file", Json::from(self.file(span))),
+			("byte_start", Json::from(start.offset())),
+			("byte_end", Json::from(end.offset())),
+			("line_start", Json::from(start.line())),
+			("column_start", Json::from(start.column())),
+			("line_end", Json::from(end.line())),
+			("column_end", Json::from(end.column())),
+		]);
+	}
+}
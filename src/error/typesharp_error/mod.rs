@@ -1,3 +1,9 @@
+pub mod diagnostic;
+pub mod error;
+pub mod json;
+pub mod render;
+
+pub use self::{ diagnostic::*, error::*, json::JsonEmitter, render::* };
 use crate::compiler::{ typesharp_ast::ast, typesharp_ast::Position, typesharp_ast::Span };
 
 // pub struct Error {
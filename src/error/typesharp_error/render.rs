@@ -0,0 +1,216 @@
+use super::diagnostic::Diagnostic;
+use super::error::ErrCompiler;
+use crate::compiler::typesharp_ast::{FileId, Position, SourceFile, SourceMap, Span};
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const BLUE: &str = "\x1b[1;34m";
+
+/// A marker drawn under a line of source code.
+struct Marker<'a> {
+	file: FileId,
+	line: u32,
+	/// First and last column of the marker, both inclusive.
+	start: u32,
+	end: u32,
+	primary: bool,
+	message: &'a str,
+}
+
+/// Renders diagnostics for the terminal, in the style of:
+/// ```text
+/// error[E0100]: expected `;`, found keyword `let`
+///  --> main.t#:1:10
+///   |
+/// 1 | let a = 1
+///   |          ^
+/// ```
+pub struct Renderer<'a> {
+	files: &'a SourceMap,
+	color: bool,
+}
+
+impl<'a> Renderer<'a> {
+	/// Creates a renderer for diagnostics in any file of `files`.
+	pub fn new(files: &'a SourceMap) -> Self {
+		return Renderer {
+			files: files,
+			color: false,
+		};
+	}
+
+	/// Whether ANSI colors should be used.
+	pub fn colored(mut self, color: bool) -> Self {
+		self.color = color;
+		return self;
+	}
+
+	pub fn render_all<'d>(&self, diagnostics: impl IntoIterator<Item = &'d Diagnostic>) -> String {
+		return diagnostics.into_iter().map(|d| self.render(d)).collect::<Vec<String>>().join("\n");
+	}
+
+	pub fn render(&self, diagnostic: &Diagnostic) -> String {
+		let mut out: String = String::new();
+		let severity: String = match diagnostic.code {
+			Some(code) => format!("{}[{}]", diagnostic.severity.name(), code),
+			None => diagnostic.severity.name().to_string(),
+		};
+
+		out.push_str(&self.paint(&severity, severity_color(diagnostic.severity)));
+		out.push_str(&self.paint(&format!(": {}", diagnostic.message), BOLD));
+		out.push('\n');
+
+		let mut markers: Vec<Marker> = Vec::new();
+		self.push_markers(&mut markers, diagnostic.span, true, "");
+		for label in diagnostic.labels.iter() {
+			self.push_markers(&mut markers, label.span, false, &label.message);
+		}
+
+		// the primary file is shown first, then any other file a label points to
+		let primary: FileId = diagnostic.span.file();
+		let mut lines: Vec<(bool, FileId, u32)> = markers.iter().map(|m| (m.file != primary, m.file, m.line)).collect();
+		lines.sort_unstable();
+		lines.dedup();
+
+		let width: usize = lines.iter().map(|l| l.2).max().map_or(1, |l| l.to_string().len());
+		let gutter: String = " ".repeat(width);
+
+		out.push_str(&format!(
+			"{}{} {}\n",
+			gutter,
+			self.paint("-->", BLUE),
+			self.files.location(diagnostic.span)
+		));
+		out.push_str(&format!("{} {}\n", gutter, self.paint("|", BLUE)));
+
+		let mut previous: Option<(FileId, u32)> = None;
+		for (_, file, line) in lines {
+			match previous {
+				Some((previous, _)) if previous != file => {
+					let name: &str = self.files.get(file).map_or("", |f| f.name());
+					out.push_str(&format!("{}{} {}\n", gutter, self.paint(":::", BLUE), name));
+					out.push_str(&format!("{} {}\n", gutter, self.paint("|", BLUE)));
+				},
+				Some((_, previous)) if line > previous + 1 => out.push_str(&self.paint("...\n", BLUE)),
+				_ => {},
+			}
+			previous = Some((file, line));
+
+			let text: &str = self.line(file, line);
+			out.push_str(&format!(
+				"{} {} {}\n",
+				self.paint(&format!("{:>width$}", line, width = width), BLUE),
+				self.paint("|", BLUE),
+				text
+			));
+
+			for marker in markers.iter().filter(|m| m.file == file && m.line == line) {
+				let (symbol, color) = match marker.primary {
+					true => ("^", severity_color(diagnostic.severity)),
+					false => ("-", BLUE),
+				};
+				let underline: String = symbol.repeat((marker.end + 1).saturating_sub(marker.start).max(1) as usize);
+				let mut row: String = format!("{}{}", indent(text, marker.start), underline);
+				if !marker.message.is_empty() {
+					row.push(' ');
+					row.push_str(marker.message);
+				}
+
+				out.push_str(&format!("{} {} {}\n", gutter, self.paint("|", BLUE), self.paint(&row, color)));
+			}
+		}
+
+		for note in diagnostic.notes.iter() {
+			out.push_str(&format!("{} {} {}: {}\n", gutter, self.paint("=", BLUE), self.paint("note", BOLD), note));
+		}
+
+		for suggestion in diagnostic.suggestions.iter() {
+			out.push_str(&format!(
+				"{} {} {}: {}: `{}`\n",
+				gutter,
+				self.paint("=", BLUE),
+				self.paint("help", BOLD),
+				suggestion.message,
+				suggestion.replacement
+			));
+		}
+
+		return out;
+	}
+
+	/// Adds markers for a span, a span over multiple lines is marked
+	/// from its start to the end of the first line, and from the start
+	/// of the last line to its end.
+	fn push_markers<'m>(&self, markers: &mut Vec<Marker<'m>>, span: Span, primary: bool, message: &'m str) {
+		let file: FileId = span.file();
+		let start: Position = span.start();
+		// the last char in the span, markers include both ends
+		let end: Position = match self.files.get(file) {
+			Some(source) if !span.is_empty() => source.position_at(span.end().offset() - 1),
+			_ => start,
+		};
+
+		if start.line() == end.line() {
+			markers.push(Marker {
+				file: file,
+				line: start.line(),
+				start: start.column(),
+				end: end.column(),
+				primary: primary,
+				message: message,
+			});
+			return;
+		}
+
+		markers.push(Marker {
+			file: file,
+			line: start.line(),
+			start: start.column(),
+			end: self.line(file, start.line()).chars().count() as u32,
+			primary: primary,
+			message: "",
+		});
+		markers.push(Marker {
+			file: file,
+			line: end.line(),
+			start: 1,
+			end: end.column(),
+			primary: primary,
+			message: message,
+		});
+	}
+
+	/// Gets a line of source by its line number, starting at 1.
+	fn line(&self, file: FileId, line: u32) -> &'a str {
+		return self.files.get(file).and_then(|f| f.line(line)).unwrap_or("");
+	}
+
+	fn paint(&self, text: &str, color: &str) -> String {
+		if !self.color {
+			return text.to_string();
+		}
+
+		return format!("{}{}{}", color, text, RESET);
+	}
+}
+
+fn severity_color(severity: ErrCompiler) -> &'static str {
+	return match severity {
+		ErrCompiler::Error | ErrCompiler::Unreachable => "\x1b[1;31m",
+		ErrCompiler::Warning => "\x1b[1;33m",
+		ErrCompiler::Note => "\x1b[1;32m",
+		ErrCompiler::Suggestion => "\x1b[1;36m",
+	};
+}
+
+/// The whitespace needed to reach a column (starting at 1) of a line,
+/// tabs are kept so that markers line up with the source.
+fn indent(text: &str, column: u32) -> String {
+	let mut chars = text.chars();
+	return (1..column)
+		.map(|_| match chars.next() {
+			Some('\t') => '\t',
+			_ => ' ',
+		})
+		.collect();
+}
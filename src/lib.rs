@@ -1,4 +1,8 @@
 #![allow(unused_imports, non_camel_case_types, dead_code)]
+#![allow(clippy::needless_return, clippy::redundant_field_names, clippy::result_large_err)]
+pub mod cli;
 pub mod compiler;
 pub mod error;
+pub mod lsp;
+pub mod util;
 //pub mod parser_h::{ parser, compile };
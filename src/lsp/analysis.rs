@@ -0,0 +1,387 @@
+use crate::compiler::{
+	typesharp_ast::{ ast::*, node::NodeId, types::{ Type, TypeKinds }, Edition, Span },
+	typesharp_check::TypeTable,
+	typesharp_cst::Document,
+	typesharp_driver::compile_front_end,
+	typesharp_feature::Features,
+	typesharp_lexer::edition_directive,
+	typesharp_resolve::Resolutions,
+};
+use crate::error::Diagnostic;
+use std::collections::BTreeMap;
+
+/// What a name was declared as.
+pub use crate::compiler::typesharp_resolve::DefKind as DeclKind;
+
+/// A name declared in a document, eg: a function or a `let` binding.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Decl {
+	pub name: String,
+	pub kind: DeclKind,
+	/// The span of the name itself.
+	pub span: Span,
+	/// The span of the whole declaration, eg: the whole function.
+	pub range: Span,
+	/// The type of the declaration if it is known, either written or inferred.
+	pub typ: Option<String>,
+	/// How the declaration is shown when hovered, eg: `let x: i32`
+	pub detail: String,
+	/// The class or enum a member belongs to, or the function a local is declared in.
+	pub parent: Option<usize>,
+}
+
+/// Everything the language server knows about a single document.
+/// Names are bound to their declarations by the compiler's resolver, see `typesharp_resolve`.
+pub struct Analysis {
+	/// Problems found while lexing, parsing, resolving and type checking the document.
+	pub diagnostics: Vec<Diagnostic>,
+	pub decls: Vec<Decl>,
+	/// Each use of a name, and the index of the declaration it refers to.
+	pub refs: Vec<(Span, usize)>,
+	resolutions: Resolutions,
+	/// The types the checker gave each declaration, written or inferred.
+	types: TypeTable,
+	/// The index of each declaration, by the id of the node declaring it.
+	ids: BTreeMap<NodeId, usize>,
+	function: Option<usize>,
+}
+
+impl Analysis {
+	pub fn new(document: &Document) -> Self {
+		// documents are not read from a package, so only an `@edition` directive changes the edition
+		let (edition, unknown) = edition_directive(document.tokens());
+		let edition: Edition = edition.unwrap_or_default();
+		let (module, resolutions, types, found) = compile_front_end(document.tokens().to_vec(), edition, &Features::new());
+
+		let mut diagnostics: Vec<Diagnostic> = document.diagnostics().iter().cloned().collect();
+		diagnostics.extend(unknown.into_vec());
+		diagnostics.extend(found.into_vec());
+
+		let mut analysis: Analysis = Analysis {
+			diagnostics: diagnostics,
+			decls: Vec::new(),
+			refs: Vec::new(),
+			resolutions: resolutions,
+			types: types,
+			ids: BTreeMap::new(),
+			function: None,
+		};
+
+		analysis.statements(&module.statements);
+		analysis.refs = analysis
+			.resolutions
+			.all_uses()
+			.filter_map(|u| analysis.ids.get(&u.def).map(|decl| (u.span, *decl)))
+			.collect();
+		return analysis;
+	}
+
+	/// Gets the declaration at a byte offset, either where it is declared or used.
+	pub fn decl_at(&self, offset: usize) -> Option<&Decl> {
+		let touches = |span: &Span| span.start().offset() <= offset && offset <= span.end().offset();
+
+		if let Some(decl) = self.decls.iter().find(|d| touches(&d.span)) {
+			return Some(decl);
+		}
+
+		return self.refs.iter().find(|(span, _)| touches(span)).map(|(_, decl)| &self.decls[*decl]);
+	}
+
+	/// Gets the declaration a name refers to, if the span is a declaration or a use of one.
+	pub fn decl_of(&self, span: Span) -> Option<(&Decl, bool)> {
+		if let Some(decl) = self.decls.iter().find(|d| d.span == span) {
+			return Some((decl, true));
+		}
+
+		return self.refs.iter().find(|(s, _)| *s == span).map(|(_, decl)| (&self.decls[*decl], false));
+	}
+
+	/// Collects the declarations of a block, in the order they can be used.
+	fn statements(&mut self, statements: &[Statement]) {
+		// functions, classes and enums can be used before they are declared
+		let mut hoisted: Vec<Option<usize>> = Vec::new();
+		for statement in statements.iter() {
+			hoisted.push(self.hoist(statement));
+		}
+
+		for (statement, decl) in statements.iter().zip(hoisted) {
+			self.statement(statement, decl);
+		}
+	}
+
+	fn hoist(&mut self, statement: &Statement) -> Option<usize> {
+		return match &statement.kind {
+			StatementKind::Function(function) => {
+				let name: &Identifier = function.name.as_ref()?;
+				let decl: Decl = self.decl(name, DeclKind::Function, statement.loc, function_type(function), function_detail("function", function));
+				Some(self.declare(function.id, decl))
+			},
+			StatementKind::Class(class) => {
+				let detail: String = match &class.extends {
+					Some(extends) => format!("class {}{} extends {}", class.name.name, generics(&class.generics), type_name(extends)),
+					None => format!("class {}{}", class.name.name, generics(&class.generics)),
+				};
+				let decl: Decl = self.decl(&class.name, DeclKind::Class, class.loc, Some(class.name.name.clone()), detail);
+				Some(self.declare(class.id, decl))
+			},
+			StatementKind::Enum(item) => {
+				let detail: String = format!("enum {}", item.name.name);
+				let decl: Decl = self.decl(&item.name, DeclKind::Enum, item.loc, Some(item.name.name.clone()), detail);
+				Some(self.declare(item.id, decl))
+			},
+			StatementKind::Package(path) => {
+				// a package declared again is a use of the first declaration, not a declaration of its own
+				for (i, package) in path.iter().enumerate() {
+					if self.resolutions.defs.contains_key(&package.id) {
+						let names: Vec<&str> = path[..=i].iter().map(|p| p.name.name.as_str()).collect();
+						let decl: Decl = self.decl(&package.name, DeclKind::Package, statement.loc, None, format!("package {}", names.join(".")));
+						self.declare(package.id, decl);
+					}
+				}
+				None
+			},
+			_ => None,
+		};
+	}
+
+	fn statement(&mut self, statement: &Statement, hoisted: Option<usize>) {
+		match &statement.kind {
+			StatementKind::Let(local) => self.local(local, DeclKind::Variable, statement.loc),
+			StatementKind::Constant(local) => self.local(local, DeclKind::Constant, statement.loc),
+			StatementKind::Function(function) => self.function(function, hoisted),
+			StatementKind::If(condition, then, otherwise) => {
+				self.expression(condition);
+				self.statements(&then.statements);
+				if let Some(otherwise) = otherwise {
+					self.statement(otherwise, None);
+				}
+			},
+			StatementKind::While(condition, body) => {
+				self.expression(condition);
+				self.statements(&body.statements);
+			},
+			StatementKind::For(for_loop) => {
+				self.expression(&for_loop.iter);
+				self.local(&for_loop.binding, DeclKind::Variable, for_loop.binding.name.loc);
+				self.statements(&for_loop.body.statements);
+			},
+			StatementKind::Return(Some(value)) | StatementKind::Expression(value) => self.expression(value),
+			StatementKind::Scope(block) => self.statements(&block.statements),
+			StatementKind::Class(class) => self.class(class, hoisted),
+			StatementKind::Enum(item) => self.enumeration(item, hoisted),
+			_ => (),
+		}
+	}
+
+	fn local(&mut self, local: &Local, kind: DeclKind, range: Span) {
+		if let Some(value) = &local.value {
+			self.expression(value);
+		}
+
+		let typ: Option<String> = match &local.typ {
+			Some(typ) => Some(type_name(typ)),
+			None => self.inferred(local.id),
+		};
+		let keyword: &str = match kind {
+			DeclKind::Constant => "const ",
+			DeclKind::Variable => "let ",
+			_ => "",
+		};
+		let detail: String = match &typ {
+			Some(typ) => format!("{}{}: {}", keyword, local.name.name, typ),
+			None => format!("{}{}", keyword, local.name.name),
+		};
+
+		let decl: Decl = self.decl(&local.name, kind, range, typ, detail);
+		self.declare(local.id, decl);
+	}
+
+	/// Collects the parameters and body of a function, `decl` is the function's own declaration.
+	fn function(&mut self, function: &Function, decl: Option<usize>) {
+		let outer: Option<usize> = self.function;
+		if decl.is_some() {
+			self.function = decl;
+		}
+
+		self.generics(&function.generics, self.function);
+		for param in function.params.iter() {
+			let typ: Option<String> = match &param.typ {
+				Some(typ) => Some(type_name(typ)),
+				None => self.inferred(param.id),
+			};
+			let detail: String = match &typ {
+				Some(typ) => format!("{}: {}", param.name.name, typ),
+				None => param.name.name.clone(),
+			};
+			let decl: Decl = self.decl(&param.name, DeclKind::Parameter, param.loc, typ, detail);
+			self.declare(param.id, decl);
+		}
+
+		self.statements(&function.body.statements);
+		self.function = outer;
+	}
+
+	fn class(&mut self, class: &Class, decl: Option<usize>) {
+		self.generics(&class.generics, decl);
+		for member in class.members.iter() {
+			match &member.kind {
+				MemberKind::Field(local) => {
+					if let Some(value) = &local.value {
+						self.expression(value);
+					}
+
+					let typ: Option<String> = match &local.typ {
+						Some(typ) => Some(type_name(typ)),
+						None => self.inferred(local.id),
+					};
+					let detail: String = match &typ {
+						Some(typ) => format!("{}: {}", local.name.name, typ),
+						None => local.name.name.clone(),
+					};
+					let mut field: Decl = self.decl(&local.name, DeclKind::Field, member.loc, typ, detail);
+					field.parent = decl;
+					self.declare(local.id, field);
+				},
+				MemberKind::Method(function) => {
+					let method: Option<usize> = function.name.as_ref().map(|name| {
+						let mut method: Decl = self.decl(name, DeclKind::Method, member.loc, function_type(function), function_detail("function", function));
+						method.parent = decl;
+						self.declare(function.id, method)
+					});
+					self.function(function, method);
+				},
+			}
+		}
+	}
+
+	fn generics(&mut self, generics: &[GenericParam], parent: Option<usize>) {
+		for generic in generics.iter() {
+			let mut decl: Decl = self.decl(&generic.name, DeclKind::TypeParam, generic.name.loc, None, generic.name.name.clone());
+			decl.parent = parent;
+			self.declare(generic.id, decl);
+		}
+	}
+
+	fn enumeration(&mut self, item: &Enum, decl: Option<usize>) {
+		for variant in item.variants.iter() {
+			if let Some(value) = &variant.value {
+				self.expression(value);
+			}
+
+			let detail: String = format!("{}.{}", item.name.name, variant.name.name);
+			let mut member: Decl = self.decl(&variant.name, DeclKind::Variant, variant.loc, Some(item.name.name.clone()), detail);
+			member.parent = decl;
+			self.declare(variant.id, member);
+		}
+	}
+
+	fn expression(&mut self, expression: &Expression) {
+		match &expression.kind {
+			ExpressionKind::Field(receiver, _) => self.expression(receiver),
+			ExpressionKind::Array(items) => items.iter().for_each(|i| self.expression(i)),
+			ExpressionKind::Function(function) => self.function(function, None),
+			ExpressionKind::Call(callee, args) | ExpressionKind::New(callee, args) => {
+				self.expression(callee);
+				args.iter().for_each(|a| self.expression(a));
+			},
+			ExpressionKind::Method(receiver, _, args, _) => {
+				self.expression(receiver);
+				args.iter().for_each(|a| self.expression(a));
+			},
+			ExpressionKind::Index(a, b)
+			| ExpressionKind::Binary(_, a, b)
+			| ExpressionKind::Assign(_, a, b)
+			| ExpressionKind::Logical(_, a, b)
+			| ExpressionKind::Compare(_, a, b) => {
+				self.expression(a);
+				self.expression(b);
+			},
+			ExpressionKind::Paren(inner) | ExpressionKind::Unary(_, inner) => self.expression(inner),
+			ExpressionKind::Template(parts) => {
+				for part in parts.iter() {
+					if let TemplatePart::Expression(inner) = part {
+						self.expression(inner);
+					}
+				}
+			},
+			ExpressionKind::Identifier(_) | ExpressionKind::Scope | ExpressionKind::Literal(_) | ExpressionKind::Err => (),
+		}
+	}
+
+	/// The type the checker inferred for a declaration without one written.
+	fn inferred(&self, id: NodeId) -> Option<String> {
+		return self.types.defs.get(&id).filter(|t| !t.is_unknown()).map(|t| t.to_string());
+	}
+
+	fn decl(&self, name: &Identifier, kind: DeclKind, range: Span, typ: Option<String>, detail: String) -> Decl {
+		return Decl {
+			name: name.name.clone(),
+			kind: kind,
+			span: name.loc,
+			range: range,
+			typ: typ,
+			detail: detail,
+			parent: self.function,
+		};
+	}
+
+	/// Adds a declaration, `id` is the node that declares it.
+	fn declare(&mut self, id: NodeId, decl: Decl) -> usize {
+		let index: usize = self.decls.len();
+		self.ids.insert(id, index);
+		self.decls.push(decl);
+		return index;
+	}
+}
+
+/// The type of a function, eg: `(a: u8, b): u8`
+fn function_type(function: &Function) -> Option<String> {
+	let params: Vec<String> = function
+		.params
+		.iter()
+		.map(|p| match &p.typ {
+			Some(typ) => format!("{}: {}", p.name.name, type_name(typ)),
+			None => p.name.name.clone(),
+		})
+		.collect();
+
+	return Some(match &function.ret {
+		Some(ret) => format!("({}): {}", params.join(", "), type_name(ret)),
+		None => format!("({})", params.join(", ")),
+	});
+}
+
+fn function_detail(keyword: &str, function: &Function) -> String {
+	let name: &str = function.name.as_ref().map_or("", |n| n.name.as_str());
+	return format!("{} {}{}{}", keyword, name, generics(&function.generics), function_type(function).unwrap_or_default());
+}
+
+/// The type parameters of a function or class as they are written, eg: `<T, U>`
+fn generics(generics: &[GenericParam]) -> String {
+	if generics.is_empty() {
+		return String::new();
+	}
+
+	return format!("<{}>", generics.iter().map(|g| g.name.name.as_str()).collect::<Vec<&str>>().join(", "));
+}
+
+/// Writes a type as it would be written in code.
+pub fn type_name(typ: &Type) -> String {
+	return match &typ.kind {
+		TypeKinds::Named(name) => name.clone(),
+		TypeKinds::Array(inner) if matches!(inner.kind, TypeKinds::Union(_) | TypeKinds::Function(..)) => format!("({})[]", type_name(inner)),
+		TypeKinds::Array(inner) => format!("{}[]", type_name(inner)),
+		TypeKinds::Generic(name, args) => format!("{}<{}>", name.name, args.iter().map(type_name).collect::<Vec<String>>().join(", ")),
+		TypeKinds::Union(variants) => variants.iter().map(type_name).collect::<Vec<String>>().join(" | "),
+		TypeKinds::Function(params, ret) => {
+			let params: String = params.iter().map(type_name).collect::<Vec<String>>().join(", ");
+			match ret {
+				Some(ret) => format!("fn({}): {}", params, type_name(ret)),
+				None => format!("fn({})", params),
+			}
+		},
+		TypeKinds::RawPtr => String::from("ptr"),
+		TypeKinds::AClass => String::from("class"),
+		TypeKinds::ARef => String::from("ref"),
+	};
+}
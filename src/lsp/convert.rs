@@ -0,0 +1,73 @@
+use crate::compiler::typesharp_ast::Span;
+use crate::util::Json;
+
+/// Converts between byte offsets, which the compiler uses,
+/// and LSP positions, which are a 0-based line and a UTF-16 character.
+pub struct LineIndex<'a> {
+	source: &'a str,
+	line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+	pub fn new(source: &'a str) -> Self {
+		let mut line_starts: Vec<usize> = vec![0];
+		line_starts.extend(source.match_indices('\n').map(|(i, _)| i + 1));
+
+		return LineIndex {
+			source: source,
+			line_starts: line_starts,
+		};
+	}
+
+	/// The line and UTF-16 character of a byte offset.
+	pub fn position(&self, offset: usize) -> (u32, u32) {
+		let offset: usize = offset.min(self.source.len());
+		let line: usize = match self.line_starts.binary_search(&offset) {
+			Ok(line) => line,
+			Err(next) => next - 1,
+		};
+
+		let start: usize = self.line_starts[line];
+		let character: usize = self.source.get(start..offset).map_or(0, |s| s.encode_utf16().count());
+		return (line as u32, character as u32);
+	}
+
+	/// The byte offset of a line and UTF-16 character.
+	/// A character past the end of the line is clamped to the end of the line, before its `\n` or `\r\n`.
+	pub fn offset(&self, line: u32, character: u32) -> Option<usize> {
+		let start: usize = *self.line_starts.get(line as usize)?;
+		let mut end: usize = self.line_starts.get(line as usize + 1).map_or(self.source.len(), |next| next - 1);
+		if end > start && self.source.as_bytes()[end - 1] == b'\r' {
+			end -= 1;
+		}
+		let text: &str = &self.source[start..end];
+
+		let mut units: u32 = 0;
+		for (i, c) in text.char_indices() {
+			if units >= character {
+				return Some(start + i);
+			}
+			units += c.len_utf16() as u32;
+		}
+
+		return Some(end);
+	}
+
+	/// Gets the byte offset of an LSP `Position` object.
+	pub fn offset_of(&self, position: &Json) -> Option<usize> {
+		let line: u64 = position.get("line")?.as_u64()?;
+		let character: u64 = position.get("character")?.as_u64()?;
+		return self.offset(line as u32, character as u32);
+	}
+
+	pub fn to_position(&self, offset: usize) -> Json {
+		let (line, character) = self.position(offset);
+		return Json::object(vec![("line", Json::from(line)), ("character", Json::from(character))]);
+	}
+
+	/// Converts a span to an LSP `Range` object.
+	pub fn to_range(&self, span: Span) -> Json {
+		let range = span.byte_range();
+		return Json::object(vec![("start", self.to_position(range.start)), ("end", self.to_position(range.end))]);
+	}
+}
@@ -0,0 +1,7 @@
+pub mod analysis;
+pub mod convert;
+pub mod rpc;
+pub mod semantic;
+pub mod server;
+
+pub use self::{ analysis::{ Analysis, Decl, DeclKind }, convert::LineIndex, server::{ run, Server } };
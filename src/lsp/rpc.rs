@@ -0,0 +1,70 @@
+use crate::util::Json;
+use std::io::{self, BufRead, Write};
+
+/// Reads a single JSON-RPC message, framed with a `Content-Length` header.
+/// Returns `None` once the input has ended.
+pub fn read_message(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+	let mut length: Option<usize> = None;
+
+	loop {
+		let mut line: String = String::new();
+		if reader.read_line(&mut line)? == 0 {
+			return Ok(None);
+		}
+
+		let line: &str = line.trim_end();
+		if line.is_empty() {
+			break;
+		}
+
+		// other headers (eg: `Content-Type`) are allowed, but not needed
+		if let Some((name, value)) = line.split_once(':') {
+			if name.eq_ignore_ascii_case("Content-Length") {
+				length = value.trim().parse::<usize>().ok();
+			}
+		}
+	}
+
+	let length: usize = match length {
+		Some(length) => length,
+		None => return Err(io::Error::new(io::ErrorKind::InvalidData, "message is missing a Content-Length header")),
+	};
+
+	let mut body: Vec<u8> = vec![0; length];
+	reader.read_exact(&mut body)?;
+
+	return String::from_utf8(body)
+		.map(Some)
+		.map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "message is not valid UTF-8"));
+}
+
+/// Writes a JSON-RPC message with its `Content-Length` header.
+pub fn write_message(writer: &mut impl Write, message: &Json) -> io::Result<()> {
+	let body: String = message.to_string();
+	write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+	return writer.flush();
+}
+
+/// A response to a request that succeeded.
+pub fn response(id: Json, result: Json) -> Json {
+	return Json::object(vec![("jsonrpc", Json::from("2.0")), ("id", id), ("result", result)]);
+}
+
+/// A response to a request that failed, `code` is one of the JSON-RPC error codes.
+pub fn error_response(id: Json, code: i64, message: &str) -> Json {
+	let error: Json = Json::object(vec![("code", Json::from(code)), ("message", Json::from(message))]);
+	return Json::object(vec![("jsonrpc", Json::from("2.0")), ("id", id), ("error", error)]);
+}
+
+/// A message sent by the server that does not expect a response.
+pub fn notification(method: &str, params: Json) -> Json {
+	return Json::object(vec![("jsonrpc", Json::from("2.0")), ("method", Json::from(method)), ("params", params)]);
+}
+
+/// Error codes defined by JSON-RPC and LSP.
+pub mod codes {
+	pub const PARSE_ERROR: i64 = -32700;
+	pub const INVALID_REQUEST: i64 = -32600;
+	pub const METHOD_NOT_FOUND: i64 = -32601;
+	pub const INVALID_PARAMS: i64 = -32602;
+}
@@ -0,0 +1,149 @@
+use super::analysis::{ Analysis, DeclKind };
+use super::convert::LineIndex;
+use crate::compiler::typesharp_lexer::{ token::TemplateChunk, Token, TokenKind };
+
+/// The token types sent to the editor, a token's type is its index in this list.
+pub const TOKEN_TYPES: [&str; 15] = [
+	"keyword",
+	"variable",
+	"function",
+	"class",
+	"enum",
+	"enumMember",
+	"parameter",
+	"property",
+	"number",
+	"string",
+	"comment",
+	"operator",
+	"type",
+	"typeParameter",
+	"namespace",
+];
+
+/// The token modifiers sent to the editor, each modifier is a bit in a token's modifiers.
+pub const TOKEN_MODIFIERS: [&str; 2] = ["declaration", "readonly"];
+
+const KEYWORD: u32 = 0;
+const VARIABLE: u32 = 1;
+const FUNCTION: u32 = 2;
+const CLASS: u32 = 3;
+const ENUM: u32 = 4;
+const ENUM_MEMBER: u32 = 5;
+const PARAMETER: u32 = 6;
+const PROPERTY: u32 = 7;
+const NUMBER: u32 = 8;
+const STRING: u32 = 9;
+const COMMENT: u32 = 10;
+const OPERATOR: u32 = 11;
+const TYPE: u32 = 12;
+const TYPE_PARAMETER: u32 = 13;
+const NAMESPACE: u32 = 14;
+
+const DECLARATION: u32 = 1;
+const READONLY: u32 = 1 << 1;
+
+/// Classifies every token in a document, encoded as LSP expects:
+/// five numbers per token, each relative to the token before it.
+pub fn semantic_tokens(source: &str, tokens: &[Token], analysis: &Analysis) -> Vec<u32> {
+	let mut ranges: Vec<(usize, usize, u32, u32)> = Vec::new();
+	for token in tokens.iter() {
+		classify(token, analysis, &mut ranges);
+	}
+
+	let index: LineIndex = LineIndex::new(source);
+	let mut data: Vec<u32> = Vec::new();
+	let (mut prev_line, mut prev_char): (u32, u32) = (0, 0);
+
+	for (start, end, typ, modifiers) in ranges {
+		let text: &str = match source.get(start..end) {
+			Some(text) => text,
+			None => continue,
+		};
+
+		// editors do not all support tokens over multiple lines, so they are split
+		let mut offset: usize = start;
+		for line in text.split('\n') {
+			let length: usize = line.trim_end_matches('\r').encode_utf16().count();
+			if length > 0 {
+				let (line, character) = index.position(offset);
+				let delta: u32 = if line == prev_line { character - prev_char } else { character };
+				data.extend([line - prev_line, delta, length as u32, typ, modifiers]);
+				prev_line = line;
+				prev_char = character;
+			}
+			offset += line.len() + 1;
+		}
+	}
+
+	return data;
+}
+
+/// Finds the type of a token, a template is split in to its strings and the tokens inside of it.
+fn classify(token: &Token, analysis: &Analysis, ranges: &mut Vec<(usize, usize, u32, u32)>) {
+	let range = token.span.byte_range();
+	let typ: u32 = match &token.kind {
+		TokenKind::Keyword(_) | TokenKind::BoolLiteral(_) => KEYWORD,
+		TokenKind::NumberLiteral(_) => NUMBER,
+		TokenKind::StringLiteral(_) => STRING,
+		TokenKind::CommentLiteral(_) => COMMENT,
+		TokenKind::GenericType(_) => TYPE,
+		TokenKind::BinaryOpLiteral(_)
+		| TokenKind::UnaryOpLiteral(_)
+		| TokenKind::AssignmentLiteral(_)
+		| TokenKind::LogicalOpLiteral(_)
+		| TokenKind::ComparisonOpLiteral(_) => OPERATOR,
+		TokenKind::Identifier(_) => {
+			let (typ, modifiers): (u32, u32) = match analysis.decl_of(token.span) {
+				Some((decl, declaration)) => {
+					let mut modifiers: u32 = if declaration { DECLARATION } else { 0 };
+					if decl.kind == DeclKind::Constant {
+						modifiers |= READONLY;
+					}
+					(decl_type(decl.kind), modifiers)
+				},
+				None => (VARIABLE, 0),
+			};
+
+			ranges.push((range.start, range.end, typ, modifiers));
+			return;
+		},
+		TokenKind::TemplateLiteral(chunks) => {
+			let mut offset: usize = range.start;
+			for chunk in chunks.iter() {
+				if let TemplateChunk::Tokens(inner) = chunk {
+					for token in inner.iter() {
+						let start: usize = token.span.start().offset();
+						if start > offset {
+							ranges.push((offset, start, STRING, 0));
+						}
+						classify(token, analysis, ranges);
+						offset = offset.max(token.span.end().offset());
+					}
+				}
+			}
+
+			if range.end > offset {
+				ranges.push((offset, range.end, STRING, 0));
+			}
+			return;
+		},
+		_ => return,
+	};
+
+	ranges.push((range.start, range.end, typ, 0));
+}
+
+fn decl_type(kind: DeclKind) -> u32 {
+	return match kind {
+		DeclKind::Variable | DeclKind::Constant => VARIABLE,
+		DeclKind::Function | DeclKind::Method => FUNCTION,
+		DeclKind::Class => CLASS,
+		DeclKind::Enum => ENUM,
+		DeclKind::Variant => ENUM_MEMBER,
+		DeclKind::Parameter => PARAMETER,
+		DeclKind::Field => PROPERTY,
+		DeclKind::TypeParam => TYPE_PARAMETER,
+		DeclKind::Package => NAMESPACE,
+	};
+}
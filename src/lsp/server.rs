@@ -0,0 +1,304 @@
+use super::analysis::{ Analysis, Decl, DeclKind };
+use super::convert::LineIndex;
+use super::rpc::{ self, codes };
+use super::semantic::{ semantic_tokens, TOKEN_MODIFIERS, TOKEN_TYPES };
+use crate::compiler::{ typesharp_ast::TextEdit, typesharp_cst::Document };
+use crate::error::{ Diagnostic, ErrCompiler };
+use crate::util::Json;
+use std::collections::HashMap;
+use std::io::{ BufRead, Write };
+
+/// A language server for the files open in a single editor.
+/// Each message is handled in turn, and the messages to send back are returned.
+#[derive(Default)]
+pub struct Server {
+	documents: HashMap<String, Document>,
+	shutdown: bool,
+	/// Set once the client asks the server to exit, the process should exit with this code.
+	pub exit: Option<i32>,
+}
+
+impl Server {
+	pub fn new() -> Self {
+		return Server::default();
+	}
+
+	/// Handles a request or notification, returning the responses and notifications to send.
+	pub fn handle(&mut self, message: &Json) -> Vec<Json> {
+		let method: &str = message.get("method").and_then(|m| m.as_str()).unwrap_or("");
+		let params: Json = message.get("params").cloned().unwrap_or(Json::Null);
+
+		let id: Json = match message.get("id") {
+			Some(id) => id.clone(),
+			None => return self.notification(method, &params),
+		};
+
+		if self.shutdown && method != "exit" {
+			return vec![rpc::error_response(id, codes::INVALID_REQUEST, "the server has been shut down")];
+		}
+
+		let result: Result<Json, (i64, String)> = match method {
+			"initialize" => Ok(capabilities()),
+			"shutdown" => {
+				self.shutdown = true;
+				Ok(Json::Null)
+			},
+			"textDocument/semanticTokens/full" => self.with_document(&params, |document, analysis, _| {
+				let data: Vec<u32> = semantic_tokens(document.source(), document.tokens(), analysis);
+				return Json::object(vec![("data", Json::from(data))]);
+			}),
+			"textDocument/documentSymbol" => self.with_document(&params, |document, analysis, _| {
+				let index: LineIndex = LineIndex::new(document.source());
+				return document_symbols(&index, analysis, None);
+			}),
+			"textDocument/hover" => self.with_document(&params, |_, analysis, offset| {
+				return match offset.and_then(|offset| analysis.decl_at(offset)) {
+					Some(decl) => hover(decl),
+					None => Json::Null,
+				};
+			}),
+			"textDocument/definition" => {
+				let uri: Json = params.path(&["textDocument", "uri"]).cloned().unwrap_or(Json::Null);
+				self.with_document(&params, |document, analysis, offset| {
+					let index: LineIndex = LineIndex::new(document.source());
+					return match offset.and_then(|offset| analysis.decl_at(offset)) {
+						Some(decl) => Json::object(vec![("uri", uri), ("range", index.to_range(decl.span))]),
+						None => Json::Null,
+					};
+				})
+			},
+			_ => Err((codes::METHOD_NOT_FOUND, format!("unknown method `{}`", method))),
+		};
+
+		return vec![match result {
+			Ok(result) => rpc::response(id, result),
+			Err((code, message)) => rpc::error_response(id, code, &message),
+		}];
+	}
+
+	fn notification(&mut self, method: &str, params: &Json) -> Vec<Json> {
+		let uri: String = match params.path(&["textDocument", "uri"]).and_then(|u| u.as_str()) {
+			Some(uri) => uri.to_string(),
+			None if method == "exit" => {
+				self.exit = Some(if self.shutdown { 0 } else { 1 });
+				return Vec::new();
+			},
+			None => return Vec::new(),
+		};
+
+		match method {
+			"textDocument/didOpen" => {
+				let text: &str = params.path(&["textDocument", "text"]).and_then(|t| t.as_str()).unwrap_or("");
+				self.documents.insert(uri.clone(), Document::new(text));
+			},
+			"textDocument/didChange" => {
+				let document: &mut Document = match self.documents.get_mut(&uri) {
+					Some(document) => document,
+					None => return Vec::new(),
+				};
+
+				for change in params.get("contentChanges").and_then(|c| c.as_array()).into_iter().flatten() {
+					let text: &str = change.get("text").and_then(|t| t.as_str()).unwrap_or("");
+					match change.get("range") {
+						Some(range) => {
+							let index: LineIndex = LineIndex::new(document.source());
+							let start: Option<usize> = range.get("start").and_then(|p| index.offset_of(p));
+							let end: Option<usize> = range.get("end").and_then(|p| index.offset_of(p));
+							if let (Some(start), Some(end)) = (start, end) {
+								document.edit(&TextEdit::new(start..end, text));
+							}
+						},
+						None => *document = Document::new(text),
+					}
+				}
+			},
+			"textDocument/didClose" => {
+				self.documents.remove(&uri);
+				// clear the diagnostics of the closed file
+				let params: Json = Json::object(vec![("uri", Json::from(uri)), ("diagnostics", Json::Array(Vec::new()))]);
+				return vec![rpc::notification("textDocument/publishDiagnostics", params)];
+			},
+			_ => return Vec::new(),
+		}
+
+		return match self.documents.get(&uri) {
+			Some(document) => vec![publish_diagnostics(&uri, document)],
+			None => Vec::new(),
+		};
+	}
+
+	/// Runs a request against an open document, and the byte offset of `params.position` if there is one.
+	fn with_document(&self, params: &Json, f: impl FnOnce(&Document, &Analysis, Option<usize>) -> Json) -> Result<Json, (i64, String)> {
+		let uri: &str = match params.path(&["textDocument", "uri"]).and_then(|u| u.as_str()) {
+			Some(uri) => uri,
+			None => return Err((codes::INVALID_PARAMS, String::from("missing `textDocument.uri`"))),
+		};
+		let document: &Document = match self.documents.get(uri) {
+			Some(document) => document,
+			None => return Err((codes::INVALID_PARAMS, format!("`{}` is not open", uri))),
+		};
+
+		let offset: Option<usize> = params.get("position").and_then(|p| LineIndex::new(document.source()).offset_of(p));
+		return Ok(f(document, &Analysis::new(document), offset));
+	}
+}
+
+/// Reads messages from `reader` and writes responses to `writer` until the client exits.
+/// Returns the code the process should exit with.
+pub fn run(mut reader: impl BufRead, mut writer: impl Write) -> i32 {
+	let mut server: Server = Server::new();
+
+	loop {
+		let body: String = match rpc::read_message(&mut reader) {
+			Ok(Some(body)) => body,
+			// the client went away without asking the server to exit
+			Ok(None) | Err(_) => return 1,
+		};
+
+		let responses: Vec<Json> = match Json::parse(&body) {
+			Ok(message) => server.handle(&message),
+			Err(err) => vec![rpc::error_response(Json::Null, codes::PARSE_ERROR, &err.to_string())],
+		};
+
+		for response in responses.iter() {
+			if rpc::write_message(&mut writer, response).is_err() {
+				return 1;
+			}
+		}
+
+		if let Some(code) = server.exit {
+			return code;
+		}
+	}
+}
+
+fn capabilities() -> Json {
+	let legend: Json = Json::object(vec![
+		("tokenTypes", Json::from(TOKEN_TYPES.to_vec())),
+		("tokenModifiers", Json::from(TOKEN_MODIFIERS.to_vec())),
+	]);
+
+	let capabilities: Json = Json::object(vec![
+		// changes are sent as edits, which are re-lexed incrementally
+		("textDocumentSync", Json::from(2u32)),
+		("semanticTokensProvider", Json::object(vec![("legend", legend), ("full", Json::from(true))])),
+		("documentSymbolProvider", Json::from(true)),
+		("hoverProvider", Json::from(true)),
+		("definitionProvider", Json::from(true)),
+	]);
+
+	return Json::object(vec![
+		("capabilities", capabilities),
+		("serverInfo", Json::object(vec![("name", Json::from("typesharp-lsp")), ("version", Json::from(env!("CARGO_PKG_VERSION")))])),
+	]);
+}
+
+fn publish_diagnostics(uri: &str, document: &Document) -> Json {
+	let index: LineIndex = LineIndex::new(document.source());
+	let diagnostics: Vec<Json> = Analysis::new(document).diagnostics.iter().map(|d| diagnostic(uri, &index, d)).collect();
+
+	return rpc::notification(
+		"textDocument/publishDiagnostics",
+		Json::object(vec![("uri", Json::from(uri)), ("diagnostics", Json::from(diagnostics))]),
+	);
+}
+
+/// Converts a diagnostic to LSP, labels become related information and notes are added to the message.
+fn diagnostic(uri: &str, index: &LineIndex, diagnostic: &Diagnostic) -> Json {
+	let severity: u32 = match diagnostic.severity {
+		ErrCompiler::Error | ErrCompiler::Unreachable => 1,
+		ErrCompiler::Warning => 2,
+		ErrCompiler::Note => 3,
+		ErrCompiler::Suggestion => 4,
+	};
+
+	let mut message: String = diagnostic.message.clone();
+	for note in diagnostic.notes.iter() {
+		message.push_str("\nnote: ");
+		message.push_str(note);
+	}
+	for suggestion in diagnostic.suggestions.iter() {
+		message.push_str("\nhelp: ");
+		message.push_str(&suggestion.message);
+	}
+
+	let related: Vec<Json> = diagnostic
+		.labels
+		.iter()
+		.map(|label| {
+			let location: Json = Json::object(vec![("uri", Json::from(uri)), ("range", index.to_range(label.span))]);
+			Json::object(vec![("location", location), ("message", Json::from(label.message.as_str()))])
+		})
+		.collect();
+
+	let mut json: Json = Json::object(vec![
+		("range", index.to_range(diagnostic.span)),
+		("severity", Json::from(severity)),
+		("source", Json::from("typesharp")),
+		("message", Json::from(message)),
+	]);
+	if let Some(code) = diagnostic.code {
+		json.insert("code", Json::from(code));
+	}
+	if !related.is_empty() {
+		json.insert("relatedInformation", Json::from(related));
+	}
+
+	return json;
+}
+
+/// Builds the outline of a document, members are nested in their class or enum.
+fn document_symbols(index: &LineIndex, analysis: &Analysis, parent: Option<usize>) -> Json {
+	let symbols: Vec<Json> = analysis
+		.decls
+		.iter()
+		.enumerate()
+		.filter(|(_, decl)| decl.parent == parent && decl.kind != DeclKind::Parameter)
+		// variables inside of functions are not part of the outline
+		.filter(|(_, decl)| parent.is_none() || !matches!(decl.kind, DeclKind::Variable | DeclKind::Constant))
+		.map(|(i, decl)| {
+			let mut symbol: Json = Json::object(vec![
+				("name", Json::from(decl.name.as_str())),
+				("kind", Json::from(symbol_kind(decl.kind))),
+				("range", index.to_range(decl.range.to(decl.span))),
+				("selectionRange", index.to_range(decl.span)),
+			]);
+			if let Some(typ) = &decl.typ {
+				symbol.insert("detail", Json::from(typ.as_str()));
+			}
+
+			let children: Json = document_symbols(index, analysis, Some(i));
+			if children.as_array().is_some_and(|c| !c.is_empty()) {
+				symbol.insert("children", children);
+			}
+			symbol
+		})
+		.collect();
+
+	return Json::Array(symbols);
+}
+
+/// The LSP `SymbolKind` of a declaration.
+fn symbol_kind(kind: DeclKind) -> u32 {
+	return match kind {
+		DeclKind::Class => 5,
+		DeclKind::Method => 6,
+		DeclKind::Field => 8,
+		DeclKind::Enum => 10,
+		DeclKind::Function => 12,
+		DeclKind::Variable | DeclKind::Parameter => 13,
+		DeclKind::Constant => 14,
+		DeclKind::Variant => 22,
+		DeclKind::TypeParam => 26,
+		DeclKind::Package => 4,
+	};
+}
+
+fn hover(decl: &Decl) -> Json {
+	let contents: Json = Json::object(vec![
+		("kind", Json::from("markdown")),
+		("value", Json::from(format!("```typesharp\n{}\n```", decl.detail))),
+	]);
+
+	return Json::object(vec![("contents", contents)]);
+}
@@ -0,0 +1,449 @@
+use std::{fmt, fmt::Write};
+
+/// A JSON value, used by tools that talk to the compiler (eg: the language server).
+/// Objects keep their keys in the order they were added.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Json {
+	Null,
+	Bool(bool),
+	Number(f64),
+	String(String),
+	Array(Vec<Json>),
+	Object(Vec<(String, Json)>),
+}
+
+/// Why some text could not be parsed as JSON, `offset` is the byte it was found at.
+#[derive(Clone, PartialEq, Debug)]
+pub struct JsonError {
+	pub message: String,
+	pub offset: usize,
+}
+
+impl fmt::Display for JsonError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		return write!(f, "{} at byte {}", self.message, self.offset);
+	}
+}
+
+impl std::error::Error for JsonError {}
+
+impl Json {
+	/// Parses a JSON document, there may be whitespace around the value but nothing else.
+	pub fn parse(text: &str) -> Result<Json, JsonError> {
+		let mut parser: JsonParser = JsonParser {
+			text: text,
+			pos: 0,
+		};
+
+		let value: Json = parser.value()?;
+		parser.whitespace();
+		if parser.pos < text.len() {
+			return Err(parser.error("trailing characters after JSON value"));
+		}
+
+		return Ok(value);
+	}
+
+	/// Creates an object from key-value pairs.
+	pub fn object(pairs: Vec<(&str, Json)>) -> Json {
+		return Json::Object(pairs.into_iter().map(|(k, v)| (k.to_string(), v)).collect());
+	}
+
+	/// Gets a value in an object by its key.
+	pub fn get(&self, key: &str) -> Option<&Json> {
+		return match self {
+			Json::Object(pairs) => pairs.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+			_ => None,
+		};
+	}
+
+	/// Follows a path of keys, eg: `["textDocument", "uri"]`
+	pub fn path(&self, keys: &[&str]) -> Option<&Json> {
+		let mut value: &Json = self;
+		for key in keys {
+			value = value.get(key)?;
+		}
+
+		return Some(value);
+	}
+
+	/// Sets a key in an object, replacing any value it already had.
+	pub fn insert(&mut self, key: &str, value: Json) {
+		if let Json::Object(pairs) = self {
+			match pairs.iter_mut().find(|(k, _)| k == key) {
+				Some(pair) => pair.1 = value,
+				None => pairs.push((key.to_string(), value)),
+			}
+		}
+	}
+
+	pub fn as_str(&self) -> Option<&str> {
+		return match self {
+			Json::String(s) => Some(s),
+			_ => None,
+		};
+	}
+
+	pub fn as_f64(&self) -> Option<f64> {
+		return match self {
+			Json::Number(n) => Some(*n),
+			_ => None,
+		};
+	}
+
+	/// Gets a number that is a whole, non-negative integer.
+	pub fn as_u64(&self) -> Option<u64> {
+		return match self {
+			Json::Number(n) if *n >= 0.0 && n.fract() == 0.0 => Some(*n as u64),
+			_ => None,
+		};
+	}
+
+	pub fn as_bool(&self) -> Option<bool> {
+		return match self {
+			Json::Bool(b) => Some(*b),
+			_ => None,
+		};
+	}
+
+	pub fn as_array(&self) -> Option<&Vec<Json>> {
+		return match self {
+			Json::Array(items) => Some(items),
+			_ => None,
+		};
+	}
+
+	pub fn is_null(&self) -> bool {
+		return *self == Json::Null;
+	}
+
+	/// Writes the value over multiple lines, indented with tabs.
+	pub fn pretty(&self) -> String {
+		let mut out: String = String::new();
+		self.write_pretty(&mut out, 0);
+		return out;
+	}
+
+	fn write_pretty(&self, out: &mut String, depth: usize) {
+		let indent: String = "\t".repeat(depth + 1);
+		match self {
+			Json::Array(items) if !items.is_empty() => {
+				out.push_str("[\n");
+				for (i, item) in items.iter().enumerate() {
+					out.push_str(&indent);
+					item.write_pretty(out, depth + 1);
+					out.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+				}
+				out.push_str(&"\t".repeat(depth));
+				out.push(']');
+			},
+			Json::Object(pairs) if !pairs.is_empty() => {
+				out.push_str("{\n");
+				for (i, (key, value)) in pairs.iter().enumerate() {
+					out.push_str(&indent);
+					out.push_str(&quote(key));
+					out.push_str(": ");
+					value.write_pretty(out, depth + 1);
+					out.push_str(if i + 1 < pairs.len() { ",\n" } else { "\n" });
+				}
+				out.push_str(&"\t".repeat(depth));
+				out.push('}');
+			},
+			value => out.push_str(&value.to_string()),
+		}
+	}
+}
+
+impl fmt::Display for Json {
+	/// Writes the value on a single line.
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Json::Null => f.write_str("null"),
+			Json::Bool(b) => write!(f, "{}", b),
+			Json::Number(n) if !n.is_finite() => f.write_str("null"),
+			Json::Number(n) if n.fract() == 0.0 && n.abs() < 1e15 => write!(f, "{}", *n as i64),
+			Json::Number(n) => write!(f, "{}", n),
+			Json::String(s) => f.write_str(&quote(s)),
+			Json::Array(items) => {
+				f.write_char('[')?;
+				for (i, item) in items.iter().enumerate() {
+					if i > 0 {
+						f.write_char(',')?;
+					}
+					write!(f, "{}", item)?;
+				}
+				f.write_char(']')
+			},
+			Json::Object(pairs) => {
+				f.write_char('{')?;
+				for (i, (key, value)) in pairs.iter().enumerate() {
+					if i > 0 {
+						f.write_char(',')?;
+					}
+					write!(f, "{}:{}", quote(key), value)?;
+				}
+				f.write_char('}')
+			},
+		}
+	}
+}
+
+impl From<bool> for Json {
+	fn from(b: bool) -> Self {
+		return Json::Bool(b);
+	}
+}
+
+impl From<&str> for Json {
+	fn from(s: &str) -> Self {
+		return Json::String(s.to_string());
+	}
+}
+
+impl From<String> for Json {
+	fn from(s: String) -> Self {
+		return Json::String(s);
+	}
+}
+
+impl From<u32> for Json {
+	fn from(n: u32) -> Self {
+		return Json::Number(n as f64);
+	}
+}
+
+impl From<usize> for Json {
+	fn from(n: usize) -> Self {
+		return Json::Number(n as f64);
+	}
+}
+
+impl From<i64> for Json {
+	fn from(n: i64) -> Self {
+		return Json::Number(n as f64);
+	}
+}
+
+impl From<f64> for Json {
+	fn from(n: f64) -> Self {
+		return Json::Number(n);
+	}
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+	fn from(items: Vec<T>) -> Self {
+		return Json::Array(items.into_iter().map(|i| i.into()).collect());
+	}
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+	fn from(value: Option<T>) -> Self {
+		return match value {
+			Some(value) => value.into(),
+			None => Json::Null,
+		};
+	}
+}
+
+/// Writes a string as a quoted JSON string.
+pub fn quote(s: &str) -> String {
+	let mut out: String = String::with_capacity(s.len() + 2);
+	out.push('"');
+
+	for c in s.chars() {
+		match c {
+			'"' => out.push_str("\\\""),
+			'\\' => out.push_str("\\\\"),
+			'\n' => out.push_str("\\n"),
+			'\r' => out.push_str("\\r"),
+			'\t' => out.push_str("\\t"),
+			c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+			c => out.push(c),
+		}
+	}
+
+	out.push('"');
+	return out;
+}
+
+struct JsonParser<'a> {
+	text: &'a str,
+	pos: usize,
+}
+
+impl<'a> JsonParser<'a> {
+	fn value(&mut self) -> Result<Json, JsonError> {
+		self.whitespace();
+		return match self.peek() {
+			Some('{') => self.object(),
+			Some('[') => self.array(),
+			Some('"') => Ok(Json::String(self.string()?)),
+			Some('t') => self.literal("true", Json::Bool(true)),
+			Some('f') => self.literal("false", Json::Bool(false)),
+			Some('n') => self.literal("null", Json::Null),
+			Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+			Some(c) => Err(self.error(&format!("unexpected character `{}`", c))),
+			None => Err(self.error("unexpected end of JSON")),
+		};
+	}
+
+	fn object(&mut self) -> Result<Json, JsonError> {
+		self.pos += 1;
+		let mut pairs: Vec<(String, Json)> = Vec::new();
+
+		self.whitespace();
+		if self.eat('}') {
+			return Ok(Json::Object(pairs));
+		}
+
+		loop {
+			self.whitespace();
+			if self.peek() != Some('"') {
+				return Err(self.error("expected a string key"));
+			}
+			let key: String = self.string()?;
+
+			self.whitespace();
+			if !self.eat(':') {
+				return Err(self.error("expected `:`"));
+			}
+			pairs.push((key, self.value()?));
+
+			self.whitespace();
+			if self.eat('}') {
+				return Ok(Json::Object(pairs));
+			}
+			if !self.eat(',') {
+				return Err(self.error("expected `,` or `}`"));
+			}
+		}
+	}
+
+	fn array(&mut self) -> Result<Json, JsonError> {
+		self.pos += 1;
+		let mut items: Vec<Json> = Vec::new();
+
+		self.whitespace();
+		if self.eat(']') {
+			return Ok(Json::Array(items));
+		}
+
+		loop {
+			items.push(self.value()?);
+
+			self.whitespace();
+			if self.eat(']') {
+				return Ok(Json::Array(items));
+			}
+			if !self.eat(',') {
+				return Err(self.error("expected `,` or `]`"));
+			}
+		}
+	}
+
+	fn string(&mut self) -> Result<String, JsonError> {
+		self.pos += 1;
+		let mut out: String = String::new();
+
+		loop {
+			let c: char = match self.next() {
+				Some(c) => c,
+				None => return Err(self.error("unterminated string")),
+			};
+
+			match c {
+				'"' => return Ok(out),
+				'\\' => match self.next() {
+					Some('"') => out.push('"'),
+					Some('\\') => out.push('\\'),
+					Some('/') => out.push('/'),
+					Some('b') => out.push('\u{8}'),
+					Some('f') => out.push('\u{c}'),
+					Some('n') => out.push('\n'),
+					Some('r') => out.push('\r'),
+					Some('t') => out.push('\t'),
+					Some('u') => {
+						let high: u32 = self.hex()?;
+						// characters outside of the BMP are written as a surrogate pair
+						let code: u32 = if (0xD800..0xDC00).contains(&high) && self.text[self.pos..].starts_with("\\u") {
+							self.pos += 2;
+							let low: u32 = self.hex()?;
+							0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF)
+						} else {
+							high
+						};
+						out.push(std::char::from_u32(code).unwrap_or('\u{FFFD}'));
+					},
+					_ => return Err(self.error("invalid escape in string")),
+				},
+				c => out.push(c),
+			}
+		}
+	}
+
+	fn hex(&mut self) -> Result<u32, JsonError> {
+		let digits: &str = self.text.get(self.pos..self.pos + 4).unwrap_or("");
+		let code: u32 = u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid unicode escape"))?;
+		self.pos += 4;
+		return Ok(code);
+	}
+
+	fn number(&mut self) -> Result<Json, JsonError> {
+		let start: usize = self.pos;
+		while let Some(c) = self.peek() {
+			if !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')) {
+				break;
+			}
+			self.pos += 1;
+		}
+
+		return self.text[start..self.pos]
+			.parse::<f64>()
+			.map(Json::Number)
+			.map_err(|_| JsonError {
+				message: String::from("invalid number"),
+				offset: start,
+			});
+	}
+
+	fn literal(&mut self, word: &str, value: Json) -> Result<Json, JsonError> {
+		if !self.text[self.pos..].starts_with(word) {
+			return Err(self.error("unexpected word"));
+		}
+
+		self.pos += word.len();
+		return Ok(value);
+	}
+
+	fn whitespace(&mut self) {
+		while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+			self.pos += 1;
+		}
+	}
+
+	fn peek(&self) -> Option<char> {
+		return self.text[self.pos..].chars().next();
+	}
+
+	fn next(&mut self) -> Option<char> {
+		let c: char = self.peek()?;
+		self.pos += c.len_utf8();
+		return Some(c);
+	}
+
+	fn eat(&mut self, c: char) -> bool {
+		if self.peek() == Some(c) {
+			self.pos += 1;
+			return true;
+		}
+
+		return false;
+	}
+
+	fn error(&self, message: &str) -> JsonError {
+		return JsonError {
+			message: message.to_string(),
+			offset: self.pos,
+		};
+	}
+}
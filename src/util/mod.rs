@@ -0,0 +1,4 @@
+pub mod json;
+pub mod yaml;
+
+pub use self::{ json::{ Json, JsonError }, yaml::to_yaml };
@@ -0,0 +1,89 @@
+use super::json::{ quote, Json };
+
+/// Writes a JSON value as a YAML document, in block style.
+/// Strings are always double quoted, so they are never read back as another type (eg: `true`).
+///
+/// EG:
+/// ```yaml
+/// keywords:
+///   - name: "Let"
+///     keyword: "let"
+/// ```
+pub fn to_yaml(value: &Json) -> String {
+	let mut out: String = String::new();
+	match value {
+		Json::Object(pairs) if !pairs.is_empty() => object(&mut out, pairs, 0),
+		Json::Array(items) if !items.is_empty() => array(&mut out, items, 0),
+		scalar => {
+			out.push_str(&inline(scalar));
+			out.push('\n');
+		},
+	}
+
+	return out;
+}
+
+fn object(out: &mut String, pairs: &[(String, Json)], indent: usize) {
+	for (key, value) in pairs.iter() {
+		out.push_str(&" ".repeat(indent));
+		out.push_str(&self::key(key));
+		out.push(':');
+		block(out, value, indent);
+	}
+}
+
+fn array(out: &mut String, items: &[Json], indent: usize) {
+	for item in items.iter() {
+		out.push_str(&" ".repeat(indent));
+		out.push('-');
+
+		match item {
+			// the first key goes on the same line as the `-`
+			Json::Object(pairs) if !pairs.is_empty() => {
+				let mut inner: String = String::new();
+				object(&mut inner, pairs, indent + 2);
+				out.push(' ');
+				out.push_str(&inner[indent + 2..]);
+			},
+			value => block(out, value, indent),
+		}
+	}
+}
+
+/// Writes the value of a key or list item, nested collections go on the following lines.
+fn block(out: &mut String, value: &Json, indent: usize) {
+	match value {
+		Json::Object(pairs) if !pairs.is_empty() => {
+			out.push('\n');
+			object(out, pairs, indent + 2);
+		},
+		Json::Array(items) if !items.is_empty() => {
+			out.push('\n');
+			array(out, items, indent + 2);
+		},
+		scalar => {
+			out.push(' ');
+			out.push_str(&inline(scalar));
+			out.push('\n');
+		},
+	}
+}
+
+fn inline(value: &Json) -> String {
+	return match value {
+		Json::String(s) => quote(s),
+		Json::Object(_) => String::from("{}"),
+		Json::Array(_) => String::from("[]"),
+		other => other.to_string(),
+	};
+}
+
+/// Keys are only quoted when they are not a plain word.
+fn key(key: &str) -> String {
+	let plain: bool = !key.is_empty()
+		&& key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+		&& !matches!(key, "true" | "false" | "null" | "yes" | "no" | "on" | "off")
+		&& !key.starts_with(|c: char| c.is_ascii_digit() || c == '-');
+
+	return if plain { key.to_string() } else { quote(key) };
+}
@@ -0,0 +1,376 @@
+#![allow(clippy::needless_return, clippy::redundant_field_names)]
+// Golden tests, every `.t#` file in the corpus is run through the `typesharp` command line
+// and what it prints is compared against the expectation files next to it.
+use std::fs;
+use std::io;
+use std::path::{ Path, PathBuf };
+use typesharp::cli;
+use typesharp::util::Json;
+
+/// The command a test is run with, taken from the first directory under the corpus (eg: `ui/parse/let.t#`).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Mode {
+	/// `typesharp lex`, stdout is compared against `.tokens`.
+	Lex,
+	/// `typesharp parse --dump-ast`, stdout is compared against `.ast`.
+	Parse,
+	/// `typesharp check`, stdout is compared against `.stdout`.
+	Check,
+	/// `typesharp run`, stdout is compared against `.stdout`.
+	Run,
+}
+
+impl Mode {
+	pub fn from_name(name: &str) -> Option<Mode> {
+		return Some(match name {
+			"lex" => Mode::Lex,
+			"parse" => Mode::Parse,
+			"check" => Mode::Check,
+			"run" => Mode::Run,
+			_ => return None,
+		});
+	}
+
+	fn args(&self) -> &'static [&'static str] {
+		return match self {
+			Mode::Lex => &["lex"],
+			Mode::Parse => &["parse", "--dump-ast"],
+			Mode::Check => &["check"],
+			Mode::Run => &["run"],
+		};
+	}
+
+	/// The extension of the file stdout is compared against, stderr is always compared against `.stderr`.
+	fn stdout(&self) -> &'static str {
+		return match self {
+			Mode::Lex => "tokens",
+			Mode::Parse => "ast",
+			Mode::Check | Mode::Run => "stdout",
+		};
+	}
+}
+
+/// An inline expectation, eg: `//~ ERROR expected `;``
+///
+/// `//~^ ERROR` points at the line above (one line per `^`), and `//~| ERROR` at the same line as the annotation before it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Annotation {
+	pub line: usize,
+	/// The severity, as the compiler names it, eg: `error` or `warning`.
+	pub severity: String,
+	/// A part of the message the diagnostic must contain.
+	pub message: String,
+}
+
+/// A `.t#` file of the corpus, and what is expected of it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TestFile {
+	pub path: PathBuf,
+	pub mode: Mode,
+	pub annotations: Vec<Annotation>,
+	/// Set with `//@ exit: <code>`, otherwise `1` when an error is annotated and `0` when none are.
+	pub exit: i32,
+}
+
+impl TestFile {
+	pub fn new(path: PathBuf, mode: Mode, source: &str) -> Result<Self, String> {
+		let mut annotations: Vec<Annotation> = Vec::new();
+		let mut exit: Option<i32> = None;
+
+		for (index, line) in source.lines().enumerate() {
+			if let Some(directive) = line.trim_start().strip_prefix("//@") {
+				match directive.trim().split_once(':') {
+					Some(("exit", code)) => {
+						exit = Some(code.trim().parse().map_err(|_| format!("line {}: `{}` is not an exit code", index + 1, code.trim()))?);
+					},
+					_ => return Err(format!("line {}: unknown directive `{}`", index + 1, directive.trim())),
+				}
+				continue;
+			}
+
+			let rest: &str = match line.find("//~") {
+				Some(start) => &line[start + 3..],
+				None => continue,
+			};
+
+			let line: usize = match rest.chars().next() {
+				Some('|') => match annotations.last() {
+					Some(previous) => previous.line,
+					None => return Err(format!("line {}: `//~|` has no annotation before it", index + 1)),
+				},
+				_ => {
+					let carets: usize = rest.chars().take_while(|c| *c == '^').count();
+					match (index + 1).checked_sub(carets).filter(|line| *line > 0) {
+						Some(line) => line,
+						None => return Err(format!("{}:{}: `//~{}` points above the first line", path.display(), index + 1, "^".repeat(carets))),
+					}
+				},
+			};
+			let rest: &str = rest.trim_start_matches(['^', '|']).trim_start();
+
+			let (severity, message) = rest.split_once(' ').unwrap_or((rest, ""));
+			let severity: &str = match severity {
+				"ERROR" => "error",
+				"WARNING" => "warning",
+				"NOTE" => "note",
+				"HELP" => "help",
+				other => return Err(format!("line {}: unknown severity `{}`, expected ERROR, WARNING, NOTE or HELP", index + 1, other)),
+			};
+
+			annotations.push(Annotation {
+				line: line,
+				severity: severity.to_string(),
+				message: message.trim().to_string(),
+			});
+		}
+
+		let errors: bool = annotations.iter().any(|a| a.severity == "error");
+		return Ok(TestFile {
+			path: path,
+			mode: mode,
+			annotations: annotations,
+			exit: exit.unwrap_or(if errors { cli::exit::FAILURE } else { cli::exit::SUCCESS }),
+		});
+	}
+
+	fn expectation(&self, extension: &str) -> PathBuf {
+		return self.path.with_extension(extension);
+	}
+}
+
+/// What the runner does with the corpus.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Config {
+	/// The directory holding the corpus, its subdirectories are named after a `Mode`.
+	pub root: PathBuf,
+	/// Overwrite the expectation files with what was printed, instead of comparing them.
+	pub bless: bool,
+	/// Only run the tests whose path contains one of these, every test is run when it is empty.
+	pub filters: Vec<String>,
+}
+
+impl Config {
+	pub fn new(root: impl Into<PathBuf>) -> Self {
+		return Config {
+			root: root.into(),
+			bless: false,
+			filters: Vec::new(),
+		};
+	}
+}
+
+/// The results of a run.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Report {
+	pub passed: usize,
+	pub blessed: usize,
+	/// Each failed test, with why it failed.
+	pub failures: Vec<(PathBuf, Vec<String>)>,
+}
+
+impl Report {
+	pub fn is_success(&self) -> bool {
+		return self.failures.is_empty();
+	}
+}
+
+impl std::fmt::Display for Report {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for (path, reasons) in self.failures.iter() {
+			writeln!(f, "---- {} ----", path.display())?;
+			for reason in reasons.iter() {
+				writeln!(f, "{}", reason.trim_end())?;
+			}
+			writeln!(f)?;
+		}
+
+		return write!(
+			f,
+			"test result: {}. {} passed; {} failed; {} blessed",
+			if self.is_success() { "ok" } else { "FAILED" },
+			self.passed,
+			self.failures.len(),
+			self.blessed
+		);
+	}
+}
+
+/// Runs every test of the corpus.
+pub fn run(config: &Config) -> io::Result<Report> {
+	let mut report: Report = Report::default();
+
+	for path in collect(&config.root)? {
+		let name: String = path.display().to_string();
+		if !config.filters.is_empty() && !config.filters.iter().any(|f| name.contains(f.as_str())) {
+			continue;
+		}
+
+		let mode: Option<Mode> = path
+			.strip_prefix(&config.root)
+			.ok()
+			.and_then(|p| p.components().next())
+			.and_then(|c| c.as_os_str().to_str())
+			.and_then(Mode::from_name);
+		let mode: Mode = match mode {
+			Some(mode) => mode,
+			None => {
+				report.failures.push((path, vec![String::from("tests must be in a `lex`, `parse`, `check` or `run` directory")]));
+				continue;
+			},
+		};
+
+		let test: TestFile = match TestFile::new(path.clone(), mode, &fs::read_to_string(&path)?) {
+			Ok(test) => test,
+			Err(e) => {
+				report.failures.push((path, vec![e]));
+				continue;
+			},
+		};
+
+		let reasons: Vec<String> = run_test(&test, config.bless)?;
+		if !reasons.is_empty() {
+			report.failures.push((path, reasons));
+		} else if config.bless {
+			report.blessed += 1;
+		} else {
+			report.passed += 1;
+		}
+	}
+
+	return Ok(report);
+}
+
+/// Runs a single test, returning why it failed.
+pub fn run_test(test: &TestFile, bless: bool) -> io::Result<Vec<String>> {
+	let mut reasons: Vec<String> = Vec::new();
+	let path: String = test.path.display().to_string();
+
+	let (code, stdout, stderr) = typesharp(test.mode.args(), &["--color", "never"], &path);
+	if code != test.exit {
+		reasons.push(format!("expected exit code {}, found {}\nstderr:\n{}", test.exit, code, stderr));
+	}
+
+	// paths differ between machines, so the directory of the test is written as `$DIR`
+	let dir: String = test.path.parent().map(|p| p.display().to_string()).unwrap_or_default();
+	let normalize = |output: &str| output.replace(&format!("{}/", dir), "$DIR/");
+
+	for (extension, actual) in [(test.mode.stdout(), normalize(&stdout)), ("stderr", normalize(&stderr))] {
+		let file: PathBuf = test.expectation(extension);
+		if bless {
+			match actual.is_empty() {
+				true if file.exists() => fs::remove_file(&file)?,
+				true => (),
+				false => fs::write(&file, &actual)?,
+			}
+			continue;
+		}
+
+		// a missing expectation file means nothing should be printed
+		let expected: String = match fs::read_to_string(&file) {
+			Ok(expected) => expected,
+			Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+			Err(e) => return Err(e),
+		};
+		if expected != actual {
+			reasons.push(format!("{} does not match, run with `--bless` to update it\n{}", file.display(), diff(&expected, &actual)));
+		}
+	}
+
+	// the diagnostics are read again as JSON, to match them against the annotations
+	let (_, _, json) = typesharp(test.mode.args(), &["--error-format", "json"], &path);
+	let mut found: Vec<(usize, String, String)> = Vec::new();
+	for line in json.lines().filter(|l| l.starts_with('{')) {
+		let diagnostic: Json = Json::parse(line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+		found.push((
+			diagnostic.path(&["span", "line_start"]).and_then(|l| l.as_u64()).unwrap_or(0) as usize,
+			diagnostic.get("severity").and_then(|s| s.as_str()).unwrap_or("").to_string(),
+			diagnostic.get("message").and_then(|m| m.as_str()).unwrap_or("").to_string(),
+		));
+	}
+	reasons.extend(match_annotations(&test.annotations, found));
+
+	return Ok(reasons);
+}
+
+/// Matches each annotation with a diagnostic on its line, every error must be annotated.
+/// Other severities only have to be annotated if the test annotates one of them.
+fn match_annotations(annotations: &[Annotation], mut found: Vec<(usize, String, String)>) -> Vec<String> {
+	let mut reasons: Vec<String> = Vec::new();
+
+	for annotation in annotations.iter() {
+		let position: Option<usize> = found
+			.iter()
+			.position(|(line, severity, message)| *line == annotation.line && *severity == annotation.severity && message.contains(&annotation.message));
+		match position {
+			Some(index) => drop(found.remove(index)),
+			None => reasons.push(format!("line {}: expected {} not found: {}", annotation.line, annotation.severity, annotation.message)),
+		}
+	}
+
+	for (line, severity, message) in found.iter() {
+		if severity == "error" || annotations.iter().any(|a| a.severity == *severity) {
+			reasons.push(format!("line {}: unexpected {}: {}", line, severity, message));
+		}
+	}
+
+	return reasons;
+}
+
+/// Runs the `typesharp` command line on a file, returning its exit code, stdout and stderr.
+fn typesharp(command: &[&str], options: &[&str], path: &str) -> (i32, String, String) {
+	let args: Vec<String> = command.iter().chain(options.iter()).chain(std::iter::once(&path)).map(|a| a.to_string()).collect();
+	let mut out: Vec<u8> = Vec::new();
+	let mut err: Vec<u8> = Vec::new();
+
+	let code: i32 = cli::main(&args, &mut out, &mut err);
+	return (code, String::from_utf8_lossy(&out).into_owned(), String::from_utf8_lossy(&err).into_owned());
+}
+
+/// Every `.t#` file under a directory, in a stable order.
+pub fn collect(root: &Path) -> io::Result<Vec<PathBuf>> {
+	let mut files: Vec<PathBuf> = Vec::new();
+	let mut entries: Vec<PathBuf> = fs::read_dir(root)?.map(|e| e.map(|e| e.path())).collect::<io::Result<_>>()?;
+	entries.sort();
+
+	for entry in entries {
+		if entry.is_dir() {
+			files.extend(collect(&entry)?);
+		} else if entry.extension().is_some_and(|e| e == cli::EXTENSION) {
+			files.push(entry);
+		}
+	}
+
+	return Ok(files);
+}
+
+/// A line diff, lines only in `expected` start with `-` and lines only in `actual` with `+`.
+pub fn diff(expected: &str, actual: &str) -> String {
+	let a: Vec<&str> = expected.lines().collect();
+	let b: Vec<&str> = actual.lines().collect();
+
+	// the length of the longest common subsequence of `a[i..]` and `b[j..]`
+	let mut lcs: Vec<Vec<usize>> = vec![vec![0; b.len() + 1]; a.len() + 1];
+	for i in (0..a.len()).rev() {
+		for j in (0..b.len()).rev() {
+			lcs[i][j] = if a[i] == b[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+		}
+	}
+
+	let mut out: String = String::new();
+	let (mut i, mut j) = (0, 0);
+	while i < a.len() || j < b.len() {
+		if i < a.len() && j < b.len() && a[i] == b[j] {
+			out.push_str(&format!(" {}\n", a[i]));
+			i += 1;
+			j += 1;
+		} else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+			out.push_str(&format!("-{}\n", a[i]));
+			i += 1;
+		} else {
+			out.push_str(&format!("+{}\n", b[j]));
+			j += 1;
+		}
+	}
+
+	return out;
+}
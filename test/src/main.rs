@@ -1,15 +1,30 @@
-use typesharp;
-// use crate::{ typesharp::compiler::typesharp_ast_old::Features };
-use std::fs::write;
-fn main() {
-	let code = "\"this is a test string\\\" with a qoutation escape sequence\"\"and another string next to that\"";
-	let lexed = typesharp::compiler::typesharp_lexer::tokenize(&code);
-	let val = lexed.len();
+#![allow(clippy::needless_return, clippy::redundant_field_names)]
+// Runs the golden tests, eg: `cargo run -p runner -- --bless parse/`
+use runner::{ Config, Report };
 
-	for token in lexed {
-		println!("Found: {:?}", token);
+fn main() {
+	let mut config: Config = Config::new(concat!(env!("CARGO_MANIFEST_DIR"), "/ui"));
+	for arg in std::env::args().skip(1) {
+		match arg.as_str() {
+			"--bless" => config.bless = true,
+			"-h" | "--help" => {
+				println!("Usage: runner [--bless] [filters...]\n\nRuns every test under `test/ui` whose path contains one of the filters.");
+				return;
+			},
+			_ => config.filters.push(arg),
+		}
 	}
 
-	println!("There are: {} tokens.", val);
+	let report: Report = match runner::run(&config) {
+		Ok(report) => report,
+		Err(e) => {
+			eprintln!("error: {}", e);
+			std::process::exit(1);
+		},
+	};
 
+	println!("{}", report);
+	if !report.is_success() {
+		std::process::exit(1);
+	}
 }
@@ -0,0 +1,22 @@
+// Runs the golden tests with `cargo test`, set `BLESS=1` to update the expectation files.
+use runner::{ Config, Mode, Report, TestFile };
+use std::path::PathBuf;
+
+#[test]
+fn ui() {
+	let mut config: Config = Config::new(concat!(env!("CARGO_MANIFEST_DIR"), "/ui"));
+	config.bless = std::env::var_os("BLESS").is_some();
+
+	let report: Report = runner::run(&config).expect("could not run the golden tests");
+	assert!(report.is_success(), "\n{}", report);
+}
+
+#[test]
+fn annotations_above_the_first_line_are_rejected() {
+	let error: String = TestFile::new(PathBuf::from("ui/check/above.t#"), Mode::Check, "let x = 1; //~^ ERROR nothing").err().unwrap();
+	assert_eq!(error, "ui/check/above.t#:1: `//~^` points above the first line");
+
+	let error: String = TestFile::new(PathBuf::from("above.t#"), Mode::Check, "\nlet x = 1; //~^^^ ERROR nothing").err().unwrap();
+	assert_eq!(error, "above.t#:2: `//~^^^` points above the first line");
+	assert!(TestFile::new(PathBuf::from("above.t#"), Mode::Check, "\nlet x = 1; //~^ ERROR first").is_ok());
+}
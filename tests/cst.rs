@@ -0,0 +1,70 @@
+//! Checks that the concrete syntax tree keeps every byte of the code it was built from.
+#![allow(clippy::needless_return)]
+use std::fs;
+use std::path::{ Path, PathBuf };
+use typesharp::compiler::typesharp_cst::{ parse, SyntaxKind, SyntaxNode };
+
+/// Code the lexer has to recover from, as well as code that is valid.
+const SOURCES: &[&str] = &[
+	"",
+	"let x = 1;",
+	"  \t\n\r\n// only a comment",
+	"let s = `a ${b + `c ${d}`} e`; /* block */ let t = 'x';",
+	"function f(a: u8): u8 {\n\treturn a;\n}\n",
+	"let é = \"ünïcödé 😀\";",
+	"let x = 0b12 + 1e + 10u7;",
+	"let s = \"never closed",
+	"let t = `${ never closed",
+	"/* never closed",
+	"let x = ((1];}",
+	"# ¤ \\ ~",
+];
+
+/// Every `.t#` file of the golden tests.
+fn corpus(dir: &Path, files: &mut Vec<PathBuf>) {
+	for entry in fs::read_dir(dir).unwrap() {
+		let path: PathBuf = entry.unwrap().path();
+		if path.is_dir() {
+			corpus(&path, files);
+		} else if path.to_string_lossy().ends_with(".t#") {
+			files.push(path);
+		}
+	}
+}
+
+#[test]
+fn the_tree_keeps_every_byte() {
+	let mut files: Vec<PathBuf> = Vec::new();
+	corpus(Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/test/ui")), &mut files);
+	assert!(!files.is_empty());
+
+	let corpus: Vec<String> = files.iter().map(|f| fs::read_to_string(f).unwrap()).collect();
+	for source in SOURCES.iter().copied().chain(corpus.iter().map(|s| s.as_str())) {
+		let tree: SyntaxNode = parse(source);
+		assert_eq!(tree.text(), source);
+		assert_eq!(tree.text_range(), 0..source.len());
+
+		let tokens: String = tree.tokens().iter().map(|t| t.text().to_string()).collect();
+		assert_eq!(tokens, source, "the tokens of `{}` do not add up to it", source);
+	}
+}
+
+#[test]
+fn trivia_is_kept_in_the_tree() {
+	let tree: SyntaxNode = parse("let x = 1; // one\n/* two */");
+	let kinds: Vec<SyntaxKind> = tree.tokens().iter().map(|t| t.kind()).filter(|k| k.is_trivia()).collect();
+	assert!(kinds.len() >= 5, "{:?}", kinds);
+	assert!(tree.tokens().iter().any(|t| t.text() == "// one"));
+	assert!(tree.tokens().iter().any(|t| t.text() == "/* two */"));
+}
+
+#[test]
+fn tokens_can_be_found_by_offset() {
+	let source: &str = "let value = 10;";
+	let tree: SyntaxNode = parse(source);
+	for (offset, _) in source.char_indices() {
+		let token = tree.token_at_offset(offset).unwrap();
+		assert!(token.text_range().contains(&offset), "{:?} at {}", token, offset);
+	}
+	assert_eq!(tree.token_at_offset(4).unwrap().text(), "value");
+}
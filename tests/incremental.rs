@@ -0,0 +1,59 @@
+//! Checks that re-lexing and re-parsing only the code an edit touched gives the same result as starting over.
+#![allow(clippy::needless_return)]
+use typesharp::compiler::typesharp_ast::TextEdit;
+use typesharp::compiler::typesharp_cst::Document;
+
+const SOURCE: &str = "let x = 1; // one\nfunction f(a: u8) {\n\treturn `${a + 1}`;\n}\n/* two */ let s = \"é\";\nif x {} else {}\n";
+
+/// Text that changes how the code around it is lexed, eg: by opening a string or a comment.
+const INSERTIONS: &[&str] = &["x", " ", "\n", "{", "}", "\"", "`", "${", "/*", "*/", "//", "é", "1e", "let "];
+
+/// Checks that an edited document is the same as a document made from its new source.
+fn check(source: &str, edit: TextEdit) {
+	let mut document: Document = Document::new(source);
+	assert!(document.edit(&edit), "{:?} is not in `{}`", edit, source);
+
+	let fresh: Document = Document::new(document.source());
+	let context: String = format!("{:?} applied to {:?}", edit, source);
+	assert_eq!(document.tree().text(), document.source(), "{}", context);
+	assert_eq!(document.tree().debug_tree(), fresh.tree().debug_tree(), "{}", context);
+	assert_eq!(document.tokens(), fresh.tokens(), "{}", context);
+
+	let messages = |d: &Document| d.diagnostics().iter().map(|d| (d.message.clone(), d.span)).collect::<Vec<_>>();
+	assert_eq!(messages(&document), messages(&fresh), "{}", context);
+}
+
+#[test]
+fn insertions_match_a_fresh_parse() {
+	for (offset, _) in SOURCE.char_indices().chain(std::iter::once((SOURCE.len(), ' '))) {
+		for text in INSERTIONS.iter() {
+			check(SOURCE, TextEdit::insert(offset, *text));
+		}
+	}
+}
+
+#[test]
+fn deletions_match_a_fresh_parse() {
+	let offsets: Vec<usize> = SOURCE.char_indices().map(|(i, _)| i).chain(std::iter::once(SOURCE.len())).collect();
+	for (i, start) in offsets.iter().enumerate() {
+		for end in offsets[i..].iter().take(4) {
+			check(SOURCE, TextEdit::delete(*start..*end));
+		}
+	}
+}
+
+#[test]
+fn replacements_match_a_fresh_parse() {
+	check(SOURCE, TextEdit::new(0..3, "const"));
+	check(SOURCE, TextEdit::new(0..SOURCE.len(), "let y = 2;"));
+	check(SOURCE, TextEdit::new(18..20, "class C {}\nfn"));
+	check("", TextEdit::insert(0, SOURCE));
+}
+
+#[test]
+fn edits_outside_of_the_document_are_rejected() {
+	let mut document: Document = Document::new("let é = 1;");
+	assert!(!document.edit(&TextEdit::insert(100, "x")));
+	assert!(!document.edit(&TextEdit::insert(5, "x")), "offset 5 is inside of `é`");
+	assert_eq!(document.source(), "let é = 1;");
+}
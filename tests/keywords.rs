@@ -0,0 +1,77 @@
+//! Checks that the keyword table agrees with itself and with the lexer.
+#![allow(clippy::needless_return)]
+use std::convert::TryFrom;
+use typesharp::compiler::typesharp_ast::{ Edition, KeyWord, KeyWordError };
+use typesharp::compiler::typesharp_lexer::{ apply_edition, dump, tokenize, Token, TokenKind };
+use typesharp::util::Json;
+
+/// Lexes a single word, ignoring whitespace.
+fn lex(word: &str) -> TokenKind {
+	let tokens: Vec<Token> = tokenize(word).into_iter().filter(|t| t.kind != TokenKind::WhiteSpace).collect();
+	assert_eq!(tokens.len(), 1, "`{}` lexed as {:?}", word, tokens);
+	return tokens[0].kind.clone();
+}
+
+#[test]
+fn every_keyword_round_trips() {
+	for keyword in KeyWord::ALL.iter() {
+		let word: &str = keyword.as_str();
+		assert_eq!(KeyWord::from_str(word).as_ref(), Ok(keyword), "`{}`", word);
+		assert_eq!(word.parse::<KeyWord>().as_ref(), Ok(keyword), "`{}`", word);
+		assert_eq!(KeyWord::try_from(word).as_ref(), Ok(keyword), "`{}`", word);
+		assert_eq!(keyword.to_string(), word);
+
+		let expected: TokenKind = match keyword {
+			KeyWord::True | KeyWord::False => TokenKind::BoolLiteral(word.to_string()),
+			_ => TokenKind::Keyword(keyword.clone()),
+		};
+		assert_eq!(lex(word), expected);
+	}
+}
+
+#[test]
+fn keywords_are_written_once() {
+	for (i, keyword) in KeyWord::ALL.iter().enumerate() {
+		assert!(!KeyWord::ALL[..i].contains(keyword), "{:?} is listed twice", keyword);
+		assert!(KeyWord::ALL[..i].iter().all(|k| k.as_str() != keyword.as_str()), "`{}` is written twice", keyword);
+	}
+}
+
+#[test]
+fn other_words_are_not_keywords() {
+	for word in ["Asm", "LET", "", "letter", "external_", "true1"].iter() {
+		assert_eq!(KeyWord::from_str(word), Err(KeyWordError(word.to_string())));
+		assert!(word.parse::<KeyWord>().is_err());
+	}
+
+	assert_eq!(lex("letter"), TokenKind::Identifier(String::from("letter")));
+	assert_eq!(lex("external"), TokenKind::Keyword(KeyWord::Extern));
+}
+
+#[test]
+fn keywords_are_names_before_their_edition() {
+	for keyword in KeyWord::ALL.iter() {
+		for edition in Edition::ALL.iter() {
+			let mut tokens: Vec<Token> = tokenize(keyword.as_str());
+			apply_edition(&mut tokens, *edition);
+
+			let reserved: bool = tokens[0].kind != TokenKind::Identifier(keyword.to_string());
+			assert_eq!(reserved, keyword.edition() <= *edition, "`{}` in the {} edition", keyword, edition);
+		}
+	}
+
+	assert!(!Edition::Edition2021.is_reserved(&KeyWord::Do));
+	assert!(Edition::LATEST.is_reserved(&KeyWord::Do));
+}
+
+#[test]
+fn dump_tokens_lists_every_keyword() {
+	let table: Json = dump::keywords_to_json();
+	let rows: &Vec<Json> = table.as_array().unwrap();
+	assert_eq!(rows.len(), KeyWord::ALL.len());
+
+	for (row, keyword) in rows.iter().zip(KeyWord::ALL.iter()) {
+		assert_eq!(row.get("name").and_then(|n| n.as_str()), Some(format!("{:?}", keyword).as_str()));
+		assert_eq!(row.get("keyword").and_then(|k| k.as_str()).map(KeyWord::from_str), Some(Ok(keyword.clone())));
+	}
+}
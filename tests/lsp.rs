@@ -0,0 +1,216 @@
+//! Drives the `typesharp-lsp` binary as an editor would, over stdin and stdout.
+#![allow(clippy::needless_return, clippy::redundant_field_names)]
+use std::io::BufReader;
+use std::process::{ Child, ChildStdout, Command, Stdio };
+use typesharp::lsp::rpc::{ read_message, write_message };
+use typesharp::lsp::LineIndex;
+use typesharp::util::Json;
+
+const URI: &str = "file:///main.t";
+const SOURCE: &str = "let x = 1;\nconst y = x + 2; // sum\nlet s = `${y}`;\n";
+
+struct Client {
+	server: Child,
+	stdout: BufReader<ChildStdout>,
+	next_id: u64,
+}
+
+impl Client {
+	fn start() -> Self {
+		let mut server: Child = Command::new(env!("CARGO_BIN_EXE_typesharp-lsp"))
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.spawn()
+			.expect("failed to start typesharp-lsp");
+		let stdout: BufReader<ChildStdout> = BufReader::new(server.stdout.take().unwrap());
+
+		return Client {
+			server: server,
+			stdout: stdout,
+			next_id: 1,
+		};
+	}
+
+	fn send(&mut self, message: Json) {
+		write_message(self.server.stdin.as_mut().unwrap(), &message).unwrap();
+	}
+
+	fn receive(&mut self) -> Json {
+		let body: String = read_message(&mut self.stdout).unwrap().expect("the server closed stdout");
+		return Json::parse(&body).unwrap();
+	}
+
+	fn notify(&mut self, method: &str, params: Json) {
+		self.send(Json::object(vec![("jsonrpc", Json::from("2.0")), ("method", Json::from(method)), ("params", params)]));
+	}
+
+	/// Sends a request and waits for its response, skipping any notifications.
+	fn request(&mut self, method: &str, params: Json) -> Json {
+		let id: u64 = self.next_id;
+		self.next_id += 1;
+		self.send(Json::object(vec![
+			("jsonrpc", Json::from("2.0")),
+			("id", Json::Number(id as f64)),
+			("method", Json::from(method)),
+			("params", params),
+		]));
+
+		loop {
+			let message: Json = self.receive();
+			if message.get("id").and_then(|i| i.as_u64()) == Some(id) {
+				return message;
+			}
+		}
+	}
+
+	fn exit(mut self) -> i32 {
+		self.notify("exit", Json::Null);
+		return self.server.wait().unwrap().code().unwrap();
+	}
+}
+
+fn document() -> Json {
+	return Json::object(vec![("textDocument", Json::object(vec![("uri", Json::from(URI))]))]);
+}
+
+fn at(line: u32, character: u32) -> Json {
+	let mut params: Json = document();
+	params.insert("position", Json::object(vec![("line", Json::from(line)), ("character", Json::from(character))]));
+	return params;
+}
+
+fn open(client: &mut Client, text: &str) -> Json {
+	let item: Json = Json::object(vec![
+		("uri", Json::from(URI)),
+		("languageId", Json::from("typesharp")),
+		("version", Json::from(1u32)),
+		("text", Json::from(text)),
+	]);
+	client.notify("textDocument/didOpen", Json::object(vec![("textDocument", item)]));
+	return client.receive();
+}
+
+#[test]
+fn initialize_and_shutdown() {
+	let mut client: Client = Client::start();
+
+	let init: Json = client.request("initialize", Json::object(vec![]));
+	let capabilities: &Json = init.path(&["result", "capabilities"]).unwrap();
+	assert_eq!(capabilities.get("textDocumentSync"), Some(&Json::from(2u32)));
+	assert_eq!(capabilities.get("hoverProvider"), Some(&Json::Bool(true)));
+	assert!(capabilities.path(&["semanticTokensProvider", "legend", "tokenTypes"]).is_some());
+
+	let unknown: Json = client.request("textDocument/unknown", Json::Null);
+	assert_eq!(unknown.path(&["error", "code"]).and_then(|c| c.as_f64()), Some(-32601.0));
+
+	assert_eq!(client.request("shutdown", Json::Null).get("result"), Some(&Json::Null));
+	assert_eq!(client.exit(), 0);
+}
+
+#[test]
+fn exit_without_shutdown_fails() {
+	let client: Client = Client::start();
+	assert_eq!(client.exit(), 1);
+}
+
+#[test]
+fn publishes_diagnostics_on_open_and_change() {
+	let mut client: Client = Client::start();
+	client.request("initialize", Json::object(vec![]));
+
+	let published: Json = open(&mut client, "let x = 1 +;\n");
+	assert_eq!(published.get("method").and_then(|m| m.as_str()), Some("textDocument/publishDiagnostics"));
+	let diagnostics: &Vec<Json> = published.path(&["params", "diagnostics"]).and_then(|d| d.as_array()).unwrap();
+	assert_eq!(diagnostics.len(), 1);
+	assert_eq!(diagnostics[0].get("severity"), Some(&Json::from(1u32)));
+	assert_eq!(diagnostics[0].path(&["range", "start", "character"]), Some(&Json::from(11u32)));
+
+	// replace `+` with `+ 2`, fixing the error
+	let range: Json = Json::object(vec![
+		("start", Json::object(vec![("line", Json::from(0u32)), ("character", Json::from(10u32))])),
+		("end", Json::object(vec![("line", Json::from(0u32)), ("character", Json::from(11u32))])),
+	]);
+	let change: Json = Json::object(vec![("range", range), ("text", Json::from("+ 2"))]);
+	let mut params: Json = document();
+	params.insert("contentChanges", Json::Array(vec![change]));
+	client.notify("textDocument/didChange", params);
+
+	let published: Json = client.receive();
+	assert_eq!(published.path(&["params", "diagnostics"]), Some(&Json::Array(Vec::new())));
+
+	client.request("shutdown", Json::Null);
+	assert_eq!(client.exit(), 0);
+}
+
+#[test]
+fn hover_definition_and_symbols() {
+	let mut client: Client = Client::start();
+	client.request("initialize", Json::object(vec![]));
+	open(&mut client, SOURCE);
+
+	// `x` in `const y = x + 2;`
+	let hover: Json = client.request("textDocument/hover", at(1, 10));
+	let value: &str = hover.path(&["result", "contents", "value"]).and_then(|v| v.as_str()).unwrap();
+	assert!(value.contains("let x: i32"), "{}", value);
+
+	let definition: Json = client.request("textDocument/definition", at(1, 10));
+	assert_eq!(definition.path(&["result", "uri"]), Some(&Json::from(URI)));
+	assert_eq!(definition.path(&["result", "range", "start", "line"]), Some(&Json::from(0u32)));
+	assert_eq!(definition.path(&["result", "range", "start", "character"]), Some(&Json::from(4u32)));
+
+	let symbols: Json = client.request("textDocument/documentSymbol", document());
+	let names: Vec<&str> = symbols
+		.get("result")
+		.and_then(|r| r.as_array())
+		.unwrap()
+		.iter()
+		.map(|s| s.get("name").and_then(|n| n.as_str()).unwrap())
+		.collect();
+	assert_eq!(names, vec!["x", "y", "s"]);
+
+	client.request("shutdown", Json::Null);
+	client.exit();
+}
+
+#[test]
+fn semantic_tokens() {
+	let mut client: Client = Client::start();
+	client.request("initialize", Json::object(vec![]));
+	open(&mut client, SOURCE);
+
+	let tokens: Json = client.request("textDocument/semanticTokens/full", document());
+	let data: Vec<u64> = tokens
+		.path(&["result", "data"])
+		.and_then(|d| d.as_array())
+		.unwrap()
+		.iter()
+		.map(|n| n.as_u64().unwrap())
+		.collect();
+	assert_eq!(data.len() % 5, 0);
+
+	#[rustfmt::skip]
+	let first_line: Vec<u64> = vec![
+		0, 0, 3, 0, 0, // let: keyword
+		0, 4, 1, 1, 1, // x: variable, declaration
+		0, 2, 1, 11, 0, // =: operator
+		0, 2, 1, 8, 0, // 1: number
+	];
+	assert_eq!(data[..20], first_line[..]);
+
+	// in `const y = x + 2;` the declaration of `y` is readonly, and `x` is only a use
+	assert_eq!(data[25..30], [0, 6, 1, 1, 3]);
+	assert_eq!(data[35..40], [0, 2, 1, 1, 0]);
+
+	client.request("shutdown", Json::Null);
+	client.exit();
+}
+
+#[test]
+fn line_ends_exclude_carriage_returns() {
+	let index: LineIndex = LineIndex::new("let x = 1;\r\nx = 2;\r\n");
+	assert_eq!(index.offset(0, 10), Some(10));
+	assert_eq!(index.offset(0, 99), Some(10));
+	assert_eq!(index.offset(1, 99), Some(18));
+	assert_eq!(index.offset(2, 0), Some(20));
+	assert_eq!(index.position(18), (1, 6));
+}
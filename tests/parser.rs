@@ -0,0 +1,98 @@
+//! Checks how the parser groups operators, against the precedence table in `typesharp_parser::op`.
+#![allow(clippy::needless_return)]
+use typesharp::compiler::typesharp_ast::{ ast::Module, ToTree };
+use typesharp::compiler::typesharp_lexer::tokenize;
+use typesharp::compiler::typesharp_parser::Parser;
+
+/// Binary operators from the loosest to the tightest, the operators in a level group to the left.
+const LEVELS: &[&[&str]] = &[
+	&["||", "??"],
+	&["&&"],
+	&["|"],
+	&["^"],
+	&["&"],
+	&["==", "!="],
+	&["<", "<=", ">", ">=", "in", "instanceof"],
+	&["<<", ">>", ">>>"],
+	&["+", "-"],
+	&["*", "/", "%"],
+];
+
+const ASSIGNMENTS: &[&str] = &["=", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "<<=", ">>=", ">>>=", "&&=", "||=", "??="];
+
+/// Parses a single expression statement and prints it as an S-expression, eg: `(+ a (* b c))`
+fn parse(source: &str) -> String {
+	let tokens = tokenize(&format!("{};", source));
+	let mut parser: Parser = Parser::new(&tokens);
+	let module: Module = parser.parse_module();
+	assert!(parser.diagnostics.is_empty(), "`{}` has errors: {:?}", source, parser.diagnostics);
+
+	let sexpr: String = module.to_tree().sexpr();
+	return sexpr
+		.strip_prefix("(module (expr ")
+		.and_then(|s| s.strip_suffix("))"))
+		.unwrap_or_else(|| panic!("`{}` is not a single expression: {}", source, sexpr))
+		.to_string();
+}
+
+fn level(op: &str) -> usize {
+	return LEVELS.iter().position(|level| level.contains(&op)).unwrap();
+}
+
+#[test]
+fn binary_operators_group_by_precedence() {
+	for a in LEVELS.iter().flat_map(|l| l.iter()) {
+		for b in LEVELS.iter().flat_map(|l| l.iter()) {
+			let expected: String = match level(a) >= level(b) {
+				true => format!("({} ({} x y) z)", b, a),
+				false => format!("({} x ({} y z))", a, b),
+			};
+			assert_eq!(parse(&format!("x {} y {} z", a, b)), expected, "`x {} y {} z`", a, b);
+		}
+	}
+}
+
+#[test]
+fn assignments_are_loosest_and_group_to_the_right() {
+	for op in ASSIGNMENTS.iter() {
+		assert_eq!(parse(&format!("x {} y = z", op)), format!("({} x (= y z))", op));
+		assert_eq!(parse(&format!("x {} y || z", op)), format!("({} x (|| y z))", op));
+	}
+}
+
+#[test]
+fn parentheses_group_first() {
+	assert_eq!(parse("(x + y) * z"), "(* (paren (+ x y)) z)");
+	assert_eq!(parse("x - (y - z)"), "(- x (paren (- y z)))");
+}
+
+#[test]
+fn prefix_and_postfix_operators() {
+	assert_eq!(parse("++x"), "(++ x)");
+	assert_eq!(parse("x++"), "(post++ x)");
+	assert_eq!(parse("--x"), "(-- x)");
+	assert_eq!(parse("x--"), "(post-- x)");
+
+	// postfix operators bind tighter than prefix ones, which bind tighter than any binary operator
+	assert_eq!(parse("-x++"), "(- (post++ x))");
+	assert_eq!(parse("!x * y"), "(* (! x) y)");
+	assert_eq!(parse("-x.y"), "(- (field x y))");
+	assert_eq!(parse("x++ + y"), "(+ (post++ x) y)");
+	assert_eq!(parse("x - -y"), "(- x (- y))");
+	assert_eq!(parse("f(x)[0].y++"), "(post++ (field (index (call f x) 0) y))");
+}
+
+#[test]
+fn invalid_assignment_targets_are_reported() {
+	let tokens = tokenize("x + y = z;");
+	let mut parser: Parser = Parser::new(&tokens);
+	parser.parse_module();
+	assert_eq!(parser.diagnostics.iter().map(|d| d.message.as_str()).collect::<Vec<_>>(), ["invalid left-hand side of assignment"]);
+}
+
+#[test]
+fn contains_is_an_identifier() {
+	assert_eq!(parse("contains(x)"), "(call contains x)");
+	assert_eq!(parse("contains + x"), "(+ contains x)");
+	assert_eq!(parse("x.contains"), "(field x contains)");
+}
@@ -0,0 +1,54 @@
+//! Checks what the resolver binds names to, beyond what the golden tests can show.
+#![allow(clippy::needless_return)]
+use typesharp::compiler::typesharp_ast::ast::Module;
+use typesharp::compiler::typesharp_lexer::tokenize;
+use typesharp::compiler::typesharp_parser::Parser;
+use typesharp::compiler::typesharp_resolve::{ resolve, DefKind, Resolutions };
+
+fn resolved(source: &str) -> Resolutions {
+	let tokens = tokenize(source);
+	let mut parser: Parser = Parser::new(&tokens);
+	let module: Module = parser.parse_module();
+	assert!(parser.diagnostics.is_empty(), "`{}` has errors: {:?}", source, parser.diagnostics);
+	return resolve(&module).0;
+}
+
+/// The kinds of the declarations the uses in the source are bound to, in the order they are written.
+fn bound_to(source: &str) -> Vec<(String, DefKind)> {
+	let resolutions: Resolutions = resolved(source);
+	let mut uses: Vec<_> = resolutions.all_uses().collect();
+	uses.sort_by_key(|u| u.span.start().offset());
+	return uses.iter().map(|u| (resolutions.defs[&u.def].name.clone(), resolutions.defs[&u.def].kind)).collect();
+}
+
+#[test]
+fn type_parameters_are_not_members() {
+	let found = bound_to("class Box<T> { value: T; }\nlet t = Box.T;\nlet v = Box.value;");
+	assert_eq!(found, [
+		(String::from("T"), DefKind::TypeParam),
+		(String::from("Box"), DefKind::Class),
+		(String::from("Box"), DefKind::Class),
+		(String::from("value"), DefKind::Field),
+	]);
+}
+
+#[test]
+fn package_members_resolve_in_their_package() {
+	let found = bound_to("let top = 1;\npackage a.b;\nlet x = top;\npackage a;\nlet y = 2;\npackage a.b;\nlet z = x;\npackage c;\nlet w = a.b.x;");
+	assert_eq!(found, [
+		(String::from("top"), DefKind::Variable),
+		// declaring `a` and `a.b` again refers to the first declarations
+		(String::from("a"), DefKind::Package),
+		(String::from("a"), DefKind::Package),
+		(String::from("b"), DefKind::Package),
+		(String::from("x"), DefKind::Variable),
+		(String::from("a"), DefKind::Package),
+		(String::from("b"), DefKind::Package),
+		(String::from("x"), DefKind::Variable),
+	]);
+
+	let resolutions: Resolutions = resolved("package a;\nlet x = 1;\npackage b;\nlet x = 2;");
+	let packages: Vec<_> = resolutions.defs.values().filter(|d| d.kind == DefKind::Package).map(|d| d.id).collect();
+	let parents: Vec<_> = resolutions.defs.values().filter(|d| d.name == "x").map(|d| d.parent).collect();
+	assert_eq!(parents, packages.into_iter().map(Some).collect::<Vec<_>>(), "each `x` is in its own package");
+}